@@ -0,0 +1,332 @@
+use std::{
+    cell::RefCell,
+    io::{BufRead, Write},
+    process::ExitCode,
+    rc::Rc,
+};
+
+use pana_lang::{
+    eval::{
+        almacen::Datos,
+        cuadros::{Cuadros, SIN_CUADROS},
+        entrada::Entrada,
+        environment::Environment,
+        evaluator::Evaluator,
+        objects::{Object, ResultObj},
+        reloj::RelojFijo,
+        tarea::{block_on, Tarea},
+    },
+    lexer::Lexer,
+    lienzo::{
+        gif::{self, AnimacionGif, FRAME_DELAY_CS},
+        grabadora::Grabadora,
+        rasterizador::{compose_frame, Rasterizador},
+    },
+    parser::Parser,
+};
+
+const USAGE: &str =
+    "Uso: pana <archivo.pana> [--captura salida.png] [--gif salida.gif] [--ancho N] [--alto N] [--cuadros N] [--reloj SEGUNDOS]";
+
+struct Options {
+    path: String,
+    capture: Option<String>,
+    gif: Option<String>,
+    width: usize,
+    height: usize,
+    frames: usize,
+    // Marca de tiempo fija para `ahora`, `marca_tiempo` y `formatear_fecha`
+    clock: Option<f64>,
+}
+
+fn parse_number(flag: &str, value: Option<String>) -> Result<usize, String> {
+    value
+        .and_then(|value| value.parse().ok())
+        .ok_or(format!("`{}` espera un numero entero positivo", flag))
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut path = None;
+    let mut options = Options {
+        path: String::new(),
+        capture: None,
+        gif: None,
+        width: 320,
+        height: 200,
+        frames: 1,
+        clock: None,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--captura" => {
+                options.capture = Some(args.next().ok_or("`--captura` espera un archivo")?)
+            }
+            "--gif" => options.gif = Some(args.next().ok_or("`--gif` espera un archivo")?),
+            "--ancho" => options.width = parse_number(&arg, args.next())?,
+            "--alto" => options.height = parse_number(&arg, args.next())?,
+            "--cuadros" => options.frames = parse_number(&arg, args.next())?,
+            "--reloj" => {
+                options.clock = Some(
+                    args.next()
+                        .and_then(|value| value.parse().ok())
+                        .ok_or("`--reloj` espera los segundos desde 1970")?,
+                )
+            }
+            _ if arg.starts_with("--") => return Err(format!("Opcion desconocida `{}`", arg)),
+            _ => path = Some(arg),
+        }
+    }
+    options.path = path.ok_or(USAGE)?;
+    if options.width == 0 || options.height == 0 {
+        return Err("El lienzo debe medir al menos 1x1 pixeles".to_owned());
+    }
+    if options.gif.is_some() && (options.width > gif::MAX_SIZE || options.height > gif::MAX_SIZE) {
+        return Err(format!(
+            "Con `--gif` el lienzo mide como maximo {}x{} pixeles",
+            gif::MAX_SIZE,
+            gif::MAX_SIZE
+        ));
+    }
+    Ok(options)
+}
+
+// `preguntar` y `leer_linea` leen de la entrada estandar
+struct EntradaEstandar;
+
+impl Entrada for EntradaEstandar {
+    // La terminal espera la linea sin detener el programa
+    fn leer_linea<'a>(&'a mut self, pregunta: &'a str) -> Tarea<'a, Option<String>> {
+        Box::pin(std::future::ready(read_stdin_line(pregunta)))
+    }
+}
+
+fn read_stdin_line(pregunta: &str) -> Option<String> {
+    if !pregunta.is_empty() {
+        print!("{} ", pregunta);
+        std::io::stdout().flush().ok()?;
+    }
+    let mut line = String::new();
+    match std::io::stdin().lock().read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => {
+            let len = line.trim_end_matches(['\n', '\r']).len();
+            line.truncate(len);
+            Some(line)
+        }
+    }
+}
+
+fn check(res: ResultObj) -> Result<(), String> {
+    match res {
+        ResultObj::Copy(Object::Error(msg)) => Err(msg),
+        _ => Ok(()),
+    }
+}
+
+// Cuadros dibujados sin ventana. Los comparten el ciclo de `Bucle` y las
+// pausas de `esperar` y `siguiente_cuadro`, y entre todos no pasan de
+// `--cuadros`.
+struct Salida {
+    grabadora: Grabadora,
+    width: usize,
+    height: usize,
+    gif: bool,
+    animation: Option<AnimacionGif>,
+    // Ultimo cuadro terminado, para `--captura`
+    frame: Option<Rasterizador>,
+    // Numero del cuadro que se esta dibujando, 0 es el del codigo inicial
+    index: usize,
+    frames: usize,
+    // El programa pidio mas cuadros de los que se iban a dibujar
+    exhausted: bool,
+}
+
+// Cuadros que dura encendido (y apagado) el cursor de texto
+const CURSOR_BLINK_FRAMES: usize = 20;
+
+impl Salida {
+    // Prepara el lienzo del cuadro. En `modo_pantalla` se dibuja directo en
+    // la pantalla, que conserva lo dibujado. En el modo ventana las figuras se
+    // graban para componerlas encima de los pixeles al terminar el cuadro.
+    fn begin_frame(&self, evaluator: &mut Evaluator) {
+        self.grabadora.limpiar();
+        evaluator.lienzo = match evaluator.screen {
+            Some(_) => Some(Box::new(evaluator.ensure_framebuffer())),
+            None => Some(Box::new(self.grabadora.clone())),
+        };
+    }
+
+    // Con `push` el cuadro tambien se agrega a la animacion de `--gif`
+    fn end_frame(&mut self, evaluator: &mut Evaluator, push: bool) {
+        evaluator.draw_turtle();
+        let blink_on = (self.index / CURSOR_BLINK_FRAMES) % 2 == 0;
+        let frame = evaluator.screen_frame(blink_on).unwrap_or_else(|| {
+            compose_frame(
+                self.width,
+                self.height,
+                evaluator.window_frame(blink_on).as_ref(),
+                &self.grabadora,
+            )
+        });
+        if push && self.gif {
+            let animation = self.animation.get_or_insert_with(|| {
+                // `parse_options` revisa el tamano y `modo_pantalla` no pasa de 1920
+                AnimacionGif::new(frame.width(), frame.height(), FRAME_DELAY_CS)
+                    .expect("El cuadro cabe en un GIF")
+            });
+            // Los cuadros de otro tamano no caben en la animacion
+            if (animation.width(), animation.height()) == (frame.width(), frame.height()) {
+                animation.push_frame(&frame);
+            }
+        }
+        self.frame = Some(frame);
+    }
+}
+
+// `esperar` y `siguiente_cuadro` terminan el cuadro actual. Cada cuadro dura
+// lo mismo que un cuadro de la animacion.
+struct CuadrosTerminal(Rc<RefCell<Salida>>);
+
+impl Cuadros for CuadrosTerminal {
+    // Sin ventana el cuadro siguiente empieza enseguida
+    fn siguiente_cuadro<'a>(
+        &'a mut self,
+        evaluator: &'a mut Evaluator,
+        keep: bool,
+    ) -> Tarea<'a, Option<f64>> {
+        Box::pin(std::future::ready(self.next_frame(evaluator, keep)))
+    }
+}
+
+impl CuadrosTerminal {
+    fn next_frame(&mut self, evaluator: &mut Evaluator, keep: bool) -> Option<f64> {
+        let mut salida = self.0.borrow_mut();
+        if salida.index >= salida.frames {
+            salida.exhausted = true;
+            return None;
+        }
+        // Las figuras de este cuadro se repiten en el siguiente, sin la
+        // tortuga que se vuelve a dibujar al final de cada cuadro
+        let previous = Grabadora::new();
+        if keep {
+            salida.grabadora.replay(&mut previous.clone());
+        }
+        salida.end_frame(evaluator, true);
+        salida.index += 1;
+        salida.begin_frame(evaluator);
+        if let Some(lienzo) = evaluator.lienzo.as_mut() {
+            previous.replay(lienzo);
+        }
+        Some(FRAME_DELAY_CS as f64 / 100.0)
+    }
+}
+
+// Archivo de `guardar_dato` junto al programa: `juego.pana` guarda en `juego.datos.json`
+fn data_path(path: &str) -> std::path::PathBuf {
+    std::path::Path::new(path).with_extension("datos.json")
+}
+
+fn load_data(path: &std::path::Path) -> Result<Datos, String> {
+    match std::fs::read_to_string(path) {
+        Ok(text) => Datos::from_json(&text)
+            .map_err(|err| format!("No se pudo leer `{}`: {}", path.display(), err)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Datos::default()),
+        Err(err) => Err(format!("No se pudo leer `{}`: {}", path.display(), err)),
+    }
+}
+
+// Ejecuta el programa y escribe los datos guardados, aunque el programa falle
+fn run(options: &Options) -> Result<(), String> {
+    let path = data_path(&options.path);
+    let datos = load_data(&path)?;
+    let result = run_program(options, datos.clone());
+    if datos.changed() {
+        std::fs::write(&path, datos.to_json())
+            .map_err(|err| format!("No se pudo escribir `{}`: {}", path.display(), err))?;
+    }
+    result
+}
+
+// Ejecuta el programa sin ventana: codigo de nivel superior, `Inicio` y
+// `cuadros` llamadas a `Bucle`, dibujando en el rasterizador. Con `--gif`
+// cada cuadro se agrega a la animacion.
+fn run_program(options: &Options, datos: Datos) -> Result<(), String> {
+    let code = std::fs::read_to_string(&options.path)
+        .map_err(|err| format!("No se pudo leer `{}`: {}", options.path, err))?;
+    let mut parser = Parser::new(Lexer::new(code.chars().collect()));
+    let mut program = parser.parse();
+    if let Some(error) = parser.error {
+        return Err(error.to_string());
+    }
+
+    let salida = Rc::new(RefCell::new(Salida {
+        grabadora: Grabadora::new(),
+        width: options.width,
+        height: options.height,
+        gif: options.gif.is_some(),
+        animation: None,
+        frame: None,
+        index: 0,
+        frames: options.frames,
+        exhausted: false,
+    }));
+    let mut evaluator = Evaluator::new(None, options.width as f32, options.height as f32);
+    evaluator.entrada = Some(Box::new(EntradaEstandar));
+    evaluator.cuadros = Some(Box::new(CuadrosTerminal(salida.clone())));
+    evaluator.datos = Some(datos);
+    if let Some(marca) = options.clock {
+        evaluator.reloj = Some(Box::new(RelojFijo { marca, desfase: 0 }));
+    }
+    let environment = Rc::new(RefCell::new(Environment::new(None)));
+    let loop_fn = evaluator.extract_loop_fn(&mut program).ok();
+    // Quedarse sin cuadros en medio de `esperar` termina el programa sin
+    // error, pero cualquier otro error se sigue reportando
+    let check_frames = |res: ResultObj| match check(res) {
+        Err(msg) if msg == SIN_CUADROS && salida.borrow().exhausted => Ok(()),
+        res => res,
+    };
+
+    evaluator.begin_frame(FRAME_DELAY_CS as f64 / 100.0);
+    salida.borrow().begin_frame(&mut evaluator);
+    check_frames(block_on(evaluator.eval_program(&program, &environment)))?;
+    if let Some(res) = block_on(evaluator.call_hook("Inicio", Vec::new(), &environment)) {
+        check_frames(res)?;
+    }
+    let push = salida.borrow().index > 0;
+    salida.borrow_mut().end_frame(&mut evaluator, push);
+    if let Some(loop_fn) = loop_fn {
+        while !salida.borrow().exhausted && salida.borrow().index < options.frames {
+            salida.borrow_mut().index += 1;
+            evaluator.begin_frame(FRAME_DELAY_CS as f64 / 100.0);
+            salida.borrow().begin_frame(&mut evaluator);
+            let env = Rc::new(RefCell::new(Environment::new(Some(environment.clone()))));
+            check_frames(block_on(evaluator.eval_program(&loop_fn, &env)))?;
+            salida.borrow_mut().end_frame(&mut evaluator, true);
+        }
+    }
+    if let Some(res) = block_on(evaluator.call_hook("AlTerminar", Vec::new(), &environment)) {
+        check_frames(res)?;
+    }
+
+    let mut salida = salida.borrow_mut();
+    if let (Some(capture), Some(frame)) = (&options.capture, &salida.frame) {
+        std::fs::write(capture, frame.to_png())
+            .map_err(|err| format!("No se pudo escribir `{}`: {}", capture, err))?;
+    }
+    if let (Some(gif), Some(animation)) = (&options.gif, salida.animation.take()) {
+        std::fs::write(gif, animation.finish())
+            .map_err(|err| format!("No se pudo escribir `{}`: {}", gif, err))?;
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let result = parse_options(std::env::args().skip(1)).and_then(|options| run(&options));
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            ExitCode::FAILURE
+        }
+    }
+}
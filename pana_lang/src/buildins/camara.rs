@@ -0,0 +1,181 @@
+use crate::{
+    eval::{
+        evaluator::Evaluator,
+        objects::{FnArgs, Object, ResultObj},
+    },
+    types::{Numeric, Vector},
+};
+
+use super::{
+    geometria::{extract_coords, extract_coords_between},
+    missmatch_args,
+};
+
+fn canvas(eval: &Evaluator) -> (f32, f32) {
+    (eval.canvas.width, eval.canvas.height)
+}
+
+// camara_posicion(x, y) -> el punto x, y del mundo queda en el centro del lienzo
+// camara_posicion() -> vector con la posicion actual
+pub fn camara_posicion(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() {
+        let (x, y) = eval.camara.center(canvas(eval));
+        return ResultObj::Copy(Object::Vector(Vector::new(x as f64, y as f64)));
+    }
+    match extract_coords(&args, 2) {
+        Ok(pos) => {
+            eval.camara.center = Some((pos[0] as f32, pos[1] as f32));
+            ResultObj::Copy(Object::Void)
+        }
+        Err(err) => err,
+    }
+}
+
+// camara_zoom(2) -> todo se ve al doble de tamano
+// camara_zoom() -> zoom actual
+pub fn camara_zoom(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() {
+        return ResultObj::Copy(Object::Numeric(Numeric::Float(eval.camara.zoom as f64)));
+    }
+    match extract_coords(&args, 1) {
+        Ok(zoom) if zoom[0] > 0.0 && zoom[0].is_finite() => {
+            eval.camara.zoom = zoom[0] as f32;
+            ResultObj::Copy(Object::Void)
+        }
+        Ok(_) => ResultObj::Copy(Object::Error(
+            "El zoom de la camara debe ser mayor a 0".to_string(),
+        )),
+        Err(err) => err,
+    }
+}
+
+// camara_seguir(x, y, 0.9) -> acerca la camara al punto, el suavizado va de 0
+// (llega de inmediato) a casi 1 (avanza despacio) y es opcional
+pub fn camara_seguir(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_coords_between(&args, 2, 3) {
+        Ok(nums) => {
+            let smoothing = nums.get(2).copied().unwrap_or(0.0) as f32;
+            let canvas = canvas(eval);
+            eval.camara
+                .follow((nums[0] as f32, nums[1] as f32), canvas, smoothing);
+            ResultObj::Copy(Object::Void)
+        }
+        Err(err) => err,
+    }
+}
+
+// usar_camara(falso) -> lo que se dibuje despues no se mueve con la camara,
+// para marcadores y menus. Cada cuadro empieza con la camara activa.
+pub fn usar_camara(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match args[0].clone() {
+        ResultObj::Copy(Object::Boolean(enabled)) => {
+            eval.camera_enabled = enabled;
+            ResultObj::Copy(Object::Void)
+        }
+        obj if eval.is_error(&obj) => obj,
+        obj => ResultObj::Copy(Object::Error(format!(
+            "Se espera un tipo de dato logico, no {}",
+            obj.get_type()
+        ))),
+    }
+}
+
+// camara_a_mundo(x, y) -> vector con el punto del mundo que se ve en la
+// posicion x, y del lienzo, por ejemplo donde se hizo clic
+pub fn camara_a_mundo(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    let pos = match extract_coords(&args, 2) {
+        Ok(pos) => (pos[0] as f32, pos[1] as f32),
+        Err(err) => return err,
+    };
+    match eval.camara.transform(canvas(eval)).invert() {
+        Some(inverse) => {
+            let (x, y) = inverse.apply(pos);
+            ResultObj::Copy(Object::Vector(Vector::new(x as f64, y as f64)))
+        }
+        None => ResultObj::Copy(Object::Null),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(
+        eval: &mut Evaluator,
+        f: fn(&mut Evaluator, FnArgs) -> ResultObj,
+        args: &[f64],
+    ) -> ResultObj {
+        let args = args
+            .iter()
+            .map(|num| ResultObj::Copy(Object::Numeric(Numeric::Float(*num))))
+            .collect();
+        f(eval, args)
+    }
+
+    fn vector(obj: ResultObj) -> (f64, f64) {
+        match obj {
+            ResultObj::Copy(Object::Vector(vector)) => (vector.x, vector.y),
+            obj => panic!("se esperaba un vector, no {}", obj.get_type()),
+        }
+    }
+
+    fn assert_near(a: (f64, f64), b: (f64, f64)) {
+        assert!(
+            (a.0 - b.0).abs() < 1e-3 && (a.1 - b.1).abs() < 1e-3,
+            "{:?} != {:?}",
+            a,
+            b
+        );
+    }
+
+    #[test]
+    fn camara_a_mundo_round_trips() {
+        let mut eval = Evaluator::new(None, 200.0, 100.0);
+        // Sin camara el lienzo y el mundo coinciden
+        assert_near(
+            vector(call(&mut eval, camara_a_mundo, &[30.0, 40.0])),
+            (30.0, 40.0),
+        );
+
+        call(&mut eval, camara_posicion, &[500.0, -250.0]);
+        call(&mut eval, camara_zoom, &[2.5]);
+        assert_near(
+            vector(call(&mut eval, camara_posicion, &[])),
+            (500.0, -250.0),
+        );
+        let ResultObj::Copy(Object::Numeric(Numeric::Float(zoom))) =
+            call(&mut eval, camara_zoom, &[])
+        else {
+            panic!("se esperaba el zoom");
+        };
+        assert_eq!(zoom, 2.5);
+
+        // El centro del lienzo es la posicion de la camara y la esquina esta a
+        // medio lienzo, dividido por el zoom
+        assert_near(
+            vector(call(&mut eval, camara_a_mundo, &[100.0, 50.0])),
+            (500.0, -250.0),
+        );
+        assert_near(
+            vector(call(&mut eval, camara_a_mundo, &[0.0, 0.0])),
+            (460.0, -270.0),
+        );
+
+        // Un punto del mundo dibujado con la camara se lee de vuelta igual
+        for world in [(0.0, 0.0), (500.0, -250.0), (-37.5, 812.25), (1e4, -1e4)] {
+            let (x, y) = eval
+                .view_transform()
+                .apply((world.0 as f32, world.1 as f32));
+            let back = vector(call(&mut eval, camara_a_mundo, &[x as f64, y as f64]));
+            assert!(
+                (back.0 - world.0).abs() < 1e-2 && (back.1 - world.1).abs() < 1e-2,
+                "{:?} != {:?}",
+                back,
+                world
+            );
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{
+    eval::{
+        evaluator::Evaluator,
+        objects::{FnArgs, Object, ResultObj},
+    },
+    lienzo::Color,
+};
+
+use super::{extract_numeric_args, internal::extract_color, missmatch_args, numeric_error, to_f64};
+
+// Constantes de color que se registran como `ROJO`, `AZUL`...
+pub const NAMED_COLORS: [(&str, Color); 14] = [
+    ("NEGRO", Color::new(0x00, 0x00, 0x00, 0xFF)),
+    ("BLANCO", Color::new(0xFF, 0xFF, 0xFF, 0xFF)),
+    ("ROJO", Color::new(0xFF, 0x00, 0x00, 0xFF)),
+    ("VERDE", Color::new(0x00, 0xFF, 0x00, 0xFF)),
+    ("AZUL", Color::new(0x00, 0x00, 0xFF, 0xFF)),
+    ("AMARILLO", Color::new(0xFF, 0xFF, 0x00, 0xFF)),
+    ("CIAN", Color::new(0x00, 0xFF, 0xFF, 0xFF)),
+    ("MAGENTA", Color::new(0xFF, 0x00, 0xFF, 0xFF)),
+    ("NARANJA", Color::new(0xFF, 0x80, 0x00, 0xFF)),
+    ("MORADO", Color::new(0x80, 0x00, 0x80, 0xFF)),
+    ("ROSA", Color::new(0xFF, 0xC0, 0xCB, 0xFF)),
+    ("CAFE", Color::new(0x8B, 0x45, 0x13, 0xFF)),
+    ("GRIS", Color::new(0x80, 0x80, 0x80, 0xFF)),
+    ("TRANSPARENTE", Color::new(0x00, 0x00, 0x00, 0x00)),
+];
+
+fn color_obj(color: Color) -> ResultObj {
+    ResultObj::Copy(Object::Color(color))
+}
+
+// Los componentes se redondean y se limitan a 0..255
+fn channel(v: f64) -> u8 {
+    v.round().clamp(0.0, 255.0) as u8
+}
+
+fn extract_numbers(args: &[ResultObj]) -> Result<Vec<f64>, ResultObj> {
+    Ok(extract_numeric_args(args)?.iter().map(to_f64).collect())
+}
+
+// rgb(255, 128, 0) -> naranja
+pub fn rgb(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 3 {
+        return missmatch_args("3", args.len());
+    }
+    match extract_numbers(&args) {
+        Ok(nums) => color_obj(Color::new(
+            channel(nums[0]),
+            channel(nums[1]),
+            channel(nums[2]),
+            0xFF,
+        )),
+        Err(err) => err,
+    }
+}
+
+// rgba(255, 0, 0, 128) -> rojo semitransparente, alfa de 0 a 255
+pub fn rgba(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 4 {
+        return missmatch_args("4", args.len());
+    }
+    match extract_numbers(&args) {
+        Ok(nums) => color_obj(Color::new(
+            channel(nums[0]),
+            channel(nums[1]),
+            channel(nums[2]),
+            channel(nums[3]),
+        )),
+        Err(err) => err,
+    }
+}
+
+// hsv(120, 1, 1) -> verde. El tono va en grados, la saturacion y el valor de
+// 0 a 1. Acepta un alfa opcional de 0 a 255.
+pub fn hsv(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 3 && args.len() != 4 {
+        return missmatch_args("3 o 4", args.len());
+    }
+    let nums = match extract_numbers(&args) {
+        Ok(nums) => nums,
+        Err(err) => return err,
+    };
+    let hue = nums[0].rem_euclid(360.0) / 60.0;
+    let saturation = nums[1].clamp(0.0, 1.0);
+    let value = nums[2].clamp(0.0, 1.0);
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (r, g, b) = match hue as u8 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = value - chroma;
+    color_obj(Color::new(
+        channel((r + m) * 255.0),
+        channel((g + m) * 255.0),
+        channel((b + m) * 255.0),
+        nums.get(3).map_or(0xFF, |&alpha| channel(alpha)),
+    ))
+}
+
+// mezclar_color(ROJO, AZUL, 0.5) -> el color a medio camino, `t` de 0 a 1
+pub fn mezclar_color(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 3 {
+        return missmatch_args("3", args.len());
+    }
+    let a = match extract_color(args[0].clone()) {
+        Ok(color) => color,
+        Err(err) => return err,
+    };
+    let b = match extract_color(args[1].clone()) {
+        Ok(color) => color,
+        Err(err) => return err,
+    };
+    let t = match args[2].clone() {
+        ResultObj::Copy(Object::Numeric(num)) => to_f64(&num).clamp(0.0, 1.0),
+        _ => return numeric_error(),
+    };
+    color_obj(a.mix(b, t))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        eval::{
+            environment::Environment, evaluator::Evaluator, objects::ResultObj, tarea::block_on,
+        },
+        lexer::Lexer,
+        parser::Parser,
+    };
+
+    fn run(code: &str) -> String {
+        let mut parser = Parser::new(Lexer::new(code.chars().collect()));
+        let program = parser.parse();
+        assert!(parser.error.is_none(), "{}", code);
+        let mut eval = Evaluator::new(None, 100.0, 80.0);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        match block_on(eval.eval_program(&program, &env)) {
+            ResultObj::Copy(obj) => obj.to_string(),
+            ResultObj::Ref(obj) => obj.borrow().to_string(),
+        }
+    }
+
+    #[test]
+    fn rgb_clamps_and_rounds_channels() {
+        assert_eq!(run("rgb(-10, 300, 127.6);"), "rgb(0, 255, 128)");
+        assert_eq!(run("rgba(0, 0, 0, 999);"), "rgb(0, 0, 0)");
+        assert_eq!(run("rgba(10, 20, 30, -1);"), "rgba(10, 20, 30, 0)");
+        assert!(run("rgb(1, 2);").contains("argumentos"));
+    }
+
+    #[test]
+    fn hsv_hue_wraps_around() {
+        assert_eq!(run("hsv(0, 1, 1);"), "rgb(255, 0, 0)");
+        assert_eq!(run("hsv(360, 1, 1);"), "rgb(255, 0, 0)");
+        assert_eq!(run("hsv(480, 1, 1);"), "rgb(0, 255, 0)");
+        assert_eq!(run("hsv(-120, 1, 1);"), "rgb(0, 0, 255)");
+        assert_eq!(run("hsv(120, 2, -1);"), "rgb(0, 0, 0)");
+    }
+
+    #[test]
+    fn black_is_not_transparent() {
+        assert_eq!(run("NEGRO;"), "rgb(0, 0, 0)");
+        assert_eq!(run("TRANSPARENTE;"), "rgba(0, 0, 0, 0)");
+        assert_eq!(run("rgb(0, 0, 0) == NEGRO;"), "verdad");
+        assert_eq!(run("NEGRO == TRANSPARENTE;"), "falso");
+        // Un numero 0x000000 se lee como negro opaco
+        assert_eq!(run("mezclar_color(0x000000, NEGRO, 0.5);"), "rgb(0, 0, 0)");
+        assert_eq!(
+            run("mezclar_color(0x000000, TRANSPARENTE, 0.5);"),
+            "rgba(0, 0, 0, 128)"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+use crate::eval::{
+    almacen::Datos,
+    evaluator::Evaluator,
+    json::Json,
+    objects::{FnArgs, Object, ResultObj},
+};
+
+use super::missmatch_args;
+
+fn datos(eval: &Evaluator) -> Result<Datos, ResultObj> {
+    eval.datos.clone().ok_or(ResultObj::Copy(Object::Error(
+        "Guardar datos no esta disponible en esta aplicacion".to_string(),
+    )))
+}
+
+fn extract_key(obj: ResultObj) -> Result<String, ResultObj> {
+    match obj {
+        ResultObj::Copy(Object::Error(msg)) => Err(ResultObj::Copy(Object::Error(msg))),
+        ResultObj::Copy(Object::String(key)) => Ok(key),
+        ResultObj::Ref(obj) => match &*obj.borrow() {
+            Object::String(key) => Ok(key.clone()),
+            obj => Err(ResultObj::Copy(Object::Error(format!(
+                "La llave debe ser una cadena, no {}",
+                obj.get_type()
+            )))),
+        },
+        ResultObj::Copy(obj) => Err(ResultObj::Copy(Object::Error(format!(
+            "La llave debe ser una cadena, no {}",
+            obj.get_type()
+        )))),
+    }
+}
+
+// guardar_dato("record", 1200) -> el valor sigue ahi la proxima vez que corra el programa
+pub fn guardar_dato(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 {
+        return missmatch_args("2", args.len());
+    }
+    let key = match extract_key(args[0].clone()) {
+        Ok(key) => key,
+        Err(err) => return err,
+    };
+    let value = args[1].clone();
+    if let ResultObj::Copy(Object::Error(_)) = value {
+        return value;
+    }
+    let json = match Json::from_obj(&value) {
+        Ok(json) => json,
+        Err(msg) => return ResultObj::Copy(Object::Error(msg)),
+    };
+    let datos = match datos(eval) {
+        Ok(datos) => datos,
+        Err(err) => return err,
+    };
+    match datos.guardar(&key, json) {
+        Ok(()) => ResultObj::Copy(Object::Void),
+        Err(msg) => ResultObj::Copy(Object::Error(msg)),
+    }
+}
+
+// cargar_dato("record", 0) -> el valor guardado, o 0 si nunca se guardo
+pub fn cargar_dato(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() || args.len() > 2 {
+        return missmatch_args("1 o 2", args.len());
+    }
+    let key = match extract_key(args[0].clone()) {
+        Ok(key) => key,
+        Err(err) => return err,
+    };
+    let datos = match datos(eval) {
+        Ok(datos) => datos,
+        Err(err) => return err,
+    };
+    match (datos.cargar(&key), args.get(1)) {
+        (Some(json), _) => json.to_obj(),
+        (None, Some(default)) => default.clone(),
+        (None, None) => ResultObj::Copy(Object::Null),
+    }
+}
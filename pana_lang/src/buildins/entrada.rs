@@ -0,0 +1,45 @@
+use crate::eval::{
+    evaluator::Evaluator,
+    objects::{new_rc_object, FnArgs, Object, ResultObj},
+    tarea::Tarea,
+};
+
+use super::missmatch_args;
+
+// Detiene el programa hasta que la aplicacion entrega una linea de texto
+async fn read_line(eval: &mut Evaluator, question: &str) -> ResultObj {
+    let Some(entrada) = eval.entrada.as_mut() else {
+        return ResultObj::Copy(Object::Error(
+            "Leer texto no esta disponible en esta aplicacion".to_string(),
+        ));
+    };
+    match entrada.leer_linea(question).await {
+        Some(line) => ResultObj::Ref(new_rc_object(Object::String(line))),
+        None => ResultObj::Copy(Object::Error("No hay mas texto para leer".to_string())),
+    }
+}
+
+// nombre = preguntar("¿Como te llamas?")
+pub fn preguntar(eval: &mut Evaluator, args: FnArgs) -> Tarea<'_, ResultObj> {
+    Box::pin(async move {
+        if args.len() != 1 {
+            return missmatch_args("1", args.len());
+        }
+        let question = match args[0].clone() {
+            ResultObj::Copy(Object::Error(msg)) => return ResultObj::Copy(Object::Error(msg)),
+            ResultObj::Copy(obj) => obj.to_string(),
+            ResultObj::Ref(obj) => obj.borrow().to_string(),
+        };
+        read_line(eval, &question).await
+    })
+}
+
+// linea = leer_linea()
+pub fn leer_linea(eval: &mut Evaluator, args: FnArgs) -> Tarea<'_, ResultObj> {
+    Box::pin(async move {
+        if !args.is_empty() {
+            return missmatch_args("0", args.len());
+        }
+        read_line(eval, "").await
+    })
+}
@@ -0,0 +1,251 @@
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    eval::{
+        evaluator::Evaluator,
+        objects::{new_rc_object, FnArgs, Object, ResultObj},
+    },
+    types::Numeric,
+};
+
+use super::{missmatch_args, numeric_error};
+
+// Fecha del calendario, ya con el desfase del reloj a la hora local
+struct Fecha {
+    anio: i64,
+    mes: i64,
+    dia: i64,
+    hora: i64,
+    minuto: i64,
+    segundo: i64,
+}
+
+impl Fecha {
+    // Desde los segundos locales contados desde 1970. Convierte los dias con el
+    // algoritmo de Howard Hinnant, que sirve tambien antes de 1970.
+    fn new(seconds: f64) -> Self {
+        let seconds = seconds.floor() as i64;
+        let days = seconds.div_euclid(86_400);
+        let time = seconds.rem_euclid(86_400);
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let mes = if mp < 10 { mp + 3 } else { mp - 9 };
+        Self {
+            anio: yoe + era * 400 + i64::from(mes <= 2),
+            mes,
+            dia: doy - (153 * mp + 2) / 5 + 1,
+            hora: time / 3600,
+            minuto: time % 3600 / 60,
+            segundo: time % 60,
+        }
+    }
+
+    // %Y anio, %m mes, %d dia, %H hora, %M minuto, %S segundo y %% un `%`
+    fn format(&self, format: &str) -> Result<String, String> {
+        let mut out = String::new();
+        let mut chars = format.chars();
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                out.push(c);
+                continue;
+            }
+            match chars.next() {
+                Some('Y') => write!(out, "{:04}", self.anio).unwrap(),
+                Some('m') => write!(out, "{:02}", self.mes).unwrap(),
+                Some('d') => write!(out, "{:02}", self.dia).unwrap(),
+                Some('H') => write!(out, "{:02}", self.hora).unwrap(),
+                Some('M') => write!(out, "{:02}", self.minuto).unwrap(),
+                Some('S') => write!(out, "{:02}", self.segundo).unwrap(),
+                Some('%') => out.push('%'),
+                Some(c) => return Err(format!("El formato `%{}` no existe", c)),
+                None => return Err("El formato termina con un `%` solo".to_string()),
+            }
+        }
+        Ok(out)
+    }
+}
+
+// Segundos UTC y desfase de la hora local
+fn read_clock(eval: &Evaluator) -> Result<(f64, i64), ResultObj> {
+    match eval.reloj.as_deref() {
+        Some(reloj) => Ok((reloj.marca_tiempo(), reloj.desfase())),
+        None => Err(ResultObj::Copy(Object::Error(
+            "Leer la hora no esta disponible en esta aplicacion".to_string(),
+        ))),
+    }
+}
+
+// marca_tiempo() -> segundos desde el 1 de enero de 1970, con decimales
+pub fn marca_tiempo(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    match read_clock(eval) {
+        Ok((seconds, _)) => ResultObj::Copy(Object::Numeric(Numeric::Float(seconds))),
+        Err(err) => err,
+    }
+}
+
+// ahora() -> {"anio": 2024, "mes": 3, "dia": 9, "hora": 17, "minuto": 5, "segundo": 42}
+pub fn ahora(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    let fecha = match read_clock(eval) {
+        Ok((seconds, offset)) => Fecha::new(seconds + offset as f64),
+        Err(err) => return err,
+    };
+    let fields = [
+        ("anio", fecha.anio),
+        ("mes", fecha.mes),
+        ("dia", fecha.dia),
+        ("hora", fecha.hora),
+        ("minuto", fecha.minuto),
+        ("segundo", fecha.segundo),
+    ];
+    let pairs: HashMap<_, _> = fields
+        .into_iter()
+        .map(|(key, value)| {
+            (
+                ResultObj::Ref(new_rc_object(Object::String(key.to_owned()))),
+                ResultObj::Copy(Object::Numeric(Numeric::Int(value))),
+            )
+        })
+        .collect();
+    ResultObj::Ref(new_rc_object(Object::Dictionary(pairs)))
+}
+
+// formatear_fecha("%d/%m/%Y %H:%M") -> "09/03/2024 17:05"
+// formatear_fecha("%H:%M", marca) -> la hora de otra marca de tiempo
+pub fn formatear_fecha(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() || args.len() > 2 {
+        return missmatch_args("1 o 2", args.len());
+    }
+    let format = match args[0].clone() {
+        ResultObj::Copy(Object::Error(msg)) => return ResultObj::Copy(Object::Error(msg)),
+        ResultObj::Copy(Object::String(format)) => format,
+        ResultObj::Ref(obj) if matches!(&*obj.borrow(), Object::String(_)) => {
+            obj.borrow().to_string()
+        }
+        _ => {
+            return ResultObj::Copy(Object::Error(
+                "Se espera un tipo de dato cadena".to_string(),
+            ))
+        }
+    };
+    let seconds = match args.get(1).cloned() {
+        None => None,
+        Some(ResultObj::Copy(Object::Numeric(Numeric::Int(n)))) => Some(n as f64),
+        Some(ResultObj::Copy(Object::Numeric(Numeric::Float(n)))) => Some(n),
+        Some(_) => return numeric_error(),
+    };
+    let (now, offset) = match read_clock(eval) {
+        Ok(clock) => clock,
+        Err(err) => return err,
+    };
+    match Fecha::new(seconds.unwrap_or(now) + offset as f64).format(&format) {
+        Ok(text) => ResultObj::Ref(new_rc_object(Object::String(text))),
+        Err(msg) => ResultObj::Copy(Object::Error(msg)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::{
+        eval::{environment::Environment, reloj::RelojFijo, tarea::block_on},
+        lexer::Lexer,
+        parser::Parser,
+    };
+
+    fn date(seconds: f64) -> String {
+        Fecha::new(seconds).format("%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn run(code: &str, marca: f64, desfase: i64) -> String {
+        let mut parser = Parser::new(Lexer::new(code.chars().collect()));
+        let program = parser.parse();
+        assert!(parser.error.is_none());
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        eval.reloj = Some(Box::new(RelojFijo { marca, desfase }));
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        match block_on(eval.eval_program(&program, &env)) {
+            ResultObj::Copy(obj) => obj.to_string(),
+            ResultObj::Ref(obj) => obj.borrow().to_string(),
+        }
+    }
+
+    #[test]
+    fn epoch() {
+        assert_eq!(date(0.0), "1970-01-01 00:00:00");
+        assert_eq!(date(0.9), "1970-01-01 00:00:00");
+    }
+
+    #[test]
+    fn negative_timestamps() {
+        assert_eq!(date(-1.0), "1969-12-31 23:59:59");
+        assert_eq!(date(-0.5), "1969-12-31 23:59:59");
+        assert_eq!(date(-31_536_000.0), "1969-01-01 00:00:00");
+        assert_eq!(date(-62_135_596_800.0), "0001-01-01 00:00:00");
+    }
+
+    #[test]
+    fn leap_days() {
+        assert_eq!(date(951_782_400.0), "2000-02-29 00:00:00");
+        assert_eq!(date(1_709_164_800.0), "2024-02-29 00:00:00");
+        // 2100 no es bisiesto
+        assert_eq!(date(4_107_542_399.0), "2100-02-28 23:59:59");
+        assert_eq!(date(4_107_542_400.0), "2100-03-01 00:00:00");
+    }
+
+    #[test]
+    fn format_specifiers() {
+        let fecha = Fecha::new(1_709_985_942.0);
+        assert_eq!(fecha.format("%d/%m/%Y %H:%M").unwrap(), "09/03/2024 12:05");
+        assert_eq!(fecha.format("100%%").unwrap(), "100%");
+        assert!(fecha.format("%q").is_err());
+        assert!(fecha.format("%").is_err());
+    }
+
+    #[test]
+    fn marca_tiempo_reads_clock() {
+        assert_eq!(run("marca_tiempo();", 1_709_996_742.5, 0), "1709996742.5");
+    }
+
+    #[test]
+    fn formatear_fecha_with_offset() {
+        let code = r#"formatear_fecha("%Y-%m-%d %H:%M:%S");"#;
+        assert_eq!(run(code, 1_709_996_742.5, 0), "2024-03-09 15:05:42");
+        assert_eq!(run(code, 1_709_996_742.5, -3 * 3600), "2024-03-09 12:05:42");
+        // El desfase puede cambiar el dia
+        assert_eq!(run(code, 0.0, -3600), "1969-12-31 23:00:00");
+    }
+
+    #[test]
+    fn formatear_fecha_with_timestamp() {
+        let code = r#"formatear_fecha("%d/%m/%Y", 951782400);"#;
+        assert_eq!(run(code, 0.0, 0), "29/02/2000");
+        assert_eq!(run(code, 0.0, 3600), "29/02/2000");
+    }
+
+    #[test]
+    fn ahora_uses_offset() {
+        assert_eq!(run(r#"ahora()["anio"];"#, 0.0, -3600), "1969");
+        assert_eq!(run(r#"ahora()["hora"];"#, 0.0, -3600), "23");
+        assert_eq!(run(r#"ahora()["mes"];"#, 951_782_400.0, 0), "2");
+        assert_eq!(run(r#"ahora()["dia"];"#, 951_782_400.0, 0), "29");
+    }
+
+    #[test]
+    fn without_clock() {
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        eval.reloj = None;
+        assert!(read_clock(&eval).is_err());
+    }
+}
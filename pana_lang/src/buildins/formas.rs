@@ -0,0 +1,313 @@
+use crate::{
+    eval::{
+        evaluator::Evaluator,
+        objects::{FnArgs, Object, ResultObj},
+    },
+    lienzo::{formas::Estilo, Color, Lienzo},
+};
+
+use super::{
+    internal::{extract_color, extract_f32_from_numeric},
+    missmatch_args, numeric_error,
+};
+
+// Extrae `count` numeros seguidos de un color opcional
+fn extract_shape_args(
+    args: &[ResultObj],
+    count: usize,
+) -> Result<(Vec<f32>, Option<Color>), ResultObj> {
+    if args.len() != count && args.len() != count + 1 {
+        return Err(missmatch_args(
+            &format!("{} o {}", count, count + 1),
+            args.len(),
+        ));
+    }
+    let mut nums = Vec::with_capacity(count);
+    let mut color = None;
+    for (i, arg) in args.iter().enumerate() {
+        match arg.clone() {
+            ResultObj::Copy(Object::Numeric(num)) if i < count => {
+                nums.push(extract_f32_from_numeric(num))
+            }
+            _ if i < count => return Err(numeric_error()),
+            color_obj => color = Some(extract_color(color_obj)?),
+        }
+    }
+    Ok((nums, color))
+}
+
+// Convierte [[x, y], [x, y], ...] en una lista de puntos
+fn extract_points(obj: ResultObj) -> Result<Vec<(f32, f32)>, ResultObj> {
+    let points_error = || {
+        ResultObj::Copy(Object::Error(
+            "Se espera una lista de puntos [[x, y], ...]".to_string(),
+        ))
+    };
+    let ResultObj::Ref(list) = obj else {
+        return Err(points_error());
+    };
+    let Object::List(items) = &*list.borrow() else {
+        return Err(points_error());
+    };
+    let mut points = Vec::with_capacity(items.len());
+    for item in items {
+        let ResultObj::Ref(point) = item else {
+            return Err(points_error());
+        };
+        let Object::List(coords) = &*point.borrow() else {
+            return Err(points_error());
+        };
+        match coords.as_slice() {
+            [ResultObj::Copy(Object::Numeric(x)), ResultObj::Copy(Object::Numeric(y))] => points
+                .push((
+                    extract_f32_from_numeric(x.clone()),
+                    extract_f32_from_numeric(y.clone()),
+                )),
+            _ => return Err(points_error()),
+        }
+    }
+    Ok(points)
+}
+
+// Dibuja con el estilo actual y el color opcional de la figura
+fn draw(
+    eval: &mut Evaluator,
+    color: Option<Color>,
+    f: impl FnOnce(&Estilo, &mut dyn Lienzo),
+) -> ResultObj {
+    let estilo = eval.estilo.with_color(color);
+    eval.draw(|lienzo| f(&estilo, lienzo));
+    ResultObj::Copy(Object::Void)
+}
+
+// dibujar_linea(0, 0, 50, 50);
+pub fn dibujar_linea(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    let (nums, color) = match extract_shape_args(&args, 4) {
+        Ok(values) => values,
+        Err(err) => return err,
+    };
+    let (color, width) = eval.estilo.line_stroke(color);
+    eval.draw(|lienzo| lienzo.line((nums[0], nums[1]), (nums[2], nums[3]), width, color));
+    ResultObj::Copy(Object::Void)
+}
+
+// dibujar_rectangulo(0, 0, 100, 100)
+pub fn dibujar_rectangulo(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    let (nums, color) = match extract_shape_args(&args, 4) {
+        Ok(values) => values,
+        Err(err) => return err,
+    };
+    let min = (nums[0].min(nums[2]), nums[1].min(nums[3]));
+    let max = (nums[0].max(nums[2]), nums[1].max(nums[3]));
+    draw(eval, color, |estilo, lienzo| {
+        estilo.draw_rect(lienzo, min, max)
+    })
+}
+
+// dibujar_circulo(0, 0, 40, 0xff0000)
+pub fn dibujar_circulo(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    let (nums, color) = match extract_shape_args(&args, 3) {
+        Ok(values) => values,
+        Err(err) => return err,
+    };
+    draw(eval, color, |estilo, lienzo| {
+        estilo.draw_ellipse(lienzo, (nums[0], nums[1]), (nums[2], nums[2]))
+    })
+}
+
+// dibujar_elipse(x, y, radio_x, radio_y)
+pub fn dibujar_elipse(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    let (nums, color) = match extract_shape_args(&args, 4) {
+        Ok(values) => values,
+        Err(err) => return err,
+    };
+    draw(eval, color, |estilo, lienzo| {
+        estilo.draw_ellipse(lienzo, (nums[0], nums[1]), (nums[2], nums[3]))
+    })
+}
+
+// dibujar_arco(x, y, radio, inicio, fin) -> angulos en radianes
+pub fn dibujar_arco(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    let (nums, color) = match extract_shape_args(&args, 5) {
+        Ok(values) => values,
+        Err(err) => return err,
+    };
+    draw(eval, color, |estilo, lienzo| {
+        estilo.draw_arc(lienzo, (nums[0], nums[1]), nums[2], nums[3], nums[4])
+    })
+}
+
+// dibujar_triangulo(x1, y1, x2, y2, x3, y3)
+pub fn dibujar_triangulo(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    let (nums, color) = match extract_shape_args(&args, 6) {
+        Ok(values) => values,
+        Err(err) => return err,
+    };
+    let points = [(nums[0], nums[1]), (nums[2], nums[3]), (nums[4], nums[5])];
+    draw(eval, color, |estilo, lienzo| {
+        estilo.draw_polygon(lienzo, &points)
+    })
+}
+
+// dibujar_poligono([[0, 0], [50, 0], [25, 40]])
+pub fn dibujar_poligono(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() || args.len() > 2 {
+        return missmatch_args("1 o 2", args.len());
+    }
+    let points_obj = args[0].clone();
+    let points = match extract_points(points_obj) {
+        Ok(points) => points,
+        Err(err) => return err,
+    };
+    let color = match args.get(1).cloned() {
+        None => None,
+        Some(color_obj) => match extract_color(color_obj) {
+            Ok(color) => Some(color),
+            Err(err) => return err,
+        },
+    };
+    if points.len() < 3 {
+        return ResultObj::Copy(Object::Error(
+            "Un poligono necesita al menos 3 puntos".to_string(),
+        ));
+    }
+    draw(eval, color, |estilo, lienzo| {
+        estilo.draw_polygon(lienzo, &points)
+    })
+}
+
+// relleno(0xFF0000) -> las figuras se rellenan de rojo
+pub fn relleno(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match extract_color(args[0].clone()) {
+        Ok(color) => {
+            eval.estilo.fill = Some(color);
+            ResultObj::Copy(Object::Void)
+        }
+        Err(err) => err,
+    }
+}
+
+pub fn sin_relleno(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    eval.estilo.fill = None;
+    ResultObj::Copy(Object::Void)
+}
+
+// trazo(0xFFFFFF) o trazo(0xFFFFFF, 3) -> borde de las figuras y grosor de las lineas
+pub fn trazo(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() || args.len() > 2 {
+        return missmatch_args("1 o 2", args.len());
+    }
+    let color = match extract_color(args[0].clone()) {
+        Ok(color) => color,
+        Err(err) => return err,
+    };
+    let width = match args.get(1).cloned() {
+        None => 1.0,
+        Some(ResultObj::Copy(Object::Numeric(num))) => extract_f32_from_numeric(num),
+        Some(_) => return numeric_error(),
+    };
+    if width <= 0.0 {
+        return ResultObj::Copy(Object::Error(
+            "El grosor del trazo debe ser mayor a 0".to_string(),
+        ));
+    }
+    eval.estilo.stroke = Some((color, width));
+    ResultObj::Copy(Object::Void)
+}
+
+pub fn sin_trazo(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    eval.estilo.stroke = None;
+    ResultObj::Copy(Object::Void)
+}
+
+// redondeo(8) -> radio de las esquinas de los rectangulos
+pub fn redondeo(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match args[0].clone() {
+        ResultObj::Copy(Object::Numeric(num)) => {
+            eval.estilo.radius = extract_f32_from_numeric(num).max(0.0);
+            ResultObj::Copy(Object::Void)
+        }
+        _ => numeric_error(),
+    }
+}
+
+// estilo_inicial() -> relleno blanco, sin trazo y sin redondeo
+pub fn estilo_inicial(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    eval.estilo = Estilo::default();
+    ResultObj::Copy(Object::Void)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        eval::{
+            environment::Environment, evaluator::Evaluator, objects::ResultObj, tarea::block_on,
+        },
+        lexer::Lexer,
+        lienzo::grabadora::{Comando, Grabadora},
+        parser::Parser,
+    };
+
+    // Dibuja el programa en una grabadora y retorna su resultado y las figuras
+    fn draw(code: &str) -> (String, Vec<Comando>) {
+        let grabadora = Grabadora::new();
+        let mut eval = Evaluator::new(Some(Box::new(grabadora.clone())), 100.0, 100.0);
+        let mut parser = Parser::new(Lexer::new(code.chars().collect()));
+        let program = parser.parse();
+        assert!(parser.error.is_none(), "{}", code);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        let res = match block_on(eval.eval_program(&program, &env)) {
+            ResultObj::Copy(obj) => obj.to_string(),
+            ResultObj::Ref(obj) => obj.borrow().to_string(),
+        };
+        (res, grabadora.comandos())
+    }
+
+    #[test]
+    fn polygon_with_fewer_than_three_points() {
+        for points in ["[]", "[[0, 0]]", "[[0, 0], [50, 0]]"] {
+            let (res, comandos) = draw(&format!("dibujar_poligono({});", points));
+            assert!(res.contains("al menos 3 puntos"), "{}: {}", points, res);
+            assert!(comandos.is_empty(), "{}", points);
+        }
+        // Con un color invalido el error es el del color
+        let (res, _) = draw("dibujar_poligono([[0, 0]], \"rojo\");");
+        assert!(!res.contains("al menos 3 puntos"), "{}", res);
+    }
+
+    #[test]
+    fn polygon_with_three_points() {
+        let (res, comandos) = draw("dibujar_poligono([[0, 0], [50, 0], [25, 40]]);");
+        assert_eq!(res, "");
+        assert!(comandos.iter().any(|comando| matches!(
+            comando,
+            Comando::Poligono { points, .. } if points.len() == 3
+        )));
+    }
+
+    #[test]
+    fn polygon_points_must_be_pairs() {
+        for points in ["[[0, 0], [50], [25, 40]]", "[[0, 0], 5, [25, 40]]", "5"] {
+            let (res, comandos) = draw(&format!("dibujar_poligono({});", points));
+            assert!(res.contains("lista de puntos"), "{}: {}", points, res);
+            assert!(comandos.is_empty(), "{}", points);
+        }
+    }
+}
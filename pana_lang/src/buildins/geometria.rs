@@ -0,0 +1,200 @@
+use crate::{
+    eval::{
+        evaluator::Evaluator,
+        objects::{FnArgs, Object, ResultObj},
+    },
+    types::{Numeric, Vector},
+};
+
+use super::{error, missmatch_args, to_f64};
+
+fn float_obj(v: f64) -> ResultObj {
+    ResultObj::Copy(Object::Numeric(Numeric::Float(v)))
+}
+
+fn bool_obj(v: bool) -> ResultObj {
+    ResultObj::Copy(Object::Boolean(v))
+}
+
+// Extrae las coordenadas de los argumentos, cada vector cuenta como dos:
+// distancia(0, 0, 3, 4) es lo mismo que distancia(vector(0, 0), vector(3, 4))
+pub fn extract_coords(args: &[ResultObj], expected: usize) -> Result<Vec<f64>, ResultObj> {
+    extract_coords_between(args, expected, expected)
+}
+
+// Como `extract_coords`, con `min` a `max` coordenadas
+pub fn extract_coords_between(
+    args: &[ResultObj],
+    min: usize,
+    max: usize,
+) -> Result<Vec<f64>, ResultObj> {
+    let mut coords = Vec::with_capacity(max);
+    let mut vectors = false;
+    for arg in args {
+        match arg {
+            ResultObj::Copy(Object::Numeric(num)) => coords.push(to_f64(num)),
+            ResultObj::Copy(Object::Vector(vector)) => {
+                vectors = true;
+                coords.extend([vector.x, vector.y]);
+            }
+            ResultObj::Copy(Object::Error(err)) => {
+                return Err(ResultObj::Copy(Object::Error(err.clone())))
+            }
+            _ => {
+                return Err(ResultObj::Copy(Object::Error(
+                    "Se espera un tipo de dato numerico o vector".to_string(),
+                )))
+            }
+        }
+    }
+    if (min..=max).contains(&coords.len()) {
+        return Ok(coords);
+    }
+    let expected = match min == max {
+        true => min.to_string(),
+        false => format!("{} o {}", min, max),
+    };
+    if vectors {
+        return Err(error(format!(
+            "Se encontro {} coordenadas de {}, cada vector cuenta como dos",
+            coords.len(),
+            expected
+        )));
+    }
+    Err(missmatch_args(&expected, args.len()))
+}
+
+// Esquinas opuestas de un rectangulo, en cualquier orden, como en `dibujar_rectangulo`
+fn rect(coords: &[f64]) -> ((f64, f64), (f64, f64)) {
+    (
+        (coords[0].min(coords[2]), coords[1].min(coords[3])),
+        (coords[0].max(coords[2]), coords[1].max(coords[3])),
+    )
+}
+
+// vector(3, 4) -> vector(3, 4)
+pub fn vector(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_coords(&args, 2) {
+        Ok(coords) => ResultObj::Copy(Object::Vector(Vector::new(coords[0], coords[1]))),
+        Err(err) => err,
+    }
+}
+
+// distancia(0, 0, 3, 4) -> 5
+pub fn distancia(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_coords(&args, 4) {
+        Ok(c) => float_obj((c[2] - c[0]).hypot(c[3] - c[1])),
+        Err(err) => err,
+    }
+}
+
+// angulo_entre(0, 0, 0, 10) -> PI / 2, en radianes y en el sentido del reloj
+pub fn angulo_entre(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_coords(&args, 4) {
+        Ok(c) => float_obj((c[3] - c[1]).atan2(c[2] - c[0])),
+        Err(err) => err,
+    }
+}
+
+// Los rectangulos cubren de la esquina menor hasta antes de la mayor, como los
+// pixeles y los tiles: un punto en el borde derecho o inferior queda fuera y
+// las figuras que solo se tocan no colisionan.
+
+// punto_en_rectangulo(px, py, x1, y1, x2, y2)
+pub fn punto_en_rectangulo(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    let coords = match extract_coords(&args, 6) {
+        Ok(coords) => coords,
+        Err(err) => return err,
+    };
+    let (min, max) = rect(&coords[2..]);
+    bool_obj((min.0..max.0).contains(&coords[0]) && (min.1..max.1).contains(&coords[1]))
+}
+
+// colisionan_rectangulos(x1, y1, x2, y2, x3, y3, x4, y4)
+pub fn colisionan_rectangulos(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    let coords = match extract_coords(&args, 8) {
+        Ok(coords) => coords,
+        Err(err) => return err,
+    };
+    let (a_min, a_max) = rect(&coords[..4]);
+    let (b_min, b_max) = rect(&coords[4..]);
+    bool_obj(a_min.0 < b_max.0 && b_min.0 < a_max.0 && a_min.1 < b_max.1 && b_min.1 < a_max.1)
+}
+
+// colisionan_circulos(x1, y1, radio1, x2, y2, radio2), los circulos que solo se
+// tocan tampoco colisionan
+pub fn colisionan_circulos(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_coords(&args, 6) {
+        Ok(c) => bool_obj((c[3] - c[0]).hypot(c[4] - c[1]) < c[2].abs() + c[5].abs()),
+        Err(err) => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(f: fn(&mut Evaluator, FnArgs) -> ResultObj, coords: &[i64]) -> bool {
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        let args = coords
+            .iter()
+            .map(|c| ResultObj::Copy(Object::Numeric(Numeric::Int(*c))))
+            .collect();
+        match f(&mut eval, args) {
+            ResultObj::Copy(Object::Boolean(b)) => b,
+            obj => panic!("se esperaba un logico, no {}", obj.get_type()),
+        }
+    }
+
+    #[test]
+    fn point_on_rectangle_edges() {
+        let inside = |x, y| check(punto_en_rectangulo, &[x, y, 0, 0, 10, 10]);
+        assert!(inside(5, 5));
+        assert!(inside(0, 0));
+        assert!(inside(0, 9));
+        assert!(!inside(10, 5));
+        assert!(!inside(5, 10));
+        assert!(!inside(-1, 5));
+        // Las esquinas se pueden dar en cualquier orden
+        assert!(check(punto_en_rectangulo, &[0, 0, 10, 10, 0, 0]));
+        assert!(!check(punto_en_rectangulo, &[10, 10, 10, 10, 0, 0]));
+    }
+
+    #[test]
+    fn touching_rectangles_do_not_collide() {
+        let collide = |b: [i64; 4]| {
+            check(
+                colisionan_rectangulos,
+                &[0, 0, 10, 10, b[0], b[1], b[2], b[3]],
+            )
+        };
+        assert!(collide([5, 5, 15, 15]));
+        assert!(collide([9, 0, 20, 10]));
+        assert!(!collide([10, 0, 20, 10]));
+        assert!(!collide([0, 10, 10, 20]));
+        assert!(!collide([10, 10, 20, 20]));
+        assert!(!collide([-10, 0, 0, 10]));
+    }
+
+    #[test]
+    fn touching_circles_do_not_collide() {
+        assert!(check(colisionan_circulos, &[0, 0, 5, 9, 0, 5]));
+        assert!(!check(colisionan_circulos, &[0, 0, 5, 10, 0, 5]));
+        assert!(!check(colisionan_circulos, &[0, 0, 5, 6, 8, 5]));
+        assert!(check(colisionan_circulos, &[0, 0, 5, 6, 7, 5]));
+    }
+
+    // Un punto en el borde cae en el mismo rectangulo con que colisiona un
+    // rectangulo de 1x1 en esa posicion
+    #[test]
+    fn point_and_rectangle_agree() {
+        for x in -1..=11 {
+            assert_eq!(
+                check(punto_en_rectangulo, &[x, 0, 0, 0, 10, 10]),
+                check(colisionan_rectangulos, &[x, 0, x + 1, 1, 0, 0, 10, 10]),
+                "x = {}",
+                x
+            );
+        }
+    }
+}
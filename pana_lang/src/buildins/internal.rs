@@ -1,50 +1,34 @@
 use std::time::UNIX_EPOCH;
 
+use crate::types::Numeric;
 use crate::{
     eval::{
-        environment::RcEnvironment,
         evaluator::Evaluator,
-        objects::{new_rc_object, Object, ResultObj},
+        objects::{new_rc_object, FnArgs, Object, ResultObj},
+        tarea::Tarea,
     },
-    parser::expression::{ExprType, Expression},
+    lienzo::{paleta::C64, rasterizador::BACKGROUND, Color},
 };
-use crate::{parser::expression::FnParams, types::Numeric};
 
-use super::rng::Rng;
+use super::{error, missmatch_args, rng::Rng};
 
-pub trait InternalFnPointer: Fn(&mut Evaluator, FnParams, &RcEnvironment) -> ResultObj {
-    fn clone_box<'a>(&self) -> Box<dyn 'a + InternalFnPointer>
-    where
-        Self: 'a;
-}
-
-impl<F> InternalFnPointer for F
-where
-    F: Fn(&mut Evaluator, FnParams, &RcEnvironment) -> ResultObj + Clone,
-{
-    fn clone_box<'a>(&self) -> Box<dyn 'a + InternalFnPointer>
-    where
-        Self: 'a,
-    {
-        Box::new(self.clone())
-    }
-}
-
-impl<'a> Clone for Box<dyn 'a + InternalFnPointer> {
-    fn clone(&self) -> Self {
-        (**self).clone_box()
-    }
+// Funcion interna del lenguaje. Recibe los argumentos ya evaluados.
+#[derive(Clone, Copy)]
+pub enum InternalFn {
+    Sync(fn(&mut Evaluator, FnArgs) -> ResultObj),
+    // Las que pueden detener el programa, como `preguntar` o `esperar`
+    Async(for<'a> fn(&'a mut Evaluator, FnArgs) -> Tarea<'a, ResultObj>),
 }
 
 // Funcion que retorna la longitud de un string o array
-pub fn longitud(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> ResultObj {
+pub fn longitud(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
     if args.len() != 1 {
         return ResultObj::Copy(Object::Error(format!(
             "Se encontro {} argumentos de 1",
             args.len()
         )));
     }
-    let arg_obj = eval.eval_expression(args.get(0).unwrap(), env);
+    let arg_obj = args[0].clone();
     match arg_obj {
         ResultObj::Copy(obj) => ResultObj::Copy(Object::Error(format!(
             "Se espera un tipo de dato cadena, no {}",
@@ -83,14 +67,14 @@ pub fn longitud(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> Re
 // }
 
 // Funcion que retorna el tipo de dato del objeto
-pub fn tipo(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> ResultObj {
+pub fn tipo(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
     if args.len() != 1 {
         return ResultObj::Copy(Object::Error(format!(
             "Se encontro {} argumentos de 1",
             args.len()
         )));
     }
-    let arg_obj = eval.eval_expression(args.get(0).unwrap(), env);
+    let arg_obj = args[0].clone();
     match arg_obj {
         ResultObj::Copy(obj) => ResultObj::Ref(new_rc_object(Object::String(obj.get_type()))),
         ResultObj::Ref(obj) => {
@@ -99,14 +83,30 @@ pub fn tipo(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> Result
     }
 }
 
-pub fn cadena(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> ResultObj {
+// Comentario `###` escrito antes de `fn nombre`, o nulo si no tiene
+pub fn documentacion(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match &args[0] {
+        ResultObj::Copy(Object::Fn(fn_obj)) => match &fn_obj.doc {
+            Some(doc) => ResultObj::Ref(new_rc_object(Object::String(doc.clone()))),
+            None => ResultObj::Copy(Object::Null),
+        },
+        ResultObj::Copy(Object::FnExpr(_) | Object::BuildinFn(_)) => ResultObj::Copy(Object::Null),
+        ResultObj::Copy(Object::Error(msg)) => error(msg.clone()),
+        obj => error(format!("Se espera una funcion, no {}", obj.get_type())),
+    }
+}
+
+pub fn cadena(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
     if args.len() != 1 {
         return ResultObj::Copy(Object::Error(format!(
             "Se encontro {} argumentos de 1",
             args.len()
         )));
     }
-    let arg_obj = eval.eval_expression(args.get(0).unwrap(), env);
+    let arg_obj = args[0].clone();
     match arg_obj {
         ResultObj::Copy(obj) => ResultObj::Ref(new_rc_object(Object::String(obj.to_string()))),
         ResultObj::Ref(obj) => {
@@ -115,57 +115,72 @@ pub fn cadena(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> Resu
     }
 }
 
-fn extract_f32_from_numeric(num: Numeric) -> f32 {
+pub fn extract_f32_from_numeric(num: Numeric) -> f32 {
     match num {
         Numeric::Int(v) => v as f32,
         Numeric::Float(v) => v as f32,
     }
 }
 
-fn extract_u32_from_numeric(num: Numeric) -> u32 {
+pub fn extract_u32_from_numeric(num: Numeric) -> u32 {
     match num {
         Numeric::Int(v) => v as u32,
         Numeric::Float(v) => v as u32,
     }
 }
 
-fn set_alpha_on_u32(n: u32) -> u32 {
+pub fn set_alpha_on_u32(n: u32) -> u32 {
     if n > 0xFFFFFF {
         return n;
     }
     ((n << 8) & 0xFFFFFF00) | 0x000000FF
 }
 
-fn extract_rgba(n: u32) -> (u8, u8, u8, u8) {
-    ((n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, (n) as u8)
+// 0xRRGGBB (opaco) o 0xRRGGBBAA
+pub fn extract_rgba(num: Numeric) -> Color {
+    Color::from_rgba_u32(set_alpha_on_u32(extract_u32_from_numeric(num)))
+}
+
+// Un valor de color, como los de `rgb` o `ROJO`, o un numero 0xRRGGBB[AA]
+pub fn extract_color(obj: ResultObj) -> Result<Color, ResultObj> {
+    match obj {
+        ResultObj::Copy(Object::Color(color)) => Ok(color),
+        ResultObj::Copy(Object::Numeric(num)) => Ok(extract_rgba(num)),
+        obj => Err(ResultObj::Copy(Object::Error(format!(
+            "Se espera un color, no {}",
+            obj.get_type()
+        )))),
+    }
 }
 
-const DEFAULT_COLOR_EXPR: ExprType = ExprType::NumericLiteral(Numeric::Int(0xFFFFFFFF));
+const DEFAULT_COLOR: Numeric = Numeric::Int(0xFFFFFFFF);
 
 //                      texto, x, y, tamano de fuente
 // dibujar_texto("hola mundo", 0, 0, 14);
-pub fn dibujar_texto(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> ResultObj {
+pub fn dibujar_texto(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
     if args.len() < 4 || args.len() > 5 {
         return ResultObj::Copy(Object::Error(format!(
             "Se encontro {} argumentos de 4 o 5",
             args.len()
         )));
     }
-    let text_obj = eval.eval_expression(args.get(0).unwrap(), env);
-    let pos_x_obj = eval.eval_expression(args.get(1).unwrap(), env);
-    let pos_y_obj = eval.eval_expression(args.get(2).unwrap(), env);
-    let font_size_obj = eval.eval_expression(args.get(3).unwrap(), env);
-    let color_obj = eval.eval_expression(
-        args.get(4)
-            .unwrap_or(&Expression::new(DEFAULT_COLOR_EXPR, 0, 0)),
-        env,
-    );
+    let text_obj = args[0].clone();
+    let pos_x_obj = args[1].clone();
+    let pos_y_obj = args[2].clone();
+    let font_size_obj = args[3].clone();
+    let color_obj = args
+        .get(4)
+        .cloned()
+        .unwrap_or(ResultObj::Copy(Object::Numeric(DEFAULT_COLOR)));
 
     let text: String;
     let pos_x: f32;
     let pos_y: f32;
     let font_size: f32;
-    let color: u32;
+    let color = match extract_color(color_obj) {
+        Ok(color) => color,
+        Err(err) => return err,
+    };
 
     match text_obj {
         ResultObj::Copy(obj) => {
@@ -187,17 +202,15 @@ pub fn dibujar_texto(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment)
         },
     };
 
-    match (pos_x_obj, pos_y_obj, font_size_obj, color_obj) {
+    match (pos_x_obj, pos_y_obj, font_size_obj) {
         (
             ResultObj::Copy(Object::Numeric(pos_x_num)),
             ResultObj::Copy(Object::Numeric(pos_y_num)),
             ResultObj::Copy(Object::Numeric(font_size_num)),
-            ResultObj::Copy(Object::Numeric(color_num)),
         ) => {
             pos_x = extract_f32_from_numeric(pos_x_num);
             pos_y = extract_f32_from_numeric(pos_y_num);
             font_size = extract_f32_from_numeric(font_size_num);
-            color = set_alpha_on_u32(extract_u32_from_numeric(color_num));
         }
         _ => {
             return ResultObj::Copy(Object::Error(
@@ -206,228 +219,231 @@ pub fn dibujar_texto(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment)
         }
     };
 
-    let rgba = extract_rgba(color);
-    if let Some(painter) = eval.painter.as_mut() {
-        let galley = painter.layout(
-            text,
-            egui::FontId::monospace(font_size),
-            egui::Color32::from_rgba_unmultiplied(rgba.0, rgba.1, rgba.2, rgba.3),
-            eval.canvas.width,
-        );
-        painter.galley(egui::Pos2::new(pos_x, pos_y + eval.canvas.top), galley);
-    }
+    eval.draw(|lienzo| lienzo.text(&text, (pos_x, pos_y), font_size, color));
 
     ResultObj::Copy(Object::Void)
 }
 
-// dibujar_linea(0, 0, 50, 50);
-pub fn dibujar_linea(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> ResultObj {
-    if args.len() < 4 || args.len() > 5 {
+// aleatorio(0, 100) -> [0, 100]
+pub fn aleatorio(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 {
         return ResultObj::Copy(Object::Error(format!(
-            "Se encontro {} argumentos de 4 o 5",
+            "Se encontro {} argumentos de 2",
             args.len()
         )));
     }
+    let min_obj = args[0].clone();
+    let max_obj = args[1].clone();
 
-    let pos1_x_obj = eval.eval_expression(args.get(0).unwrap(), env);
-    let pos1_y_obj = eval.eval_expression(args.get(1).unwrap(), env);
-    let pos2_x_obj = eval.eval_expression(args.get(2).unwrap(), env);
-    let pos2_y_obj = eval.eval_expression(args.get(3).unwrap(), env);
-    let color_obj = eval.eval_expression(
-        args.get(4)
-            .unwrap_or(&Expression::new(DEFAULT_COLOR_EXPR, 0, 0)),
-        env,
-    );
-
-    let pos1_x: f32;
-    let pos1_y: f32;
-    let pos2_x: f32;
-    let pos2_y: f32;
-    let color: u32;
-
-    match (pos1_x_obj, pos1_y_obj, pos2_x_obj, pos2_y_obj, color_obj) {
-        (
-            ResultObj::Copy(Object::Numeric(pos1_x_num)),
-            ResultObj::Copy(Object::Numeric(pos1_y_num)),
-            ResultObj::Copy(Object::Numeric(pos2_x_num)),
-            ResultObj::Copy(Object::Numeric(pos2_y_num)),
-            ResultObj::Copy(Object::Numeric(color_num)),
-        ) => {
-            pos1_x = extract_f32_from_numeric(pos1_x_num);
-            pos1_y = extract_f32_from_numeric(pos1_y_num);
-            pos2_x = extract_f32_from_numeric(pos2_x_num);
-            pos2_y = extract_f32_from_numeric(pos2_y_num);
-            color = set_alpha_on_u32(extract_u32_from_numeric(color_num));
-        }
-        _ => {
-            return ResultObj::Copy(Object::Error(
-                "Se espera un tipo de dato numerico".to_string(),
-            ))
+    match (min_obj, max_obj) {
+        (ResultObj::Copy(Object::Numeric(min_num)), ResultObj::Copy(Object::Numeric(max_num))) => {
+            let mut rng = Rng::new(
+                std::time::SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs(),
+            );
+            match (min_num, max_num) {
+                (Numeric::Int(a), Numeric::Int(b)) => {
+                    ResultObj::Copy(Object::Numeric(Numeric::Int(rng.rand_range_i64(a, b))))
+                }
+                (Numeric::Int(a), Numeric::Float(b)) => ResultObj::Copy(Object::Numeric(
+                    Numeric::Int(rng.rand_range_i64(a, b as i64)),
+                )),
+                (Numeric::Float(a), Numeric::Int(b)) => ResultObj::Copy(Object::Numeric(
+                    Numeric::Int(rng.rand_range_i64(a as i64, b)),
+                )),
+                (Numeric::Float(a), Numeric::Float(b)) => ResultObj::Copy(Object::Numeric(
+                    Numeric::Int(rng.rand_range_i64(a as i64, b as i64)),
+                )),
+            }
         }
+        _ => ResultObj::Copy(Object::Error(
+            "Se espera un tipo de dato numerico".to_string(),
+        )),
     }
+}
 
-    let rgba = extract_rgba(color);
-    if let Some(painter) = eval.painter.as_mut() {
-        painter.line_segment(
-            [
-                egui::Pos2::new(pos1_x, pos1_y + eval.canvas.top),
-                egui::Pos2::new(pos2_x, pos2_y + eval.canvas.top),
-            ],
-            egui::Stroke::new(
-                1.0,
-                egui::Color32::from_rgba_unmultiplied(rgba.0, rgba.1, rgba.2, rgba.3),
-            ),
-        )
+pub fn lienzo_ancho(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return ResultObj::Copy(Object::Error(format!(
+            "Se encontro {} argumentos de 0",
+            args.len()
+        )));
     }
-    ResultObj::Copy(Object::Void)
+
+    ResultObj::Copy(Object::Numeric(Numeric::Float(eval.canvas.width as f64)))
 }
 
-// dibujar_rectangulo(0, 0, 100, 100)
-pub fn dibujar_rectangulo(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> ResultObj {
-    if args.len() < 4 || args.len() > 5 {
+pub fn lienzo_altura(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
         return ResultObj::Copy(Object::Error(format!(
-            "Se encontro {} argumentos de 4 o 5",
+            "Se encontro {} argumentos de 0",
             args.len()
         )));
     }
 
-    let pos1_x_obj = eval.eval_expression(args.get(0).unwrap(), env);
-    let pos1_y_obj = eval.eval_expression(args.get(1).unwrap(), env);
-    let pos2_x_obj = eval.eval_expression(args.get(2).unwrap(), env);
-    let pos2_y_obj = eval.eval_expression(args.get(3).unwrap(), env);
-    // let filled_obj = eval.eval_expression(args.get(4).unwrap(), env);
-    let color_obj = eval.eval_expression(
-        args.get(4)
-            .unwrap_or(&Expression::new(DEFAULT_COLOR_EXPR, 0, 0)),
-        env,
-    );
-
-    let pos1_x: f32;
-    let pos1_y: f32;
-    let pos2_x: f32;
-    let pos2_y: f32;
-    let color: u32;
-
-    match (pos1_x_obj, pos1_y_obj, pos2_x_obj, pos2_y_obj, color_obj) {
+    ResultObj::Copy(Object::Numeric(Numeric::Float(eval.canvas.height as f64)))
+}
+
+// Resolucion maxima de `modo_pantalla`
+const MAX_SCREEN_SIZE: i64 = 1920;
+
+// modo_pantalla(320, 200) -> pantalla de 320x200 pixeles
+// modo_pantalla() -> vuelve al tamano de la ventana
+pub fn modo_pantalla(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() {
+        eval.screen = None;
+        eval.framebuffer = None;
+        eval.text = None;
+        return ResultObj::Copy(Object::Void);
+    }
+    if args.len() != 2 {
+        return ResultObj::Copy(Object::Error(format!(
+            "Se encontro {} argumentos de 0 o 2",
+            args.len()
+        )));
+    }
+    let width_obj = args[0].clone();
+    let height_obj = args[1].clone();
+
+    let (width, height) = match (width_obj, height_obj) {
         (
-            ResultObj::Copy(Object::Numeric(pos1_x_num)),
-            ResultObj::Copy(Object::Numeric(pos1_y_num)),
-            ResultObj::Copy(Object::Numeric(pos2_x_num)),
-            ResultObj::Copy(Object::Numeric(pos2_y_num)),
-            ResultObj::Copy(Object::Numeric(color_num)),
-        ) => {
-            pos1_x = extract_f32_from_numeric(pos1_x_num);
-            pos1_y = extract_f32_from_numeric(pos1_y_num);
-            pos2_x = extract_f32_from_numeric(pos2_x_num);
-            pos2_y = extract_f32_from_numeric(pos2_y_num);
-            color = set_alpha_on_u32(extract_u32_from_numeric(color_num));
-        }
+            ResultObj::Copy(Object::Numeric(Numeric::Int(width))),
+            ResultObj::Copy(Object::Numeric(Numeric::Int(height))),
+        ) => (width, height),
         _ => {
             return ResultObj::Copy(Object::Error(
-                "Se espera un tipo de dato numerico".to_string(),
+                "Se espera un tipo de dato numerico entero".to_string(),
             ))
         }
+    };
+    if !(1..=MAX_SCREEN_SIZE).contains(&width) || !(1..=MAX_SCREEN_SIZE).contains(&height) {
+        return ResultObj::Copy(Object::Error(format!(
+            "La pantalla debe medir entre 1 y {} pixeles",
+            MAX_SCREEN_SIZE
+        )));
     }
 
-    let rgba = extract_rgba(color);
-    if let Some(painter) = eval.painter.as_mut() {
-        painter.rect_filled(
-            egui::Rect::from_two_pos(
-                egui::Pos2::new(pos1_x, pos1_y + eval.canvas.top),
-                egui::Pos2::new(pos2_x, pos2_y + eval.canvas.top),
-            ),
-            egui::Rounding::ZERO,
-            egui::Color32::from_rgba_unmultiplied(rgba.0, rgba.1, rgba.2, rgba.3),
-        );
-    }
+    eval.set_screen(width as usize, height as usize);
     ResultObj::Copy(Object::Void)
 }
 
-// dibujar_circulo(0, 0, 40, 0xff0000)
-pub fn dibujar_circulo(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> ResultObj {
-    if args.len() < 3 || args.len() > 4 {
+// color(5) -> verde de la paleta de 16 colores
+pub fn color(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return ResultObj::Copy(Object::Error(format!(
+            "Se encontro {} argumentos de 1",
+            args.len()
+        )));
+    }
+    match args[0].clone() {
+        ResultObj::Copy(Object::Numeric(Numeric::Int(index))) if (0..16).contains(&index) => {
+            ResultObj::Copy(Object::Color(C64[index as usize]))
+        }
+        ResultObj::Copy(Object::Numeric(Numeric::Int(index))) => ResultObj::Copy(Object::Error(
+            format!("La paleta tiene 16 colores, de 0 a 15, no {}", index),
+        )),
+        _ => ResultObj::Copy(Object::Error(
+            "Se espera un tipo de dato numerico entero".to_string(),
+        )),
+    }
+}
+
+// color_borde(color(14))
+pub fn color_borde(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
         return ResultObj::Copy(Object::Error(format!(
-            "Se encontro {} argumentos de 3 o 4",
+            "Se encontro {} argumentos de 1",
             args.len()
         )));
     }
+    let color = match extract_color(args[0].clone()) {
+        Ok(color) => color,
+        Err(err) => return err,
+    };
+    match eval.screen.as_mut() {
+        Some(screen) => {
+            screen.border = color;
+            ResultObj::Copy(Object::Void)
+        }
+        None => ResultObj::Copy(Object::Error(
+            "El borde solo existe en `modo_pantalla`".to_string(),
+        )),
+    }
+}
 
-    let pos_x_obj = eval.eval_expression(args.get(0).unwrap(), env);
-    let pos_y_obj = eval.eval_expression(args.get(1).unwrap(), env);
-    let radius_obj = eval.eval_expression(args.get(2).unwrap(), env);
-    let color_obj = eval.eval_expression(
-        args.get(3)
-            .unwrap_or(&Expression::new(DEFAULT_COLOR_EXPR, 0, 0)),
-        env,
-    );
+fn extract_i64_from_numeric(num: Numeric) -> i64 {
+    match num {
+        Numeric::Int(v) => v,
+        Numeric::Float(v) => v.floor() as i64,
+    }
+}
 
-    let pos_x: f32;
-    let pos_y: f32;
-    let radius: f32;
-    let color: u32;
+// poner_pixel(x, y, color)
+pub fn poner_pixel(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() < 2 || args.len() > 3 {
+        return ResultObj::Copy(Object::Error(format!(
+            "Se encontro {} argumentos de 2 o 3",
+            args.len()
+        )));
+    }
+    let pos_x_obj = args[0].clone();
+    let pos_y_obj = args[1].clone();
+    let color_obj = args
+        .get(2)
+        .cloned()
+        .unwrap_or(ResultObj::Copy(Object::Numeric(DEFAULT_COLOR)));
+
+    let color = match extract_color(color_obj) {
+        Ok(color) => color,
+        Err(err) => return err,
+    };
 
-    match (pos_x_obj, pos_y_obj, radius_obj, color_obj) {
+    match (pos_x_obj, pos_y_obj) {
         (
             ResultObj::Copy(Object::Numeric(pos_x_num)),
             ResultObj::Copy(Object::Numeric(pos_y_num)),
-            ResultObj::Copy(Object::Numeric(radius_num)),
-            ResultObj::Copy(Object::Numeric(color_num)),
         ) => {
-            pos_x = extract_f32_from_numeric(pos_x_num);
-            pos_y = extract_f32_from_numeric(pos_y_num);
-            radius = extract_f32_from_numeric(radius_num);
-            color = set_alpha_on_u32(extract_u32_from_numeric(color_num));
-        }
-        _ => {
-            return ResultObj::Copy(Object::Error(
-                "Se espera un tipo de dato numerico".to_string(),
-            ))
+            eval.ensure_framebuffer().set_pixel(
+                extract_i64_from_numeric(pos_x_num),
+                extract_i64_from_numeric(pos_y_num),
+                color,
+            );
+            ResultObj::Copy(Object::Void)
         }
+        _ => ResultObj::Copy(Object::Error(
+            "Se espera un tipo de dato numerico".to_string(),
+        )),
     }
-
-    let rgba = extract_rgba(color);
-    if let Some(painter) = eval.painter.as_mut() {
-        painter.circle_filled(
-            egui::Pos2::new(pos_x, pos_y + eval.canvas.top),
-            radius,
-            egui::Color32::from_rgba_unmultiplied(rgba.0, rgba.1, rgba.2, rgba.3),
-        );
-    }
-    ResultObj::Copy(Object::Void)
 }
 
-// aleatorio(0, 100) -> [0, 100]
-pub fn aleatorio(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> ResultObj {
+// obtener_pixel(x, y) -> el color del pixel, como `rgb(255, 0, 0)`, o nulo fuera de la pantalla
+pub fn obtener_pixel(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
     if args.len() != 2 {
         return ResultObj::Copy(Object::Error(format!(
             "Se encontro {} argumentos de 2",
             args.len()
         )));
     }
-    let min_obj = eval.eval_expression(args.get(0).unwrap(), env);
-    let max_obj = eval.eval_expression(args.get(1).unwrap(), env);
+    let pos_x_obj = args[0].clone();
+    let pos_y_obj = args[1].clone();
 
-    match (min_obj, max_obj) {
-        (ResultObj::Copy(Object::Numeric(min_num)), ResultObj::Copy(Object::Numeric(max_num))) => {
-            let mut rng = Rng::new(
-                std::time::SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs(),
+    match (pos_x_obj, pos_y_obj) {
+        (
+            ResultObj::Copy(Object::Numeric(pos_x_num)),
+            ResultObj::Copy(Object::Numeric(pos_y_num)),
+        ) => {
+            let (x, y) = (
+                extract_i64_from_numeric(pos_x_num),
+                extract_i64_from_numeric(pos_y_num),
             );
-            match (min_num, max_num) {
-                (Numeric::Int(a), Numeric::Int(b)) => {
-                    ResultObj::Copy(Object::Numeric(Numeric::Int(rng.rand_range_i64(a, b))))
-                }
-                (Numeric::Int(a), Numeric::Float(b)) => ResultObj::Copy(Object::Numeric(
-                    Numeric::Int(rng.rand_range_i64(a, b as i64)),
-                )),
-                (Numeric::Float(a), Numeric::Int(b)) => ResultObj::Copy(Object::Numeric(
-                    Numeric::Int(rng.rand_range_i64(a as i64, b)),
-                )),
-                (Numeric::Float(a), Numeric::Float(b)) => ResultObj::Copy(Object::Numeric(
-                    Numeric::Int(rng.rand_range_i64(a as i64, b as i64)),
-                )),
+            if x < 0 || y < 0 {
+                return ResultObj::Copy(Object::Null);
+            }
+            match eval.ensure_framebuffer().get_pixel(x as usize, y as usize) {
+                Some(color) => ResultObj::Copy(Object::Color(color)),
+                None => ResultObj::Copy(Object::Null),
             }
         }
         _ => ResultObj::Copy(Object::Error(
@@ -436,24 +452,259 @@ pub fn aleatorio(eval: &mut Evaluator, args: FnParams, env: &RcEnvironment) -> R
     }
 }
 
-pub fn lienzo_ancho(eval: &mut Evaluator, args: FnParams, _env: &RcEnvironment) -> ResultObj {
-    if !args.is_empty() {
+// limpiar_pantalla() o limpiar_pantalla(color(6))
+pub fn limpiar_pantalla(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() > 1 {
         return ResultObj::Copy(Object::Error(format!(
-            "Se encontro {} argumentos de 0",
+            "Se encontro {} argumentos de 0 o 1",
             args.len()
         )));
     }
+    let color = match args.get(0).cloned() {
+        None => BACKGROUND,
+        Some(color_obj) => match extract_color(color_obj) {
+            Ok(color) => color,
+            Err(err) => return err,
+        },
+    };
+    eval.ensure_framebuffer().clear(color);
+    if let Some(text) = eval.text.as_mut() {
+        text.clear();
+    }
+    ResultObj::Copy(Object::Void)
+}
 
-    ResultObj::Copy(Object::Numeric(Numeric::Float(eval.canvas.width as f64)))
+fn extract_cell(args: &[ResultObj]) -> Option<(i64, i64)> {
+    let col_obj = args[0].clone();
+    let row_obj = args[1].clone();
+    match (col_obj, row_obj) {
+        (ResultObj::Copy(Object::Numeric(col)), ResultObj::Copy(Object::Numeric(row))) => {
+            Some((extract_i64_from_numeric(col), extract_i64_from_numeric(row)))
+        }
+        _ => None,
+    }
 }
 
-pub fn lienzo_altura(eval: &mut Evaluator, args: FnParams, _env: &RcEnvironment) -> ResultObj {
-    if !args.is_empty() {
+// ubicar(col, fila) -> mueve el cursor de texto
+pub fn ubicar(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 {
         return ResultObj::Copy(Object::Error(format!(
-            "Se encontro {} argumentos de 0",
+            "Se encontro {} argumentos de 2",
             args.len()
         )));
     }
+    let Some((col, row)) = extract_cell(&args) else {
+        return ResultObj::Copy(Object::Error(
+            "Se espera un tipo de dato numerico".to_string(),
+        ));
+    };
+    let text = eval.ensure_text();
+    if col < 0 || row < 0 || !text.locate(col as usize, row as usize) {
+        return ResultObj::Copy(Object::Error(format!(
+            "La posicion ({}, {}) esta fuera de la pantalla de {}x{} caracteres",
+            col,
+            row,
+            text.cols(),
+            text.rows()
+        )));
+    }
+    ResultObj::Copy(Object::Void)
+}
 
-    ResultObj::Copy(Object::Numeric(Numeric::Float(eval.canvas.height as f64)))
+// escribir("hola") -> escribe y pasa a la siguiente linea
+// escribir("hola", falso) -> deja el cursor despues del texto
+pub fn escribir(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() || args.len() > 2 {
+        return ResultObj::Copy(Object::Error(format!(
+            "Se encontro {} argumentos de 1 o 2",
+            args.len()
+        )));
+    }
+    let value = match args[0].clone() {
+        ResultObj::Copy(Object::Error(msg)) => return ResultObj::Copy(Object::Error(msg)),
+        ResultObj::Copy(obj) => obj.to_string(),
+        ResultObj::Ref(obj) => obj.borrow().to_string(),
+    };
+    let newline = match args.get(1).cloned() {
+        None => true,
+        Some(ResultObj::Copy(Object::Boolean(newline))) => newline,
+        Some(_) => {
+            return ResultObj::Copy(Object::Error(
+                "Se espera un tipo de dato logico".to_string(),
+            ))
+        }
+    };
+    eval.ensure_text().write(&value, newline);
+    ResultObj::Copy(Object::Void)
+}
+
+// color_texto(color(1)) o color_texto(color(1), color(6)) con fondo
+pub fn color_texto(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() || args.len() > 2 {
+        return ResultObj::Copy(Object::Error(format!(
+            "Se encontro {} argumentos de 1 o 2",
+            args.len()
+        )));
+    }
+    let mut colors = Vec::with_capacity(args.len());
+    for arg in args.iter() {
+        match extract_color(arg.clone()) {
+            Ok(color) => colors.push(color),
+            Err(err) => return err,
+        }
+    }
+    eval.ensure_text()
+        .set_colors(colors[0], colors.get(1).copied());
+    ResultObj::Copy(Object::Void)
+}
+
+// caracter_en(col, fila) -> "a", " " si la celda esta vacia
+pub fn caracter_en(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 {
+        return ResultObj::Copy(Object::Error(format!(
+            "Se encontro {} argumentos de 2",
+            args.len()
+        )));
+    }
+    let Some((col, row)) = extract_cell(&args) else {
+        return ResultObj::Copy(Object::Error(
+            "Se espera un tipo de dato numerico".to_string(),
+        ));
+    };
+    if col < 0 || row < 0 {
+        return ResultObj::Copy(Object::Null);
+    }
+    match eval.ensure_text().char_at(col as usize, row as usize) {
+        Some(c) => ResultObj::Ref(new_rc_object(Object::String(c.to_string()))),
+        None => ResultObj::Copy(Object::Null),
+    }
+}
+
+// mostrar_cursor(falso) -> oculta el cursor parpadeante
+pub fn mostrar_cursor(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return ResultObj::Copy(Object::Error(format!(
+            "Se encontro {} argumentos de 1",
+            args.len()
+        )));
+    }
+    match args[0].clone() {
+        ResultObj::Copy(Object::Boolean(visible)) => {
+            eval.ensure_text().cursor_visible = visible;
+            ResultObj::Copy(Object::Void)
+        }
+        _ => ResultObj::Copy(Object::Error(
+            "Se espera un tipo de dato logico".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::{
+        eval::{environment::Environment, tarea::block_on},
+        lexer::Lexer,
+        parser::Parser,
+    };
+
+    // Ejecuta el programa con `eval` y retorna su resultado
+    fn run(eval: &mut Evaluator, code: &str) -> String {
+        let mut parser = Parser::new(Lexer::new(code.chars().collect()));
+        let program = parser.parse();
+        assert!(parser.error.is_none(), "{}", code);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        match block_on(eval.eval_program(&program, &env)) {
+            ResultObj::Copy(obj) => obj.to_string(),
+            ResultObj::Ref(obj) => obj.borrow().to_string(),
+        }
+    }
+
+    fn screen(eval: &Evaluator) -> Option<(usize, usize)> {
+        eval.screen
+            .as_ref()
+            .map(|screen| (screen.width, screen.height))
+    }
+
+    #[test]
+    fn modo_pantalla_limits() {
+        let mut eval = Evaluator::new(None, 100.0, 80.0);
+        for code in [
+            "modo_pantalla(0, 200);",
+            "modo_pantalla(320, 0);",
+            "modo_pantalla(-320, 200);",
+            "modo_pantalla(1921, 200);",
+            "modo_pantalla(320, 1921);",
+        ] {
+            assert!(run(&mut eval, code).contains("entre 1 y 1920"), "{}", code);
+            assert_eq!(screen(&eval), None, "{}", code);
+        }
+        assert!(run(&mut eval, "modo_pantalla(320.5, 200);").contains("entero"));
+        assert!(run(&mut eval, "modo_pantalla(320);").contains("0 o 2"));
+        assert_eq!(screen(&eval), None);
+
+        run(&mut eval, "modo_pantalla(1, 1);");
+        assert_eq!(screen(&eval), Some((1, 1)));
+        run(&mut eval, "modo_pantalla(1920, 1920);");
+        assert_eq!(screen(&eval), Some((1920, 1920)));
+        assert_eq!((eval.canvas.width, eval.canvas.height), (1920.0, 1920.0));
+    }
+
+    #[test]
+    fn modo_pantalla_without_arguments_returns_to_the_window() {
+        let mut eval = Evaluator::new(None, 100.0, 80.0);
+        run(&mut eval, "modo_pantalla(320, 200);\nescribir(\"hola\");");
+        assert!(eval.framebuffer.is_some() && eval.text.is_some());
+        run(&mut eval, "modo_pantalla();");
+        assert_eq!(screen(&eval), None);
+        assert!(eval.framebuffer.is_none() && eval.text.is_none());
+    }
+
+    #[test]
+    fn obtener_pixel_after_poner_pixel() {
+        let mut eval = Evaluator::new(None, 100.0, 80.0);
+        run(
+            &mut eval,
+            "modo_pantalla(4, 4);\nponer_pixel(1, 2, color(2));",
+        );
+        assert_eq!(run(&mut eval, "obtener_pixel(1, 2) == color(2);"), "verdad");
+        // Los decimales se truncan
+        assert_eq!(
+            run(&mut eval, "obtener_pixel(1.7, 2.2) == color(2);"),
+            "verdad"
+        );
+        // El resto conserva el fondo de la pantalla
+        assert_eq!(run(&mut eval, "obtener_pixel(2, 2);"), "rgb(0, 0, 0)");
+        // Sin color usa el predeterminado
+        run(&mut eval, "poner_pixel(3, 3);");
+        assert_ne!(run(&mut eval, "obtener_pixel(3, 3);"), "rgb(0, 0, 0)");
+    }
+
+    #[test]
+    fn pixels_outside_the_screen() {
+        let mut eval = Evaluator::new(None, 100.0, 80.0);
+        run(&mut eval, "modo_pantalla(4, 4);");
+        for (x, y) in [(4, 0), (0, 4), (-1, 0), (0, -1)] {
+            let code = format!(
+                "poner_pixel({}, {}, color(3));\nobtener_pixel({}, {});",
+                x, y, x, y
+            );
+            assert_eq!(run(&mut eval, &code), "", "{}", code);
+            let code = format!("obtener_pixel({}, {});", x, y);
+            assert_eq!(run(&mut eval, &code), "nulo", "{}", code);
+        }
+    }
+
+    #[test]
+    fn pixels_in_the_window() {
+        let mut eval = Evaluator::new(None, 100.0, 80.0);
+        // Sin `modo_pantalla` el bufer empieza transparente
+        assert_eq!(run(&mut eval, "obtener_pixel(5, 5);"), "rgba(0, 0, 0, 0)");
+        run(&mut eval, "poner_pixel(5, 5, rgb(10, 20, 30));");
+        assert_eq!(run(&mut eval, "obtener_pixel(5, 5);"), "rgb(10, 20, 30)");
+        assert_eq!(run(&mut eval, "obtener_pixel(99, 79);"), "rgba(0, 0, 0, 0)");
+        assert_eq!(run(&mut eval, "obtener_pixel(100, 79);"), "nulo");
+    }
 }
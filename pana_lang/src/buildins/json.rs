@@ -0,0 +1,62 @@
+use crate::eval::{
+    evaluator::Evaluator,
+    json::Json,
+    objects::{new_rc_object, FnArgs, Object, ResultObj},
+};
+
+use super::missmatch_args;
+
+// a_json({"nivel": 1}) -> "{"nivel":1}"
+// a_json({"nivel": 1}, verdad) -> cada elemento en su linea, con sangria
+pub fn a_json(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() || args.len() > 2 {
+        return missmatch_args("1 o 2", args.len());
+    }
+    let value = args[0].clone();
+    if let ResultObj::Copy(Object::Error(_)) = value {
+        return value;
+    }
+    let pretty = match args.get(1).cloned() {
+        None => false,
+        Some(ResultObj::Copy(Object::Boolean(pretty))) => pretty,
+        Some(_) => {
+            return ResultObj::Copy(Object::Error(
+                "Se espera un tipo de dato logico".to_string(),
+            ))
+        }
+    };
+    match Json::from_obj(&value) {
+        Ok(json) => ResultObj::Ref(new_rc_object(Object::String(json.write(pretty)))),
+        Err(msg) => ResultObj::Copy(Object::Error(msg)),
+    }
+}
+
+// desde_json("[1, 2.5, nulo]") -> [1, 2.5, nulo]
+pub fn desde_json(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    let parsed = match args[0].clone() {
+        ResultObj::Copy(Object::Error(msg)) => return ResultObj::Copy(Object::Error(msg)),
+        ResultObj::Copy(Object::String(text)) => Json::parse(&text),
+        ResultObj::Ref(obj) => match &*obj.borrow() {
+            Object::String(text) => Json::parse(text),
+            obj => {
+                return ResultObj::Copy(Object::Error(format!(
+                    "Se espera un tipo de dato cadena, no {}",
+                    obj.get_type()
+                )))
+            }
+        },
+        ResultObj::Copy(obj) => {
+            return ResultObj::Copy(Object::Error(format!(
+                "Se espera un tipo de dato cadena, no {}",
+                obj.get_type()
+            )))
+        }
+    };
+    match parsed {
+        Ok(json) => json.to_obj(),
+        Err(err) => ResultObj::Copy(Object::Error(format!("JSON invalido: {}", err))),
+    }
+}
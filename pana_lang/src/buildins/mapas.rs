@@ -0,0 +1,353 @@
+use std::collections::HashMap;
+
+use crate::{
+    eval::{
+        evaluator::Evaluator,
+        objects::{new_rc_object, FnArgs, Object, ResultObj},
+    },
+    lienzo::{mapa::Mapa, Color},
+    types::Numeric,
+};
+
+use super::{error, geometria::extract_coords, internal::extract_color, missmatch_args};
+
+// Evita que un mapa enorme agote la memoria
+const MAX_TILES: usize = 1 << 20;
+
+fn extract_int(arg: &ResultObj) -> Result<i64, ResultObj> {
+    match arg {
+        ResultObj::Copy(Object::Numeric(Numeric::Int(int))) => Ok(*int),
+        ResultObj::Copy(Object::Error(_)) => Err(arg.clone()),
+        obj => Err(error(format!(
+            "Se espera un tipo de dato numerico entero, no {}",
+            obj.get_type()
+        ))),
+    }
+}
+
+// El primer argumento, que debe ser un mapa
+fn extract_mapa(args: &[ResultObj]) -> Result<ResultObj, ResultObj> {
+    let obj = args[0].clone();
+    if let ResultObj::Copy(Object::Error(_)) = obj {
+        return Err(obj);
+    }
+    let is_mapa = match &obj {
+        ResultObj::Ref(ref_obj) => matches!(*ref_obj.borrow(), Object::Mapa(_)),
+        ResultObj::Copy(_) => false,
+    };
+    if is_mapa {
+        return Ok(obj);
+    }
+    Err(error(format!(
+        "Se espera un tipo de dato mapa, no {}",
+        obj.get_type()
+    )))
+}
+
+fn with_mapa<T>(obj: &ResultObj, f: impl FnOnce(&mut Mapa) -> T) -> T {
+    let ResultObj::Ref(ref_obj) = obj else {
+        unreachable!("`extract_mapa` solo retorna mapas");
+    };
+    let Object::Mapa(ref mut mapa) = *ref_obj.borrow_mut() else {
+        unreachable!("`extract_mapa` solo retorna mapas");
+    };
+    f(mapa)
+}
+
+// Colores de cada tile: una lista donde el indice es el tile, o un
+// diccionario de tile a color. `nulo` deja el tile sin dibujar.
+fn extract_sprites(obj: ResultObj) -> Result<HashMap<i64, Color>, ResultObj> {
+    let sprites_error =
+        || error("Se espera una lista o un diccionario con los colores de los tiles".to_string());
+    let ResultObj::Ref(ref_obj) = obj else {
+        return Err(sprites_error());
+    };
+    let pairs: Vec<(i64, ResultObj)> = match &*ref_obj.borrow() {
+        Object::List(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| (i as i64, item.clone()))
+            .collect(),
+        Object::Dictionary(pairs) => {
+            let mut tiles = Vec::with_capacity(pairs.len());
+            for (key, value) in pairs {
+                let ResultObj::Copy(Object::Numeric(Numeric::Int(tile))) = key else {
+                    return Err(error(format!(
+                        "Las llaves de los colores deben ser tiles enteros, no {}",
+                        key.get_type()
+                    )));
+                };
+                tiles.push((*tile, value.clone()));
+            }
+            tiles
+        }
+        _ => return Err(sprites_error()),
+    };
+    let mut colors = HashMap::with_capacity(pairs.len());
+    for (tile, value) in pairs {
+        if let ResultObj::Copy(Object::Null) = value {
+            continue;
+        }
+        colors.insert(tile, extract_color(value)?);
+    }
+    Ok(colors)
+}
+
+// crear_mapa(columnas, filas, tam) -> mapa con todos los tiles en 0
+pub fn crear_mapa(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 3 {
+        return missmatch_args("3", args.len());
+    }
+    let mut dims = [0; 2];
+    for (dim, arg) in dims.iter_mut().zip(&args) {
+        match extract_int(arg) {
+            Ok(int) if int > 0 => *dim = int as usize,
+            Ok(_) => {
+                return error("El mapa debe tener al menos una columna y una fila".to_string())
+            }
+            Err(err) => return err,
+        }
+    }
+    let size = match args[2].clone() {
+        ResultObj::Copy(Object::Numeric(num)) => match num {
+            Numeric::Int(int) => int as f32,
+            Numeric::Float(float) => float as f32,
+        },
+        obj if eval.is_error(&obj) => return obj,
+        obj => {
+            return error(format!(
+                "Se espera un tipo de dato numerico, no {}",
+                obj.get_type()
+            ))
+        }
+    };
+    if !(size > 0.0 && size.is_finite()) {
+        return error("El tamano de los tiles debe ser mayor a 0".to_string());
+    }
+    if dims[0].saturating_mul(dims[1]) > MAX_TILES {
+        return error(format!(
+            "El mapa puede tener hasta {} tiles, no {}x{}",
+            MAX_TILES, dims[0], dims[1]
+        ));
+    }
+    ResultObj::Ref(new_rc_object(Object::Mapa(Mapa::new(
+        dims[0], dims[1], size,
+    ))))
+}
+
+// poner_tile(mapa, columna, fila, tile)
+pub fn poner_tile(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 4 {
+        return missmatch_args("4", args.len());
+    }
+    let mapa = match extract_mapa(&args) {
+        Ok(mapa) => mapa,
+        Err(err) => return err,
+    };
+    let mut nums = [0; 3];
+    for (num, arg) in nums.iter_mut().zip(&args[1..]) {
+        match extract_int(arg) {
+            Ok(int) => *num = int,
+            Err(err) => return err,
+        }
+    }
+    let [column, row, tile] = nums;
+    with_mapa(&mapa, |mapa| match mapa.set(column, row, tile) {
+        true => ResultObj::Copy(Object::Void),
+        false => error(format!(
+            "La celda ({}, {}) esta fuera del mapa de {}x{}",
+            column, row, mapa.columns, mapa.rows
+        )),
+    })
+}
+
+// obtener_tile(mapa, columna, fila) -> tile, o nulo fuera del mapa
+pub fn obtener_tile(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 3 {
+        return missmatch_args("3", args.len());
+    }
+    let mapa = match extract_mapa(&args) {
+        Ok(mapa) => mapa,
+        Err(err) => return err,
+    };
+    let mut cell = [0; 2];
+    for (num, arg) in cell.iter_mut().zip(&args[1..]) {
+        match extract_int(arg) {
+            Ok(int) => *num = int,
+            Err(err) => return err,
+        }
+    }
+    match with_mapa(&mapa, |mapa| mapa.get(cell[0], cell[1])) {
+        Some(tile) => ResultObj::Copy(Object::Numeric(Numeric::Int(tile))),
+        None => ResultObj::Copy(Object::Null),
+    }
+}
+
+// tile_en(mapa, x, y) -> tile en esa posicion en pixeles, o nulo fuera del mapa
+pub fn tile_en(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() {
+        return missmatch_args("3", args.len());
+    }
+    let mapa = match extract_mapa(&args) {
+        Ok(mapa) => mapa,
+        Err(err) => return err,
+    };
+    let pos = match extract_coords(&args[1..], 2) {
+        Ok(pos) => (pos[0] as f32, pos[1] as f32),
+        Err(err) => return err,
+    };
+    match with_mapa(&mapa, |mapa| mapa.tile_at(pos)) {
+        Some(tile) => ResultObj::Copy(Object::Numeric(Numeric::Int(tile))),
+        None => ResultObj::Copy(Object::Null),
+    }
+}
+
+// colisiona_mapa(mapa, x1, y1, x2, y2) -> verdad si el rectangulo se
+// superpone con algun tile distinto de 0
+pub fn colisiona_mapa(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() {
+        return missmatch_args("5", args.len());
+    }
+    let mapa = match extract_mapa(&args) {
+        Ok(mapa) => mapa,
+        Err(err) => return err,
+    };
+    let c = match extract_coords(&args[1..], 4) {
+        Ok(coords) => coords,
+        Err(err) => return err,
+    };
+    let min = (c[0].min(c[2]) as f32, c[1].min(c[3]) as f32);
+    let max = (c[0].max(c[2]) as f32, c[1].max(c[3]) as f32);
+    ResultObj::Copy(Object::Boolean(with_mapa(&mapa, |mapa| {
+        mapa.collides(min, max)
+    })))
+}
+
+// dibujar_mapa(mapa, [nulo, GRIS, VERDE], x, y), la posicion es opcional
+pub fn dibujar_mapa(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 && args.len() != 4 {
+        return missmatch_args("2 o 4", args.len());
+    }
+    let mapa = match extract_mapa(&args) {
+        Ok(mapa) => mapa,
+        Err(err) => return err,
+    };
+    let sprites = args[1].clone();
+    if eval.is_error(&sprites) {
+        return sprites;
+    }
+    let colors = match extract_sprites(sprites) {
+        Ok(colors) => colors,
+        Err(err) => return err,
+    };
+    let origin = match args.len() {
+        4 => match extract_coords(&args[2..], 2) {
+            Ok(pos) => (pos[0] as f32, pos[1] as f32),
+            Err(err) => return err,
+        },
+        _ => (0.0, 0.0),
+    };
+    // Solo se dibujan los tiles que se ven en el lienzo
+    let Some(inverse) = eval.view_transform().invert() else {
+        return ResultObj::Copy(Object::Void);
+    };
+    let (width, height) = (eval.canvas.width, eval.canvas.height);
+    let corners =
+        [(0.0, 0.0), (width, 0.0), (0.0, height), (width, height)].map(|p| inverse.apply(p));
+    let min = corners
+        .iter()
+        .fold((f32::INFINITY, f32::INFINITY), |min, p| {
+            (min.0.min(p.0), min.1.min(p.1))
+        });
+    let max = corners
+        .iter()
+        .fold((f32::NEG_INFINITY, f32::NEG_INFINITY), |max, p| {
+            (max.0.max(p.0), max.1.max(p.1))
+        });
+    let rects = with_mapa(&mapa, |mapa| {
+        mapa.rects(origin, (min, max), |tile| colors.get(&tile).copied())
+    });
+    eval.draw(|lienzo| lienzo.rects_filled(&rects));
+    ResultObj::Copy(Object::Void)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        eval::{
+            environment::{Environment, RcEnvironment},
+            tarea::block_on,
+        },
+        lexer::Lexer,
+        parser::Parser,
+    };
+
+    use super::*;
+
+    fn show(obj: ResultObj) -> String {
+        match obj {
+            ResultObj::Copy(obj) => obj.to_string(),
+            ResultObj::Ref(obj) => obj.borrow().to_string(),
+        }
+    }
+
+    // Ejecuta el programa y retorna su resultado y el entorno
+    fn run(code: &str) -> (String, RcEnvironment) {
+        let mut parser = Parser::new(Lexer::new(code.chars().collect()));
+        let program = parser.parse();
+        assert!(parser.error.is_none(), "{}", code);
+        let mut eval = Evaluator::new(None, 100.0, 80.0);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        let result = block_on(eval.eval_program(&program, &env));
+        (show(result), env)
+    }
+
+    #[test]
+    fn crear_mapa_limits() {
+        for code in ["crear_mapa(0, 4, 16);", "crear_mapa(4, -1, 16);"] {
+            assert!(run(code).0.contains("al menos una columna"), "{}", code);
+        }
+        for code in ["crear_mapa(4, 4, 0);", "crear_mapa(4, 4, -8);"] {
+            assert!(run(code).0.contains("mayor a 0"), "{}", code);
+        }
+        assert!(run("crear_mapa(4.5, 4, 16);").0.contains("entero"));
+        assert!(run("crear_mapa(4, 4);").0.contains("argumentos"));
+        assert_eq!(run("crear_mapa(1024, 1024, 1);").0, "mapa(1024, 1024, 1)");
+        assert!(run("crear_mapa(1025, 1024, 1);")
+            .0
+            .contains("hasta 1048576 tiles"));
+    }
+
+    // Un mapa de 3x3 tiles de 10 pixeles con un solo tile en el centro, que
+    // ocupa de (10, 10) a (20, 20)
+    fn collides(x1: f32, y1: f32, x2: f32, y2: f32) -> String {
+        let (_, env) = run(&format!(
+            "var m = crear_mapa(3, 3, 10);
+            poner_tile(m, 1, 1, 5);
+            var r = colisiona_mapa(m, {}, {}, {}, {});",
+            x1, y1, x2, y2
+        ));
+        let r = env.borrow().get(&"r".to_owned()).unwrap();
+        show(r)
+    }
+
+    #[test]
+    fn colisiona_mapa_at_tile_borders() {
+        // Tocar los bordes del tile no es chocar
+        assert_eq!(collides(0.0, 0.0, 10.0, 10.0), "falso");
+        assert_eq!(collides(0.0, 12.0, 10.0, 18.0), "falso");
+        assert_eq!(collides(20.0, 12.0, 30.0, 18.0), "falso");
+        assert_eq!(collides(12.0, 20.0, 18.0, 30.0), "falso");
+        // Apenas entrar si lo es
+        assert_eq!(collides(0.0, 0.0, 10.5, 10.5), "verdad");
+        assert_eq!(collides(19.5, 12.0, 30.0, 18.0), "verdad");
+        assert_eq!(collides(12.0, 12.0, 18.0, 18.0), "verdad");
+        // Las esquinas pueden venir en cualquier orden
+        assert_eq!(collides(18.0, 18.0, 12.0, 12.0), "verdad");
+        // Fuera del mapa no hay tiles
+        assert_eq!(collides(-50.0, -50.0, 0.0, 0.0), "falso");
+        assert_eq!(collides(30.0, 30.0, 80.0, 80.0), "falso");
+    }
+}
@@ -0,0 +1,365 @@
+use crate::{
+    eval::{
+        evaluator::Evaluator,
+        objects::{FnArgs, Object, ResultObj},
+    },
+    types::Numeric,
+};
+
+use super::{extract_numeric_args, missmatch_args, to_f64};
+
+fn float_obj(v: f64) -> ResultObj {
+    ResultObj::Copy(Object::Numeric(Numeric::Float(v)))
+}
+
+fn int_obj(v: i64) -> ResultObj {
+    ResultObj::Copy(Object::Numeric(Numeric::Int(v)))
+}
+
+// Funciones de un argumento que siempre retornan flotante
+fn unary_float(args: FnArgs, f: fn(f64) -> f64) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match extract_numeric_args(&args) {
+        Ok(nums) => float_obj(f(to_f64(&nums[0]))),
+        Err(err) => err,
+    }
+}
+
+// seno(PI / 2) -> 1.0
+pub fn seno(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    unary_float(args, f64::sin)
+}
+
+// coseno(0) -> 1.0
+pub fn coseno(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    unary_float(args, f64::cos)
+}
+
+// tangente(PI / 4) -> 1.0
+pub fn tangente(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    unary_float(args, f64::tan)
+}
+
+// atan2(y, x) -> angulo en radianes
+pub fn atan2(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 {
+        return missmatch_args("2", args.len());
+    }
+    match extract_numeric_args(&args) {
+        Ok(nums) => float_obj(to_f64(&nums[0]).atan2(to_f64(&nums[1]))),
+        Err(err) => err,
+    }
+}
+
+// raiz(16) -> 4.0
+pub fn raiz(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match extract_numeric_args(&args) {
+        Ok(nums) => {
+            let v = to_f64(&nums[0]);
+            if v < 0.0 {
+                return ResultObj::Copy(Object::Error(
+                    "No se puede calcular la raiz de un numero negativo".to_owned(),
+                ));
+            }
+            float_obj(v.sqrt())
+        }
+        Err(err) => err,
+    }
+}
+
+// potencia(2, 3) -> 8, potencia(2, -1) -> 0.5
+pub fn potencia(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 {
+        return missmatch_args("2", args.len());
+    }
+    match extract_numeric_args(&args) {
+        Ok(nums) => match (&nums[0], &nums[1]) {
+            // Entero elevado a un exponente entero positivo se mantiene entero
+            (Numeric::Int(base), Numeric::Int(exp)) if *exp >= 0 => {
+                match u32::try_from(*exp)
+                    .ok()
+                    .and_then(|exp| base.checked_pow(exp))
+                {
+                    Some(v) => int_obj(v),
+                    None => float_obj((*base as f64).powf(*exp as f64)),
+                }
+            }
+            (Numeric::Int(base), Numeric::Int(exp)) => float_obj((*base as f64).powi(*exp as i32)),
+            (base, exp) => float_obj(to_f64(base).powf(to_f64(exp))),
+        },
+        Err(err) => err,
+    }
+}
+
+// abs(-3) -> 3, abs(-1.5) -> 1.5
+pub fn abs(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match extract_numeric_args(&args) {
+        Ok(nums) => match nums[0] {
+            // abs(i64::MIN) no cabe en un entero, igual que `potencia` pasa a decimal
+            Numeric::Int(v) => match v.checked_abs() {
+                Some(v) => int_obj(v),
+                None => float_obj((v as f64).abs()),
+            },
+            Numeric::Float(v) => float_obj(v.abs()),
+        },
+        Err(err) => err,
+    }
+}
+
+// Las funciones de redondeo retornan enteros para poder usarlos como indices
+fn round_to_int(args: FnArgs, f: fn(f64) -> f64) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match extract_numeric_args(&args) {
+        Ok(nums) => match nums[0] {
+            Numeric::Int(v) => int_obj(v),
+            // `i64::MAX as f64` ya es 2^63, que no cabe en un entero
+            Numeric::Float(v) => match f(v) {
+                v if v >= i64::MIN as f64 && v < i64::MAX as f64 => int_obj(v as i64),
+                v => ResultObj::Copy(Object::Error(format!(
+                    "El numero {} no se puede convertir a entero",
+                    v
+                ))),
+            },
+        },
+        Err(err) => err,
+    }
+}
+
+// piso(2.7) -> 2
+pub fn piso(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    round_to_int(args, f64::floor)
+}
+
+// techo(2.1) -> 3
+pub fn techo(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    round_to_int(args, f64::ceil)
+}
+
+// Un f64 tiene unos 15 digitos decimales de precision
+const MAX_DECIMALS: i64 = 15;
+
+// redondear(2.5) -> 3, redondear(3.14159, 2) -> 3.14
+pub fn redondear(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 {
+        return round_to_int(args, f64::round);
+    }
+    match extract_numeric_args(&args) {
+        Ok(nums) => match (&nums[0], &nums[1]) {
+            (_, Numeric::Int(decimals)) if !(0..=MAX_DECIMALS).contains(decimals) => {
+                ResultObj::Copy(Object::Error(format!(
+                    "Los decimales deben estar entre 0 y {}, no {}",
+                    MAX_DECIMALS, decimals
+                )))
+            }
+            (num, Numeric::Int(decimals)) => {
+                let factor = 10f64.powi(*decimals as i32);
+                float_obj((to_f64(num) * factor).round() / factor)
+            }
+            (_, Numeric::Float(_)) => ResultObj::Copy(Object::Error(
+                "Se espera un tipo de dato numerico entero para los decimales".to_owned(),
+            )),
+        },
+        Err(err) => err,
+    }
+}
+
+// Acepta varios numeros o una sola lista: min(3, 1, 2) o min([3, 1, 2])
+fn extremum(args: FnArgs, keep: std::cmp::Ordering) -> ResultObj {
+    if args.is_empty() {
+        return missmatch_args("1 o mas", args.len());
+    }
+    let nums = if args.len() == 1 {
+        match args[0].clone() {
+            ResultObj::Copy(Object::Numeric(num)) => vec![num],
+            ResultObj::Copy(Object::Error(msg)) => return ResultObj::Copy(Object::Error(msg)),
+            ResultObj::Ref(obj) => match *obj.borrow() {
+                Object::List(ref list) => {
+                    let mut nums = Vec::with_capacity(list.len());
+                    for obj in list {
+                        match obj {
+                            ResultObj::Copy(Object::Numeric(num)) => nums.push(num.clone()),
+                            obj => {
+                                return ResultObj::Copy(Object::Error(format!(
+                                    "Se espera una lista de numeros, se encontro {}",
+                                    obj.get_type()
+                                )))
+                            }
+                        }
+                    }
+                    nums
+                }
+                ref obj => {
+                    return ResultObj::Copy(Object::Error(format!(
+                        "Se espera un tipo de dato numerico o lista, no {}",
+                        obj.get_type()
+                    )))
+                }
+            },
+            obj => {
+                return ResultObj::Copy(Object::Error(format!(
+                    "Se espera un tipo de dato numerico o lista, no {}",
+                    obj.get_type()
+                )))
+            }
+        }
+    } else {
+        match extract_numeric_args(&args) {
+            Ok(nums) => nums,
+            Err(err) => return err,
+        }
+    };
+
+    match nums
+        .into_iter()
+        .reduce(|a, b| if b.total_cmp(&a) == keep { b } else { a })
+    {
+        Some(num) => ResultObj::Copy(Object::Numeric(num)),
+        None => ResultObj::Copy(Object::Null),
+    }
+}
+
+// min(3, 1, 2) -> 1
+pub fn min(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    extremum(args, std::cmp::Ordering::Less)
+}
+
+// max(3, 1, 2) -> 3
+pub fn max(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    extremum(args, std::cmp::Ordering::Greater)
+}
+
+// limitar(valor, minimo, maximo) -> limitar(15, 0, 10) -> 10
+pub fn limitar(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 3 {
+        return missmatch_args("3", args.len());
+    }
+    match extract_numeric_args(&args) {
+        Ok(nums) => match (&nums[0], &nums[1], &nums[2]) {
+            (_, lo, hi) if to_f64(lo) > to_f64(hi) => ResultObj::Copy(Object::Error(format!(
+                "El minimo {} es mayor que el maximo {}",
+                lo, hi
+            ))),
+            (Numeric::Int(v), Numeric::Int(lo), Numeric::Int(hi)) => {
+                int_obj((*v).max(*lo).min(*hi))
+            }
+            (v, lo, hi) => float_obj(to_f64(v).max(to_f64(lo)).min(to_f64(hi))),
+        },
+        Err(err) => err,
+    }
+}
+
+// interpolar(a, b, t) -> interpolar(0, 10, 0.5) -> 5.0
+pub fn interpolar(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 3 {
+        return missmatch_args("3", args.len());
+    }
+    match extract_numeric_args(&args) {
+        Ok(nums) => {
+            let (a, b, t) = (to_f64(&nums[0]), to_f64(&nums[1]), to_f64(&nums[2]));
+            float_obj(a + (b - a) * t)
+        }
+        Err(err) => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Numeric::{Float, Int};
+
+    fn call(f: fn(&mut Evaluator, FnArgs) -> ResultObj, args: &[Numeric]) -> ResultObj {
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        let args = args
+            .iter()
+            .map(|num| ResultObj::Copy(Object::Numeric(num.clone())))
+            .collect();
+        f(&mut eval, args)
+    }
+
+    fn value(f: fn(&mut Evaluator, FnArgs) -> ResultObj, args: &[Numeric]) -> String {
+        match call(f, args) {
+            ResultObj::Copy(Object::Numeric(num)) => format!("{:?}", num),
+            obj => panic!("se esperaba un numero, no {}", obj.get_type()),
+        }
+    }
+
+    fn is_error(f: fn(&mut Evaluator, FnArgs) -> ResultObj, args: &[Numeric]) -> bool {
+        matches!(call(f, args), ResultObj::Copy(Object::Error(_)))
+    }
+
+    #[test]
+    fn rounding_returns_integers() {
+        assert_eq!(value(piso, &[Float(2.7)]), "Int(2)");
+        assert_eq!(value(piso, &[Float(-2.1)]), "Int(-3)");
+        assert_eq!(value(techo, &[Float(2.1)]), "Int(3)");
+        assert_eq!(value(redondear, &[Float(2.5)]), "Int(3)");
+        assert_eq!(value(redondear, &[Float(-2.5)]), "Int(-3)");
+        assert_eq!(value(piso, &[Int(i64::MAX)]), format!("Int({})", i64::MAX));
+        assert_eq!(value(redondear, &[Float(1.23456), Int(2)]), "Float(1.23)");
+    }
+
+    #[test]
+    fn rounding_non_finite_and_out_of_range() {
+        for f in [piso, techo, redondear] {
+            assert!(is_error(f, &[Float(f64::NAN)]));
+            assert!(is_error(f, &[Float(f64::INFINITY)]));
+            assert!(is_error(f, &[Float(f64::NEG_INFINITY)]));
+            assert!(is_error(f, &[Float(1e19)]));
+            assert!(is_error(f, &[Float(-1e19)]));
+            assert!(is_error(f, &[Float(i64::MAX as f64)]));
+        }
+        // -2^63 si cabe
+        assert_eq!(
+            value(piso, &[Float(i64::MIN as f64)]),
+            format!("Int({})", i64::MIN)
+        );
+        assert!(is_error(redondear, &[Float(1.5), Int(16)]));
+        assert!(is_error(redondear, &[Float(1.5), Int(-1)]));
+        assert!(is_error(redondear, &[Float(1.5), Float(1.0)]));
+    }
+
+    #[test]
+    fn power_and_abs_stay_integers_while_they_fit() {
+        assert_eq!(value(potencia, &[Int(2), Int(10)]), "Int(1024)");
+        assert_eq!(value(potencia, &[Int(2), Int(-1)]), "Float(0.5)");
+        assert_eq!(value(potencia, &[Int(10), Int(19)]), "Float(1e19)");
+        assert_eq!(value(abs, &[Int(-3)]), "Int(3)");
+        assert_eq!(value(abs, &[Int(i64::MIN)]), "Float(9.223372036854776e18)");
+    }
+
+    #[test]
+    fn square_root_and_trigonometry() {
+        assert_eq!(value(raiz, &[Int(16)]), "Float(4.0)");
+        assert!(is_error(raiz, &[Int(-1)]));
+        assert_eq!(value(coseno, &[Int(0)]), "Float(1.0)");
+        assert_eq!(value(atan2, &[Int(0), Int(1)]), "Float(0.0)");
+        assert!(is_error(seno, &[]));
+    }
+
+    #[test]
+    fn min_max_limitar_and_interpolar() {
+        assert_eq!(value(min, &[Int(3), Int(1), Int(2)]), "Int(1)");
+        assert_eq!(value(max, &[Int(3), Float(3.5), Int(2)]), "Float(3.5)");
+        assert!(is_error(min, &[]));
+        assert_eq!(value(limitar, &[Int(15), Int(0), Int(10)]), "Int(10)");
+        assert_eq!(
+            value(limitar, &[Float(-0.5), Int(0), Int(10)]),
+            "Float(0.0)"
+        );
+        assert!(is_error(limitar, &[Int(5), Int(10), Int(0)]));
+        assert_eq!(
+            value(interpolar, &[Int(0), Int(10), Float(0.5)]),
+            "Float(5.0)"
+        );
+    }
+}
@@ -1,60 +1,74 @@
+use std::cmp::Ordering;
+
 use crate::{
     eval::{
-        environment::RcEnvironment,
         evaluator::{create_msg_err, Evaluator},
-        objects::{new_rc_object, Object, ResultObj},
+        objects::{new_rc_object, FnArgs, Object, ResultObj},
     },
     lexer::Lexer,
-    parser::expression::FnParams,
     token::TokenType,
-    types::Numeric,
+    types::{Numeric, Vector},
 };
 
-pub fn match_member_fn(
+pub async fn match_member_fn(
     eval: &mut Evaluator,
     identifier: &String,
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     match identifier.as_str() {
         // Mixto
-        "eliminar" => eliminar(eval, args, target, target_line, target_col, env),
+        "eliminar" => eliminar(eval, args, target, target_line, target_col),
         "limpiar" => limpiar(args, target, target_line, target_col),
-        "buscar" => buscar(eval, args, target, target_line, target_col, env),
-        "insertar" => insertar(eval, args, target, target_line, target_col, env),
+        "buscar" => buscar(eval, args, target, target_line, target_col),
+        "insertar" => insertar(eval, args, target, target_line, target_col),
         "vacio" => vacio(args, target, target_line, target_col),
         "invertir" => invertir(args, target, target_line, target_col),
 
         // Funciones miembro de las listas
-        "agregar" => agregar(eval, args, target, target_line, target_col, env),
-        "indice" => indice(eval, args, target, target_line, target_col, env),
-        "ordenar" => ordenar(eval, args, target, target_line, target_col, env),
-        "concatenar" => concatenar(eval, args, target, target_line, target_col, env),
-        "eliminar_indice" => eliminar_indice(eval, args, target, target_line, target_col, env),
-        "juntar" => juntar(eval, args, target, target_line, target_col, env),
+        "agregar" => agregar(eval, args, target, target_line, target_col),
+        "indice" => indice(eval, args, target, target_line, target_col),
+        "ordenar" => ordenar(eval, args, target, target_line, target_col).await,
+        "ordenar_por" => ordenar_por(eval, args, target, target_line, target_col).await,
+        "concatenar" => concatenar(eval, args, target, target_line, target_col),
+        "eliminar_indice" => eliminar_indice(eval, args, target, target_line, target_col),
+        "juntar" => juntar(eval, args, target, target_line, target_col),
+        "mapear" => mapear(eval, args, target, target_line, target_col).await,
+        "filtrar" => filtrar(eval, args, target, target_line, target_col).await,
+        "reducir" => reducir(eval, args, target, target_line, target_col).await,
+        "cada" => cada(eval, args, target, target_line, target_col).await,
+        "alguno" => alguno(eval, args, target, target_line, target_col).await,
+        "todos" => todos(eval, args, target, target_line, target_col).await,
+        "encontrar" => encontrar(eval, args, target, target_line, target_col).await,
 
         // Funciones miembro de los dicccionarios
         "llaves" => llaves(args, target, target_line, target_col),
         "valores" => valores(args, target, target_line, target_col),
 
         // Funciones miembro de las cadenas
-        "separar" => separar(eval, args, target, target_line, target_col, env),
-        "caracter" => caracter(eval, args, target, target_line, target_col, env),
+        "separar" => separar(eval, args, target, target_line, target_col),
+        "caracter" => caracter(eval, args, target, target_line, target_col),
         "caracteres" => caracteres(args, target, target_line, target_col),
         "es_alfabetico" => es_alfabetico(args, target, target_line, target_col),
         "es_numerico" => es_numerico(args, target, target_line, target_col),
         "es_alfanumerico" => es_alfanumerico(args, target, target_line, target_col),
-        "inicia_con" => inicia_con(eval, args, target, target_line, target_col, env),
-        "termina_con" => termina_con(eval, args, target, target_line, target_col, env),
+        "inicia_con" => inicia_con(eval, args, target, target_line, target_col),
+        "termina_con" => termina_con(eval, args, target, target_line, target_col),
         "a_mayusculas" => a_mayusculas(args, target, target_line, target_col),
         "a_minusculas" => a_minusculas(args, target, target_line, target_col),
-        "reemplazar" => reemplazar(eval, args, target, target_line, target_col, env),
+        "reemplazar" => reemplazar(eval, args, target, target_line, target_col),
         "recortar" => recortar(args, target, target_line, target_col),
-        "subcadena" => subcadena(eval, args, target, target_line, target_col, env),
+        "subcadena" => subcadena(eval, args, target, target_line, target_col),
         "a_numerico" => a_numerico(args, target, target_line, target_col),
+
+        // Funciones miembro de los vectores
+        "x" => vector_x(args, target, target_line, target_col),
+        "y" => vector_y(args, target, target_line, target_col),
+        "longitud" => longitud(args, target, target_line, target_col),
+        "normalizar" => normalizar(args, target, target_line, target_col),
+        "rotar" => rotar(eval, args, target, target_line, target_col),
         _ => ResultObj::Copy(Object::Error(create_msg_err(
             format!(
                 "El tipo de dato {} no posee el miembro `{}`",
@@ -105,82 +119,168 @@ fn missmatch_args(
     )))
 }
 
-fn quick_sort(slice: &mut [ResultObj]) -> Option<ResultObj> {
-    let len = slice.len();
-    _quick_sort(slice, 0, (len - 1) as isize)
+// Copia los elementos de la lista para que la funcion recibida pueda modificarla
+fn list_items(
+    name: &str,
+    target: &ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> Result<Vec<ResultObj>, ResultObj> {
+    match target {
+        ResultObj::Copy(obj) => Err(missmatch_type(
+            name,
+            &obj.get_type(),
+            target_line,
+            target_col,
+        )),
+        ResultObj::Ref(ref_obj) => match *ref_obj.borrow() {
+            Object::List(ref list) => Ok(list.clone()),
+            ref obj => Err(missmatch_type(
+                name,
+                &obj.get_type(),
+                target_line,
+                target_col,
+            )),
+        },
+    }
 }
 
-fn _quick_sort(slice: &mut [ResultObj], low: isize, high: isize) -> Option<ResultObj> {
-    if low < high {
-        match partition(slice, low, high) {
-            Ok(p) => {
-                let mut res = _quick_sort(slice, low, p - 1);
-                if res.is_some() {
-                    return res;
-                }
-                res = _quick_sort(slice, p + 1, high);
-                if res.is_some() {
-                    return res;
-                }
-            }
-            Err(res) => return Some(res),
-        }
+fn extract_fn_arg(
+    arg: &ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> Result<ResultObj, ResultObj> {
+    match arg {
+        ResultObj::Copy(Object::Error(_)) => Err(arg.clone()),
+        ResultObj::Copy(Object::FnExpr(_))
+        | ResultObj::Copy(Object::Fn(_))
+        | ResultObj::Copy(Object::BuildinFn(_)) => Ok(arg.clone()),
+        obj => Err(missmatch_type_arg(
+            "funcion",
+            &obj.get_type(),
+            target_line,
+            target_col,
+        )),
     }
-    None
 }
 
-fn partition(slice: &mut [ResultObj], low: isize, high: isize) -> Result<isize, ResultObj> {
-    let pivot = high as usize;
-    let mut store_index = low - 1;
-    let mut last_index = high;
-    loop {
-        store_index += 1;
-        loop {
-            match slice[store_index as usize].partial_cmp(&slice[pivot]) {
-                Some(ord) => match ord {
-                    std::cmp::Ordering::Less => store_index += 1,
-                    _ => break,
-                },
-                None => todo!("Error no se puede comparar"),
-            }
-        }
+fn fn_result_value(
+    obj: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> Result<ResultObj, ResultObj> {
+    match obj {
+        ResultObj::Copy(Object::Error(_)) => Err(obj),
+        ResultObj::Copy(Object::Void) => Err(ResultObj::Copy(Object::Error(create_msg_err(
+            "La funcion no retorno ningun valor".into(),
+            target_line,
+            target_col,
+        )))),
+        obj => Ok(obj),
+    }
+}
 
-        last_index -= 1;
+fn fn_result_condition(
+    obj: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> Result<bool, ResultObj> {
+    match obj {
+        ResultObj::Copy(Object::Numeric(numeric)) => Ok(numeric != Numeric::Int(0)),
+        ResultObj::Copy(Object::Boolean(b)) => Ok(b),
+        ResultObj::Copy(Object::Null) => Ok(false),
+        ResultObj::Copy(Object::Error(_)) => Err(obj),
+        obj => Err(missmatch_type_arg(
+            "logico",
+            &obj.get_type(),
+            target_line,
+            target_col,
+        )),
+    }
+}
 
-        while last_index >= 0 {
-            match slice[last_index as usize].partial_cmp(&slice[pivot]) {
-                Some(ord) => match ord {
-                    std::cmp::Ordering::Greater => last_index -= 1,
-                    _ => break,
-                },
-                None => todo!("Error no se puede comparar"),
+// Ordenamiento por mezcla con la funcion del usuario como comparador. Es
+// estable y, a diferencia de `sort_by`, permite que el comparador falle cuando
+// la funcion retorna un error. Mezcla tramos de 1, 2, 4... elementos, porque
+// una funcion asincrona no puede llamarse a si misma.
+async fn merge_sort(
+    eval: &mut Evaluator,
+    mut list: Vec<ResultObj>,
+    fn_obj: &ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> Result<Vec<ResultObj>, ResultObj> {
+    let mut width = 1;
+    while width < list.len() {
+        let mut merged = Vec::with_capacity(list.len());
+        let mut rest = list.into_iter();
+        loop {
+            let left = rest.by_ref().take(width).collect::<Vec<_>>();
+            if left.is_empty() {
+                break;
+            }
+            let right = rest.by_ref().take(width).collect::<Vec<_>>();
+            let mut left = left.into_iter().peekable();
+            let mut right = right.into_iter().peekable();
+            while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+                // Solo se toma de la derecha si es estrictamente menor, asi los
+                // iguales conservan su orden original
+                let arguments = vec![r.clone(), l.clone()];
+                if less(eval, fn_obj, arguments, target_line, target_col).await? {
+                    merged.extend(right.next());
+                } else {
+                    merged.extend(left.next());
+                }
             }
+            merged.extend(left);
+            merged.extend(right);
         }
+        list = merged;
+        width *= 2;
+    }
+    Ok(list)
+}
 
-        if store_index >= last_index {
-            break;
-        } else {
-            slice.swap(store_index as usize, last_index as usize);
+// La funcion puede retornar un logico (verdad si `a` va antes que `b`)
+// o un numero (negativo si `a` va antes que `b`)
+async fn less(
+    eval: &mut Evaluator,
+    fn_obj: &ResultObj,
+    arguments: FnArgs,
+    target_line: usize,
+    target_col: usize,
+) -> Result<bool, ResultObj> {
+    let res_obj = eval
+        .call_fn_obj(fn_obj, arguments, target_line, target_col)
+        .await;
+    match res_obj {
+        ResultObj::Copy(Object::Boolean(b)) => Ok(b),
+        ResultObj::Copy(Object::Numeric(numeric)) => {
+            Ok(numeric.total_cmp(&Numeric::Int(0)) == Ordering::Less)
         }
+        ResultObj::Copy(Object::Error(_)) => Err(res_obj),
+        obj => Err(missmatch_type_arg(
+            "logico o numerico",
+            &obj.get_type(),
+            target_line,
+            target_col,
+        )),
     }
-    slice.swap(store_index as usize, pivot);
-    Ok(store_index)
 }
 
 // TODO sumar el numero de caracteres a las columnas
 
 pub fn eliminar(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "eliminar".len(), target_line, target_col);
     }
-    let obj_to_remove = eval.eval_expression(&args.remove(0), env);
+    let obj_to_remove = args.remove(0);
     if eval.is_error(&obj_to_remove) {
         return obj_to_remove;
     }
@@ -203,7 +303,7 @@ pub fn eliminar(
 }
 
 pub fn limpiar(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -233,16 +333,15 @@ pub fn limpiar(
 
 pub fn buscar(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "buscar".len(), target_line, target_col);
     }
-    let find_obj = eval.eval_expression(&args.remove(0), env);
+    let find_obj = args.remove(0);
     if eval.is_error(&find_obj) {
         return find_obj;
     }
@@ -272,20 +371,19 @@ pub fn buscar(
 
 pub fn insertar(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 2 {
         return missmatch_args(2, args.len(), "insertar".len(), target_line, target_col);
     }
-    let insert_obj = eval.eval_expression(&args.remove(0), env);
+    let insert_obj = args.remove(0);
     if eval.is_error(&insert_obj) {
         return insert_obj;
     }
-    let index_obj = eval.eval_expression(&args.remove(0), env);
+    let index_obj = args.remove(0);
     if eval.is_error(&index_obj) {
         return index_obj;
     }
@@ -348,12 +446,7 @@ pub fn insertar(
     }
 }
 
-pub fn vacio(
-    args: FnParams,
-    target: ResultObj,
-    target_line: usize,
-    target_col: usize,
-) -> ResultObj {
+pub fn vacio(args: FnArgs, target: ResultObj, target_line: usize, target_col: usize) -> ResultObj {
     if !args.is_empty() {
         return missmatch_args(0, args.len(), "vacio".len(), target_line, target_col);
     }
@@ -369,7 +462,7 @@ pub fn vacio(
 }
 
 pub fn invertir(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -397,11 +490,10 @@ pub fn invertir(
 
 pub fn agregar(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "agregar".len(), target_line, target_col);
@@ -410,7 +502,7 @@ pub fn agregar(
         ResultObj::Copy(obj) => missmatch_type("agregar", &obj.get_type(), target_line, target_col),
         ResultObj::Ref(ref_obj) => match *ref_obj.as_ref().borrow_mut() {
             Object::List(ref mut list) => {
-                let new_obj = eval.eval_expression(&args.remove(0), env);
+                let new_obj = args.remove(0);
                 if eval.is_error(&new_obj) {
                     return new_obj;
                 }
@@ -424,16 +516,15 @@ pub fn agregar(
 
 pub fn indice(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "indice".len(), target_line, target_col);
     }
-    let find_obj = eval.eval_expression(&args.remove(0), env);
+    let find_obj = args.remove(0);
     if eval.is_error(&find_obj) {
         return find_obj;
     }
@@ -449,62 +540,120 @@ pub fn indice(
     }
 }
 
-#[allow(dead_code, unused)]
-pub fn ordenar(
+// [3, 1, 2].ordenar() -> [1, 2, 3]
+// puntajes.ordenar(fn(a, b) { a["puntos"] > b["puntos"]; })
+pub async fn ordenar(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
-    if !args.is_empty() {
-        return missmatch_args(0, args.len(), "ordenar".len(), target_line, target_col);
+    if args.len() > 1 {
+        return missmatch_args(1, args.len(), "ordenar".len(), target_line, target_col);
     }
 
-    match target {
-        ResultObj::Copy(obj) => missmatch_type("ordenar", &obj.get_type(), target_line, target_col),
-        ResultObj::Ref(mut ref_obj) => match *ref_obj.clone().borrow_mut() {
-            Object::List(ref mut list) => {
-                // if !args.is_empty() {
-                //     let ord_func_expr = args.remove(0);
-                //     let line = ord_func_expr.line;
-                //     let col = ord_func_expr.col;
-                //     let ord_func_obj = eval.eval_call(ord_func_expr, vec![], env);
-                //     if eval.is_error(&ord_func_obj) {
-                //         return ord_func_obj;
-                //     }
-                //     return ResultObj::Ref(ref_obj);
-                // }
-                match quick_sort(list) {
-                    Some(res) => res,
-                    None => ResultObj::Ref(ref_obj),
-                }
+    let ref_obj = match target {
+        ResultObj::Copy(obj) => {
+            return missmatch_type("ordenar", &obj.get_type(), target_line, target_col)
+        }
+        ResultObj::Ref(ref_obj) => ref_obj,
+    };
+    let list = match *ref_obj.borrow() {
+        Object::List(ref list) => list.clone(),
+        Object::String(ref string) => {
+            if !args.is_empty() {
+                return missmatch_args(0, args.len(), "ordenar".len(), target_line, target_col);
             }
-            Object::String(ref mut string) => {
-                let mut chars = string.chars().collect::<Vec<char>>();
-                chars.sort();
-                ResultObj::Ref(new_rc_object(Object::String(
-                    chars.iter().collect::<String>(),
-                )))
+            let mut chars = string.chars().collect::<Vec<char>>();
+            chars.sort();
+            return ResultObj::Ref(new_rc_object(Object::String(
+                chars.iter().collect::<String>(),
+            )));
+        }
+        ref obj => return missmatch_type("ordenar", &obj.get_type(), target_line, target_col),
+    };
+
+    let sorted = match args.first() {
+        None => {
+            // `sort_by` es estable
+            let mut list = list;
+            list.sort_by(|a, b| a.total_cmp(b));
+            Ok(list)
+        }
+        Some(arg) => {
+            let fn_obj = match extract_fn_arg(arg, target_line, target_col) {
+                Ok(fn_obj) => fn_obj,
+                Err(err) => return err,
+            };
+            merge_sort(eval, list, &fn_obj, target_line, target_col).await
+        }
+    };
+
+    match sorted {
+        Ok(sorted) => {
+            if let Object::List(ref mut list) = *ref_obj.borrow_mut() {
+                *list = sorted;
             }
-            ref obj => missmatch_type("ordenar", &obj.get_type(), target_line, target_col),
-        },
+            ResultObj::Ref(ref_obj)
+        }
+        Err(err) => err,
     }
 }
 
+// puntajes.ordenar_por(fn(x) { x["puntos"]; })
+pub async fn ordenar_por(
+    eval: &mut Evaluator,
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args(1, args.len(), "ordenar_por".len(), target_line, target_col);
+    }
+    let list = match list_items("ordenar_por", &target, target_line, target_col) {
+        Ok(list) => list,
+        Err(err) => return err,
+    };
+    let fn_obj = match extract_fn_arg(&args[0], target_line, target_col) {
+        Ok(fn_obj) => fn_obj,
+        Err(err) => return err,
+    };
+
+    // Cada llave se calcula una sola vez
+    let mut pairs = Vec::with_capacity(list.len());
+    for obj in list {
+        let key = eval
+            .call_fn_obj(&fn_obj, vec![obj.clone()], target_line, target_col)
+            .await;
+        match fn_result_value(key, target_line, target_col) {
+            Ok(key) => pairs.push((key, obj)),
+            Err(err) => return err,
+        }
+    }
+    // `sort_by` es estable
+    pairs.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    if let ResultObj::Ref(ref ref_obj) = target {
+        if let Object::List(ref mut list) = *ref_obj.borrow_mut() {
+            *list = pairs.into_iter().map(|(_, obj)| obj).collect();
+        }
+    }
+    target
+}
+
 pub fn concatenar(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "concatenar".len(), target_line, target_col);
     }
-    let concat_obj = eval.eval_expression(&args.remove(0), env);
+    let concat_obj = args.remove(0);
     if eval.is_error(&concat_obj) {
         return concat_obj;
     }
@@ -534,16 +683,15 @@ pub fn concatenar(
 
 pub fn eliminar_indice(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "eliminar".len(), target_line, target_col);
     }
-    let index_to_remove = eval.eval_expression(&args.remove(0), env);
+    let index_to_remove = args.remove(0);
     let index;
     if eval.is_error(&index_to_remove) {
         return index_to_remove;
@@ -575,17 +723,16 @@ pub fn eliminar_indice(
 
 pub fn juntar(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "separar".len(), target_line, target_col);
     }
     let join;
-    let join_obj = eval.eval_expression(&args.remove(0), env);
+    let join_obj = args.remove(0);
     if eval.is_error(&join_obj) {
         return join_obj;
     }
@@ -624,12 +771,7 @@ pub fn juntar(
     }
 }
 
-pub fn llaves(
-    args: FnParams,
-    target: ResultObj,
-    target_line: usize,
-    target_col: usize,
-) -> ResultObj {
+pub fn llaves(args: FnArgs, target: ResultObj, target_line: usize, target_col: usize) -> ResultObj {
     if !args.is_empty() {
         return missmatch_args(0, args.len(), "llaves".len(), target_line, target_col);
     }
@@ -645,7 +787,7 @@ pub fn llaves(
 }
 
 pub fn valores(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -666,17 +808,16 @@ pub fn valores(
 
 pub fn separar(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "separar".len(), target_line, target_col);
     }
     let split;
-    let split_obj = eval.eval_expression(&args.remove(0), env);
+    let split_obj = args.remove(0);
     if eval.is_error(&split_obj) {
         return split_obj;
     }
@@ -716,16 +857,15 @@ pub fn separar(
 
 pub fn caracter(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "caracter".len(), target_line, target_col);
     }
-    let index_obj = eval.eval_expression(&args.remove(0), env);
+    let index_obj = args.remove(0);
     if eval.is_error(&index_obj) {
         return index_obj;
     }
@@ -763,7 +903,7 @@ pub fn caracter(
 }
 
 pub fn caracteres(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -788,7 +928,7 @@ pub fn caracteres(
 }
 
 pub fn es_alfabetico(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -816,7 +956,7 @@ pub fn es_alfabetico(
 }
 
 pub fn es_numerico(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -838,7 +978,7 @@ pub fn es_numerico(
 }
 
 pub fn es_alfanumerico(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -869,16 +1009,15 @@ pub fn es_alfanumerico(
 
 pub fn inicia_con(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "inicia_con".len(), target_line, target_col);
     }
-    let pattern_obj = eval.eval_expression(&args.remove(0), env);
+    let pattern_obj = args.remove(0);
     if eval.is_error(&pattern_obj) {
         return pattern_obj;
     }
@@ -912,16 +1051,15 @@ pub fn inicia_con(
 
 pub fn termina_con(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 1 {
         return missmatch_args(1, args.len(), "termina_con".len(), target_line, target_col);
     }
-    let pattern_obj = eval.eval_expression(&args.remove(0), env);
+    let pattern_obj = args.remove(0);
     if eval.is_error(&pattern_obj) {
         return pattern_obj;
     }
@@ -954,7 +1092,7 @@ pub fn termina_con(
 }
 
 pub fn a_mayusculas(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -976,7 +1114,7 @@ pub fn a_mayusculas(
 }
 
 pub fn a_minusculas(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -999,20 +1137,19 @@ pub fn a_minusculas(
 
 pub fn reemplazar(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 2 {
         return missmatch_args(2, args.len(), "reemplazar".len(), target_line, target_col);
     }
-    let pattern_obj = eval.eval_expression(&args.remove(0), env);
+    let pattern_obj = args.remove(0);
     if eval.is_error(&pattern_obj) {
         return pattern_obj;
     }
-    let new_obj = eval.eval_expression(&args.remove(0), env);
+    let new_obj = args.remove(0);
     if eval.is_error(&pattern_obj) {
         return pattern_obj;
     }
@@ -1063,7 +1200,7 @@ pub fn reemplazar(
 }
 
 pub fn recortar(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -1087,20 +1224,19 @@ pub fn recortar(
 
 pub fn subcadena(
     eval: &mut Evaluator,
-    mut args: FnParams,
+    mut args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
-    env: &RcEnvironment,
 ) -> ResultObj {
     if args.len() != 2 {
         return missmatch_args(2, args.len(), "subcadena".len(), target_line, target_col);
     }
-    let pos_obj = eval.eval_expression(&args.remove(0), env);
+    let pos_obj = args.remove(0);
     if eval.is_error(&pos_obj) {
         return pos_obj;
     }
-    let len_obj = eval.eval_expression(&args.remove(0), env);
+    let len_obj = args.remove(0);
     if eval.is_error(&len_obj) {
         return len_obj;
     }
@@ -1165,7 +1301,7 @@ pub fn subcadena(
 }
 
 pub fn a_numerico(
-    args: FnParams,
+    args: FnArgs,
     target: ResultObj,
     target_line: usize,
     target_col: usize,
@@ -1204,3 +1340,449 @@ pub fn a_numerico(
         },
     }
 }
+
+// [1, 2, 3].mapear(fn(x) { x * 2; }) -> [2, 4, 6]
+pub async fn mapear(
+    eval: &mut Evaluator,
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args(1, args.len(), "mapear".len(), target_line, target_col);
+    }
+    let list = match list_items("mapear", &target, target_line, target_col) {
+        Ok(list) => list,
+        Err(err) => return err,
+    };
+    let fn_obj = match extract_fn_arg(&args[0], target_line, target_col) {
+        Ok(fn_obj) => fn_obj,
+        Err(err) => return err,
+    };
+
+    let mut objs = Vec::with_capacity(list.len());
+    for obj in list {
+        let res_obj = eval
+            .call_fn_obj(&fn_obj, vec![obj], target_line, target_col)
+            .await;
+        match fn_result_value(res_obj, target_line, target_col) {
+            Ok(obj) => objs.push(obj),
+            Err(err) => return err,
+        }
+    }
+    ResultObj::Ref(new_rc_object(Object::List(objs)))
+}
+
+// [1, 2, 3].filtrar(fn(x) { x > 1; }) -> [2, 3]
+pub async fn filtrar(
+    eval: &mut Evaluator,
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args(1, args.len(), "filtrar".len(), target_line, target_col);
+    }
+    let list = match list_items("filtrar", &target, target_line, target_col) {
+        Ok(list) => list,
+        Err(err) => return err,
+    };
+    let fn_obj = match extract_fn_arg(&args[0], target_line, target_col) {
+        Ok(fn_obj) => fn_obj,
+        Err(err) => return err,
+    };
+
+    let mut objs = Vec::new();
+    for obj in list {
+        let res_obj = eval
+            .call_fn_obj(&fn_obj, vec![obj.clone()], target_line, target_col)
+            .await;
+        match fn_result_condition(res_obj, target_line, target_col) {
+            Ok(true) => objs.push(obj),
+            Ok(false) => {}
+            Err(err) => return err,
+        }
+    }
+    ResultObj::Ref(new_rc_object(Object::List(objs)))
+}
+
+// [1, 2, 3].reducir(0, fn(total, x) { total + x; }) -> 6
+pub async fn reducir(
+    eval: &mut Evaluator,
+    mut args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if args.len() != 2 {
+        return missmatch_args(2, args.len(), "reducir".len(), target_line, target_col);
+    }
+    let list = match list_items("reducir", &target, target_line, target_col) {
+        Ok(list) => list,
+        Err(err) => return err,
+    };
+    let mut acc = args.remove(0);
+    if eval.is_error(&acc) {
+        return acc;
+    }
+    let fn_obj = match extract_fn_arg(&args[0], target_line, target_col) {
+        Ok(fn_obj) => fn_obj,
+        Err(err) => return err,
+    };
+
+    for obj in list {
+        let res_obj = eval
+            .call_fn_obj(&fn_obj, vec![acc, obj], target_line, target_col)
+            .await;
+        match fn_result_value(res_obj, target_line, target_col) {
+            Ok(obj) => acc = obj,
+            Err(err) => return err,
+        }
+    }
+    acc
+}
+
+// [1, 2, 3].cada(fn(x) { dibujar_circulo(x * 10, 10, 5); })
+pub async fn cada(
+    eval: &mut Evaluator,
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args(1, args.len(), "cada".len(), target_line, target_col);
+    }
+    let list = match list_items("cada", &target, target_line, target_col) {
+        Ok(list) => list,
+        Err(err) => return err,
+    };
+    let fn_obj = match extract_fn_arg(&args[0], target_line, target_col) {
+        Ok(fn_obj) => fn_obj,
+        Err(err) => return err,
+    };
+
+    for obj in list {
+        let res_obj = eval
+            .call_fn_obj(&fn_obj, vec![obj], target_line, target_col)
+            .await;
+        if eval.is_error(&res_obj) {
+            return res_obj;
+        }
+    }
+    ResultObj::Copy(Object::Void)
+}
+
+// [1, 2, 3].alguno(fn(x) { x == 2; }) -> verdad
+pub async fn alguno(
+    eval: &mut Evaluator,
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args(1, args.len(), "alguno".len(), target_line, target_col);
+    }
+    let list = match list_items("alguno", &target, target_line, target_col) {
+        Ok(list) => list,
+        Err(err) => return err,
+    };
+    let fn_obj = match extract_fn_arg(&args[0], target_line, target_col) {
+        Ok(fn_obj) => fn_obj,
+        Err(err) => return err,
+    };
+
+    for obj in list {
+        let res_obj = eval
+            .call_fn_obj(&fn_obj, vec![obj], target_line, target_col)
+            .await;
+        match fn_result_condition(res_obj, target_line, target_col) {
+            Ok(true) => return ResultObj::Copy(Object::Boolean(true)),
+            Ok(false) => {}
+            Err(err) => return err,
+        }
+    }
+    ResultObj::Copy(Object::Boolean(false))
+}
+
+// [1, 2, 3].todos(fn(x) { x > 0; }) -> verdad
+pub async fn todos(
+    eval: &mut Evaluator,
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args(1, args.len(), "todos".len(), target_line, target_col);
+    }
+    let list = match list_items("todos", &target, target_line, target_col) {
+        Ok(list) => list,
+        Err(err) => return err,
+    };
+    let fn_obj = match extract_fn_arg(&args[0], target_line, target_col) {
+        Ok(fn_obj) => fn_obj,
+        Err(err) => return err,
+    };
+
+    for obj in list {
+        let res_obj = eval
+            .call_fn_obj(&fn_obj, vec![obj], target_line, target_col)
+            .await;
+        match fn_result_condition(res_obj, target_line, target_col) {
+            Ok(true) => {}
+            Ok(false) => return ResultObj::Copy(Object::Boolean(false)),
+            Err(err) => return err,
+        }
+    }
+    ResultObj::Copy(Object::Boolean(true))
+}
+
+// [1, 2, 3].encontrar(fn(x) { x > 1; }) -> 2
+pub async fn encontrar(
+    eval: &mut Evaluator,
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args(1, args.len(), "encontrar".len(), target_line, target_col);
+    }
+    let list = match list_items("encontrar", &target, target_line, target_col) {
+        Ok(list) => list,
+        Err(err) => return err,
+    };
+    let fn_obj = match extract_fn_arg(&args[0], target_line, target_col) {
+        Ok(fn_obj) => fn_obj,
+        Err(err) => return err,
+    };
+
+    for obj in list {
+        let res_obj = eval
+            .call_fn_obj(&fn_obj, vec![obj.clone()], target_line, target_col)
+            .await;
+        match fn_result_condition(res_obj, target_line, target_col) {
+            Ok(true) => return obj,
+            Ok(false) => {}
+            Err(err) => return err,
+        }
+    }
+    ResultObj::Copy(Object::Null)
+}
+
+fn vector_target(
+    name: &str,
+    target: &ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> Result<Vector, ResultObj> {
+    match target {
+        ResultObj::Copy(Object::Vector(vector)) => Ok(*vector),
+        ResultObj::Copy(obj) => Err(missmatch_type(
+            name,
+            &obj.get_type(),
+            target_line,
+            target_col,
+        )),
+        ResultObj::Ref(ref_obj) => Err(missmatch_type(
+            name,
+            &ref_obj.borrow().get_type(),
+            target_line,
+            target_col,
+        )),
+    }
+}
+
+// vector(3, 4).x() -> 3
+pub fn vector_x(
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args(0, args.len(), "x".len(), target_line, target_col);
+    }
+    match vector_target("x", &target, target_line, target_col) {
+        Ok(vector) => ResultObj::Copy(Object::Numeric(Numeric::Float(vector.x))),
+        Err(err) => err,
+    }
+}
+
+// vector(3, 4).y() -> 4
+pub fn vector_y(
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args(0, args.len(), "y".len(), target_line, target_col);
+    }
+    match vector_target("y", &target, target_line, target_col) {
+        Ok(vector) => ResultObj::Copy(Object::Numeric(Numeric::Float(vector.y))),
+        Err(err) => err,
+    }
+}
+
+// vector(3, 4).longitud() -> 5
+pub fn longitud(
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args(0, args.len(), "longitud".len(), target_line, target_col);
+    }
+    match vector_target("longitud", &target, target_line, target_col) {
+        Ok(vector) => ResultObj::Copy(Object::Numeric(Numeric::Float(vector.length()))),
+        Err(err) => err,
+    }
+}
+
+// vector(3, 4).normalizar() -> vector(0.6, 0.8)
+pub fn normalizar(
+    args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args(0, args.len(), "normalizar".len(), target_line, target_col);
+    }
+    match vector_target("normalizar", &target, target_line, target_col) {
+        Ok(vector) => ResultObj::Copy(Object::Vector(vector.normalize())),
+        Err(err) => err,
+    }
+}
+
+// vector(1, 0).rotar(PI / 2) -> vector(0, 1), en radianes como `rotar`
+pub fn rotar(
+    eval: &mut Evaluator,
+    mut args: FnArgs,
+    target: ResultObj,
+    target_line: usize,
+    target_col: usize,
+) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args(1, args.len(), "rotar".len(), target_line, target_col);
+    }
+    let vector = match vector_target("rotar", &target, target_line, target_col) {
+        Ok(vector) => vector,
+        Err(err) => return err,
+    };
+    let angle = match args.remove(0) {
+        ResultObj::Copy(Object::Numeric(Numeric::Int(int))) => int as f64,
+        ResultObj::Copy(Object::Numeric(Numeric::Float(float))) => float,
+        obj if eval.is_error(&obj) => return obj,
+        obj => return missmatch_type_arg("numerico", &obj.get_type(), target_line, target_col),
+    };
+    ResultObj::Copy(Object::Vector(vector.rotate(angle)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        eval::{
+            environment::{Environment, RcEnvironment},
+            evaluator::Evaluator,
+            objects::ResultObj,
+            tarea::block_on,
+        },
+        lexer::Lexer,
+        parser::Parser,
+    };
+
+    fn show(res: ResultObj) -> String {
+        match res {
+            ResultObj::Copy(obj) => obj.to_string(),
+            ResultObj::Ref(obj) => obj.borrow().to_string(),
+        }
+    }
+
+    // Resultado del programa y sus variables
+    fn run(code: &str) -> (String, RcEnvironment) {
+        let mut parser = Parser::new(Lexer::new(code.chars().collect()));
+        let program = parser.parse();
+        assert!(parser.error.is_none(), "{}", code);
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        let res = show(block_on(eval.eval_program(&program, &env)));
+        (res, env)
+    }
+
+    fn var(env: &RcEnvironment, name: &str) -> String {
+        show(env.borrow().get(&name.to_owned()).unwrap())
+    }
+
+    #[test]
+    fn callbacks_on_empty_list() {
+        let code = "var n = 0;\n\
+            var m = [].mapear(fn(x) { n = n + 1; retornar x; });\n\
+            var f = [].filtrar(fn(x) { n = n + 1; retornar verdad; });\n\
+            var r = [].reducir(10, fn(t, x) { n = n + 1; retornar t + x; });";
+        let (_, env) = run(code);
+        assert_eq!(var(&env, "m"), "[]");
+        assert_eq!(var(&env, "f"), "[]");
+        assert_eq!(var(&env, "r"), "10");
+        assert_eq!(var(&env, "n"), "0");
+    }
+
+    #[test]
+    fn callback_error_stops_the_loop() {
+        let calls = [
+            "[1, 2, 3].mapear(fn(x)",
+            "[1, 2, 3].filtrar(fn(x)",
+            "[1, 2, 3].reducir(0, fn(t, x)",
+        ];
+        for call in calls {
+            let code = format!(
+                "var n = 0;\nvar r = {} {{ n = n + 1; si x == 2 {{ no_existe; }} retornar x; }});",
+                call
+            );
+            let (res, env) = run(&code);
+            assert!(res.contains("no_existe"), "{}: {}", call, res);
+            assert_eq!(var(&env, "n"), "2", "{}", call);
+        }
+    }
+
+    #[test]
+    fn callback_without_value() {
+        let (res, _) = run("[1].mapear(fn(x) { si falso { x; } });");
+        assert!(res.contains("no retorno ningun valor"), "{}", res);
+        let (res, _) = run("[1].reducir(0, fn(t, x) { si falso { x; } });");
+        assert!(res.contains("no retorno ningun valor"), "{}", res);
+    }
+
+    #[test]
+    fn comparator_must_return_boolean_or_number() {
+        for value in ["\"si\"", "nulo", "[1]"] {
+            let code = format!(
+                "var l = [3, 1, 2];\nvar r = l.ordenar(fn(a, b) {{ {}; }});",
+                value
+            );
+            let (res, env) = run(&code);
+            assert!(res.contains("logico o numerico"), "{}: {}", value, res);
+            // La lista queda como estaba
+            assert_eq!(var(&env, "l"), "[3, 1, 2]", "{}", value);
+        }
+    }
+
+    #[test]
+    fn comparator_with_numbers() {
+        let (res, _) = run("[3, 1, 2].ordenar(fn(a, b) { a - b; });");
+        assert_eq!(res, "[1, 2, 3]");
+        let (res, _) = run("[3, 1, 2].ordenar(fn(a, b) { b - a; });");
+        assert_eq!(res, "[3, 2, 1]");
+        // Con un solo elemento no se llama al comparador
+        let (res, _) = run("[1].ordenar(fn(a, b) { \"si\"; });");
+        assert_eq!(res, "[1]");
+    }
+}
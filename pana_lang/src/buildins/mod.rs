@@ -1,3 +1,61 @@
+pub mod camara;
+pub mod colores;
+pub mod datos;
+pub mod entrada;
+pub mod fecha;
+pub mod formas;
+pub mod geometria;
 pub mod internal;
+pub mod json;
+pub mod mapas;
+pub mod math;
 pub mod member;
+pub mod particulas;
+pub mod pausa;
 pub mod rng;
+pub mod tortuga;
+pub mod transformacion;
+
+use crate::{
+    eval::objects::{Object, ResultObj},
+    types::Numeric,
+};
+
+// Ayudas que comparten las funciones internas
+
+pub fn error(msg: String) -> ResultObj {
+    ResultObj::Copy(Object::Error(msg))
+}
+
+pub fn missmatch_args(expected: &str, len: usize) -> ResultObj {
+    error(format!("Se encontro {} argumentos de {}", len, expected))
+}
+
+pub fn numeric_error() -> ResultObj {
+    error("Se espera un tipo de dato numerico".to_string())
+}
+
+pub fn to_f64(num: &Numeric) -> f64 {
+    match num {
+        Numeric::Int(v) => *v as f64,
+        Numeric::Float(v) => *v,
+    }
+}
+
+// Verifica que todos los argumentos sean numericos
+pub fn extract_numeric_args(args: &[ResultObj]) -> Result<Vec<Numeric>, ResultObj> {
+    let mut nums = Vec::with_capacity(args.len());
+    for arg in args {
+        match arg {
+            ResultObj::Copy(Object::Numeric(num)) => nums.push(num.clone()),
+            ResultObj::Copy(Object::Error(_)) => return Err(arg.clone()),
+            obj => {
+                return Err(error(format!(
+                    "Se espera un tipo de dato numerico, no {}",
+                    obj.get_type()
+                )))
+            }
+        }
+    }
+    Ok(nums)
+}
@@ -0,0 +1,332 @@
+use crate::{
+    eval::{
+        evaluator::Evaluator,
+        objects::{new_rc_object, FnArgs, Object, ResultObj},
+    },
+    lienzo::{
+        particulas::{Emisor, Opciones},
+        Color,
+    },
+    types::Numeric,
+};
+
+use super::{error, geometria::extract_coords, internal::extract_color, missmatch_args};
+
+const OPTIONS: &str = "ritmo, vida, velocidad, direccion, dispersion, gravedad, colores o tamano";
+
+// El primer argumento, que debe ser un emisor
+fn extract_emisor(args: &[ResultObj]) -> Result<ResultObj, ResultObj> {
+    let obj = args[0].clone();
+    if let ResultObj::Copy(Object::Error(_)) = obj {
+        return Err(obj);
+    }
+    let is_emisor = match &obj {
+        ResultObj::Ref(ref_obj) => matches!(*ref_obj.borrow(), Object::Emisor(_)),
+        ResultObj::Copy(_) => false,
+    };
+    if is_emisor {
+        return Ok(obj);
+    }
+    Err(error(format!(
+        "Se espera un tipo de dato emisor, no {}",
+        obj.get_type()
+    )))
+}
+
+fn with_emisor<T>(obj: &ResultObj, f: impl FnOnce(&mut Emisor) -> T) -> T {
+    let ResultObj::Ref(ref_obj) = obj else {
+        unreachable!("`extract_emisor` solo retorna emisores");
+    };
+    let Object::Emisor(ref mut emisor) = *ref_obj.borrow_mut() else {
+        unreachable!("`extract_emisor` solo retorna emisores");
+    };
+    f(emisor)
+}
+
+fn option_number(name: &str, value: &ResultObj) -> Result<f32, ResultObj> {
+    match value {
+        ResultObj::Copy(Object::Numeric(Numeric::Int(int))) => Ok(*int as f32),
+        ResultObj::Copy(Object::Numeric(Numeric::Float(float))) => Ok(*float as f32),
+        obj => Err(error(format!(
+            "La opcion `{}` espera un tipo de dato numerico, no {}",
+            name,
+            obj.get_type()
+        ))),
+    }
+}
+
+// Aplica las llaves del diccionario sobre `options`, las que faltan quedan igual
+fn apply_options(options: &mut Opciones, obj: ResultObj) -> Result<(), ResultObj> {
+    let ResultObj::Ref(ref_obj) = &obj else {
+        return Err(error(format!(
+            "Se espera un diccionario con las opciones, no {}",
+            obj.get_type()
+        )));
+    };
+    let Object::Dictionary(pairs) = &*ref_obj.borrow() else {
+        return Err(error(format!(
+            "Se espera un diccionario con las opciones, no {}",
+            obj.get_type()
+        )));
+    };
+    for (key, value) in pairs {
+        let name = match key {
+            ResultObj::Ref(key) => match &*key.borrow() {
+                Object::String(name) => name.clone(),
+                _ => String::new(),
+            },
+            ResultObj::Copy(_) => String::new(),
+        };
+        match name.as_str() {
+            "ritmo" => options.rate = option_number(&name, value)?.max(0.0),
+            "vida" => options.lifetime = option_number(&name, value)?,
+            "velocidad" => options.speed = option_number(&name, value)?,
+            "direccion" => options.direction = option_number(&name, value)?,
+            "dispersion" => options.spread = option_number(&name, value)?,
+            "gravedad" => options.gravity = option_number(&name, value)?,
+            "tamano" => options.size = option_number(&name, value)?.max(0.0),
+            "colores" => options.colors = extract_colors(value.clone())?,
+            _ => {
+                return Err(error(format!(
+                    "El emisor no tiene la opcion `{}`, las opciones son {}",
+                    key, OPTIONS
+                )))
+            }
+        }
+    }
+    if options.lifetime.is_nan() || options.lifetime <= 0.0 {
+        return Err(error(
+            "La vida de las particulas debe ser mayor a 0".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+// Un color o una lista de colores
+fn extract_colors(obj: ResultObj) -> Result<Vec<Color>, ResultObj> {
+    let items = match &obj {
+        ResultObj::Ref(ref_obj) => match &*ref_obj.borrow() {
+            Object::List(items) => items.clone(),
+            _ => vec![obj.clone()],
+        },
+        ResultObj::Copy(_) => vec![obj.clone()],
+    };
+    if items.is_empty() {
+        return Err(error(
+            "La opcion `colores` necesita al menos un color".to_string(),
+        ));
+    }
+    items.into_iter().map(extract_color).collect()
+}
+
+// crear_emisor(x, y, {"ritmo": 50, "colores": [AMARILLO, ROJO]}), las opciones
+// son opcionales
+pub fn crear_emisor(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() || args.len() > 3 {
+        return missmatch_args("2 o 3", args.len());
+    }
+    // Las opciones van al final, despues de la posicion o de un vector
+    let mut options = Opciones::default();
+    let mut coords = Vec::with_capacity(2);
+    for (i, arg) in args.iter().enumerate() {
+        match arg.clone() {
+            ResultObj::Copy(Object::Numeric(Numeric::Int(int))) => coords.push(int as f32),
+            ResultObj::Copy(Object::Numeric(Numeric::Float(float))) => coords.push(float as f32),
+            ResultObj::Copy(Object::Vector(vector)) => {
+                coords.extend([vector.x as f32, vector.y as f32])
+            }
+            obj if eval.is_error(&obj) => return obj,
+            obj @ ResultObj::Ref(_) if i > 0 && i == args.len() - 1 => {
+                if let Err(err) = apply_options(&mut options, obj) {
+                    return err;
+                }
+            }
+            obj => {
+                return error(format!(
+                    "Se espera un tipo de dato numerico o vector, no {}",
+                    obj.get_type()
+                ))
+            }
+        }
+    }
+    let [x, y] = coords[..] else {
+        return error("Se espera la posicion x, y del emisor".to_string());
+    };
+    let seed = eval.emitter_seed;
+    eval.emitter_seed += 1;
+    ResultObj::Ref(new_rc_object(Object::Emisor(Emisor::new(
+        (x, y),
+        options,
+        seed,
+    ))))
+}
+
+// cambiar_emisor(emisor, {"ritmo": 0}) -> cambia solo las opciones indicadas
+pub fn cambiar_emisor(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 {
+        return missmatch_args("2", args.len());
+    }
+    let emisor = match extract_emisor(&args) {
+        Ok(emisor) => emisor,
+        Err(err) => return err,
+    };
+    let obj = args[1].clone();
+    if eval.is_error(&obj) {
+        return obj;
+    }
+    let mut options = with_emisor(&emisor, |emisor| emisor.options.clone());
+    if let Err(err) = apply_options(&mut options, obj) {
+        return err;
+    }
+    with_emisor(&emisor, |emisor| emisor.options = options);
+    ResultObj::Copy(Object::Void)
+}
+
+// mover_emisor(emisor, x, y) -> las particulas nuevas salen desde ahi
+pub fn mover_emisor(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.is_empty() {
+        return missmatch_args("3", args.len());
+    }
+    let emisor = match extract_emisor(&args) {
+        Ok(emisor) => emisor,
+        Err(err) => return err,
+    };
+    let pos = match extract_coords(&args[1..], 2) {
+        Ok(pos) => (pos[0] as f32, pos[1] as f32),
+        Err(err) => return err,
+    };
+    with_emisor(&emisor, |emisor| emisor.pos = pos);
+    ResultObj::Copy(Object::Void)
+}
+
+// emitir(emisor, 100) -> lanza 100 particulas de una vez, para explosiones
+pub fn emitir(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 2 {
+        return missmatch_args("2", args.len());
+    }
+    let emisor = match extract_emisor(&args) {
+        Ok(emisor) => emisor,
+        Err(err) => return err,
+    };
+    match args[1].clone() {
+        ResultObj::Copy(Object::Numeric(Numeric::Int(count))) => {
+            with_emisor(&emisor, |emisor| emisor.emit(count.max(0) as usize));
+            ResultObj::Copy(Object::Void)
+        }
+        obj if eval.is_error(&obj) => obj,
+        obj => error(format!(
+            "Se espera un tipo de dato numerico entero, no {}",
+            obj.get_type()
+        )),
+    }
+}
+
+// dibujar_emisor(emisor) -> avanza las particulas un cuadro y las dibuja
+pub fn dibujar_emisor(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    let emisor = match extract_emisor(&args) {
+        Ok(emisor) => emisor,
+        Err(err) => return err,
+    };
+    let seconds = eval.frame_seconds as f32;
+    let rects = with_emisor(&emisor, |emisor| {
+        emisor.update(seconds);
+        emisor.rects()
+    });
+    eval.draw(|lienzo| lienzo.rects_filled(&rects));
+    ResultObj::Copy(Object::Void)
+}
+
+// cantidad_particulas(emisor) -> particulas vivas, 0 cuando termino una explosion
+pub fn cantidad_particulas(_eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match extract_emisor(&args) {
+        Ok(emisor) => ResultObj::Copy(Object::Numeric(Numeric::Int(with_emisor(
+            &emisor,
+            |emisor| emisor.len() as i64,
+        )))),
+        Err(err) => err,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use crate::{
+        eval::{environment::Environment, tarea::block_on},
+        lexer::Lexer,
+        lienzo::{
+            grabadora::{Comando, Grabadora},
+            particulas::MAX_PARTICLES,
+        },
+        parser::{statement::BlockStatement, Parser},
+    };
+
+    use super::*;
+
+    fn parse(code: &str) -> BlockStatement {
+        let mut parser = Parser::new(Lexer::new(code.chars().collect()));
+        let program = parser.parse();
+        assert!(parser.error.is_none(), "{}", code);
+        program
+    }
+
+    // Ejecuta `setup` y luego `frame` en cada cuadro, como `Bucle`, y
+    // retorna lo que se dibujo
+    fn simulate(setup: &str, frame: &str, frames: usize) -> Vec<Comando> {
+        let grabadora = Grabadora::new();
+        let mut eval = Evaluator::new(Some(Box::new(grabadora.clone())), 100.0, 100.0);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        block_on(eval.eval_program(&parse(setup), &env));
+        let frame = parse(frame);
+        for _ in 0..frames {
+            eval.begin_frame(1.0 / 60.0);
+            block_on(eval.eval_program(&frame, &env));
+        }
+        grabadora.comandos()
+    }
+
+    #[test]
+    fn emitters_are_deterministic_across_runs() {
+        let setup = "var fuego = crear_emisor(50, 90, {\"ritmo\": 200, \"dispersion\": TAU});
+            var chispas = crear_emisor(20, 20, {\"ritmo\": 0, \"gravedad\": 100});
+            emitir(chispas, 50);";
+        let frame = "dibujar_emisor(fuego); dibujar_emisor(chispas);";
+        let first = simulate(setup, frame, 30);
+        assert!(!first.is_empty());
+        assert_eq!(first, simulate(setup, frame, 30));
+
+        // Cada emisor del programa tiene su propia semilla
+        let twins = "var a = crear_emisor(50, 50, {\"ritmo\": 0});
+            var b = crear_emisor(50, 50, {\"ritmo\": 0});
+            emitir(a, 10);
+            emitir(b, 10);";
+        let drawn = simulate(twins, "dibujar_emisor(a); dibujar_emisor(b);", 1);
+        assert_eq!(drawn.len(), 2);
+        assert_ne!(drawn[0], drawn[1]);
+    }
+
+    #[test]
+    fn emitter_particle_cap() {
+        let grabadora = Grabadora::new();
+        let mut eval = Evaluator::new(Some(Box::new(grabadora)), 100.0, 100.0);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        let code = format!(
+            "var e = crear_emisor(0, 0);
+            emitir(e, {});
+            emitir(e, 10);
+            var n = cantidad_particulas(e);",
+            MAX_PARTICLES + 1
+        );
+        block_on(eval.eval_program(&parse(&code), &env));
+        let n = env.borrow().get(&"n".to_owned()).unwrap();
+        assert!(
+            matches!(n, ResultObj::Copy(Object::Numeric(Numeric::Int(n))) if n == MAX_PARTICLES as i64)
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use crate::{
+    eval::{
+        cuadros::SIN_CUADROS,
+        evaluator::Evaluator,
+        objects::{FnArgs, Object, ResultObj},
+        tarea::Tarea,
+    },
+    types::Numeric,
+};
+
+use super::{missmatch_args, numeric_error};
+
+// Deja pasar un cuadro y retorna cuantos segundos duro
+async fn next_frame(eval: &mut Evaluator, keep: bool) -> Result<f64, ResultObj> {
+    let Some(mut cuadros) = eval.cuadros.take() else {
+        return Err(ResultObj::Copy(Object::Error(
+            "Esperar cuadros no esta disponible en esta aplicacion".to_string(),
+        )));
+    };
+    let seconds = cuadros.siguiente_cuadro(eval, keep).await;
+    eval.cuadros = Some(cuadros);
+    if let Some(seconds) = seconds {
+        eval.frame_seconds = seconds;
+    }
+    seconds.ok_or(ResultObj::Copy(Object::Error(SIN_CUADROS.to_string())))
+}
+
+// esperar(0.5) -> sigue medio segundo despues, con lo dibujado en pantalla
+pub fn esperar(eval: &mut Evaluator, args: FnArgs) -> Tarea<'_, ResultObj> {
+    Box::pin(async move {
+        if args.len() != 1 {
+            return missmatch_args("1", args.len());
+        }
+        let seconds = match args[0].clone() {
+            ResultObj::Copy(Object::Numeric(Numeric::Int(n))) => n as f64,
+            ResultObj::Copy(Object::Numeric(Numeric::Float(n))) => n,
+            _ => return numeric_error(),
+        };
+        let mut waited = 0.0;
+        while waited < seconds {
+            match next_frame(eval, true).await {
+                Ok(frame) => waited += frame,
+                Err(err) => return err,
+            }
+        }
+        ResultObj::Copy(Object::Void)
+    })
+}
+
+// siguiente_cuadro() -> sigue en el proximo cuadro, que empieza vacio como en `Bucle`
+pub fn siguiente_cuadro(eval: &mut Evaluator, args: FnArgs) -> Tarea<'_, ResultObj> {
+    Box::pin(async move {
+        if !args.is_empty() {
+            return missmatch_args("0", args.len());
+        }
+        match next_frame(eval, false).await {
+            Ok(_) => ResultObj::Copy(Object::Void),
+            Err(err) => err,
+        }
+    })
+}
@@ -1,3 +1,4 @@
+#[derive(Clone, Debug, PartialEq)]
 pub struct Rng {
     state: (u64, u64),
 }
@@ -41,6 +42,11 @@ impl Rng {
         }
     }
 
+    // Flotante en [0, 1)
+    pub fn rand_f64(&mut self) -> f64 {
+        (self.rand_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
     pub fn rand_range_i64(&mut self, a: i64, b: i64) -> i64 {
         a + self.rand_bounded_u64((b - a) as u64) as i64
     }
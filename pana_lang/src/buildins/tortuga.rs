@@ -0,0 +1,144 @@
+use crate::{
+    eval::{
+        evaluator::Evaluator,
+        objects::{FnArgs, Object, ResultObj},
+    },
+    types::Numeric,
+};
+
+use super::{
+    extract_numeric_args,
+    internal::{extract_color, extract_f32_from_numeric},
+    missmatch_args,
+};
+
+fn void() -> ResultObj {
+    ResultObj::Copy(Object::Void)
+}
+
+// El unico argumento numerico de la funcion
+fn extract_numeric_arg(args: &[ResultObj]) -> Result<Numeric, ResultObj> {
+    if args.len() != 1 {
+        return Err(missmatch_args("1", args.len()));
+    }
+    Ok(extract_numeric_args(args)?.remove(0))
+}
+
+// avanzar(50) -> avanza dibujando si el lapiz esta abajo
+pub fn avanzar(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_numeric_arg(&args) {
+        Ok(distance) => {
+            eval.ensure_turtle()
+                .forward(extract_f32_from_numeric(distance));
+            void()
+        }
+        Err(err) => err,
+    }
+}
+
+// retroceder(50) -> igual que avanzar(-50)
+pub fn retroceder(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_numeric_arg(&args) {
+        Ok(distance) => {
+            eval.ensure_turtle()
+                .forward(-extract_f32_from_numeric(distance));
+            void()
+        }
+        Err(err) => err,
+    }
+}
+
+// girar(90) -> gira a la derecha, girar(-90) a la izquierda
+pub fn girar(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_numeric_arg(&args) {
+        Ok(degrees) => {
+            eval.ensure_turtle().turn(extract_f32_from_numeric(degrees));
+            void()
+        }
+        Err(err) => err,
+    }
+}
+
+pub fn subir_lapiz(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    eval.ensure_turtle().pen_down = false;
+    void()
+}
+
+pub fn bajar_lapiz(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    eval.ensure_turtle().pen_down = true;
+    void()
+}
+
+// color_lapiz(0xFF0000)
+pub fn color_lapiz(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match extract_color(args[0].clone()) {
+        Ok(color) => {
+            eval.ensure_turtle().color = color;
+            void()
+        }
+        Err(err) => err,
+    }
+}
+
+// grosor(3) -> ancho del lapiz en pixeles
+pub fn grosor(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_numeric_arg(&args) {
+        Ok(width) if extract_f32_from_numeric(width.clone()) > 0.0 => {
+            eval.ensure_turtle().width = extract_f32_from_numeric(width);
+            void()
+        }
+        Ok(_) => ResultObj::Copy(Object::Error(
+            "El grosor del lapiz debe ser mayor a 0".to_string(),
+        )),
+        Err(err) => err,
+    }
+}
+
+// velocidad_tortuga(2) -> muestra 2 pasos por cuadro, 0 dibuja todo de una vez
+pub fn velocidad_tortuga(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_numeric_arg(&args) {
+        Ok(Numeric::Int(speed)) if speed >= 0 => {
+            eval.ensure_turtle().speed = speed as usize;
+            void()
+        }
+        Ok(_) => ResultObj::Copy(Object::Error(
+            "La velocidad debe ser un numero entero positivo o 0".to_string(),
+        )),
+        Err(err) => err,
+    }
+}
+
+// mostrar_tortuga(falso) -> oculta el triangulo de la tortuga
+pub fn mostrar_tortuga(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if args.len() != 1 {
+        return missmatch_args("1", args.len());
+    }
+    match args[0].clone() {
+        ResultObj::Copy(Object::Boolean(visible)) => {
+            eval.ensure_turtle().visible = visible;
+            void()
+        }
+        _ => ResultObj::Copy(Object::Error(
+            "Se espera un tipo de dato logico".to_string(),
+        )),
+    }
+}
+
+// reiniciar_tortuga() -> borra el camino y vuelve al centro mirando arriba
+pub fn reiniciar_tortuga(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    eval.tortuga = None;
+    eval.ensure_turtle();
+    void()
+}
@@ -0,0 +1,88 @@
+use crate::eval::{
+    evaluator::Evaluator,
+    objects::{FnArgs, Object, ResultObj},
+};
+
+use super::{extract_numeric_args, internal::extract_f32_from_numeric, missmatch_args};
+
+// Cantidad maxima de `guardar_estado` sin su `restaurar_estado`
+const MAX_SAVED_STATES: usize = 256;
+
+// Extrae `min` a `max` argumentos numericos
+fn extract_numbers(args: &[ResultObj], min: usize, max: usize) -> Result<Vec<f32>, ResultObj> {
+    if args.len() < min || args.len() > max {
+        let expected = match min == max {
+            true => min.to_string(),
+            false => format!("{} o {}", min, max),
+        };
+        return Err(missmatch_args(&expected, args.len()));
+    }
+    let nums = extract_numeric_args(args)?;
+    Ok(nums.into_iter().map(extract_f32_from_numeric).collect())
+}
+
+// trasladar(100, 50) -> el origen pasa a (100, 50)
+pub fn trasladar(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_numbers(&args, 2, 2) {
+        Ok(nums) => {
+            eval.transform = eval.transform.translate(nums[0], nums[1]);
+            ResultObj::Copy(Object::Void)
+        }
+        Err(err) => err,
+    }
+}
+
+// rotar(PI / 4) -> gira en radianes alrededor del origen
+pub fn rotar(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_numbers(&args, 1, 1) {
+        Ok(nums) => {
+            eval.transform = eval.transform.rotate(nums[0]);
+            ResultObj::Copy(Object::Void)
+        }
+        Err(err) => err,
+    }
+}
+
+// escalar(2) o escalar(2, 0.5)
+pub fn escalar(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    match extract_numbers(&args, 1, 2) {
+        Ok(nums) => {
+            let (x, y) = (nums[0], *nums.get(1).unwrap_or(&nums[0]));
+            eval.transform = eval.transform.scale(x, y);
+            ResultObj::Copy(Object::Void)
+        }
+        Err(err) => err,
+    }
+}
+
+// guardar_estado() -> guarda la transformacion y el estilo
+pub fn guardar_estado(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    if eval.saved_states.len() >= MAX_SAVED_STATES {
+        return ResultObj::Copy(Object::Error(format!(
+            "Se llamo `guardar_estado` mas de {} veces sin `restaurar_estado`",
+            MAX_SAVED_STATES
+        )));
+    }
+    eval.saved_states.push((eval.transform, eval.estilo));
+    ResultObj::Copy(Object::Void)
+}
+
+// restaurar_estado() -> vuelve al ultimo estado guardado
+pub fn restaurar_estado(eval: &mut Evaluator, args: FnArgs) -> ResultObj {
+    if !args.is_empty() {
+        return missmatch_args("0", args.len());
+    }
+    match eval.saved_states.pop() {
+        Some((transform, estilo)) => {
+            eval.transform = transform;
+            eval.estilo = estilo;
+            ResultObj::Copy(Object::Void)
+        }
+        None => ResultObj::Copy(Object::Error(
+            "`restaurar_estado` no tiene un `guardar_estado` que restaurar".to_string(),
+        )),
+    }
+}
@@ -0,0 +1,87 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::BTreeMap,
+    rc::Rc,
+};
+
+use super::json::{Json, JsonError};
+
+// Limites de `guardar_dato`, contando el texto JSON de cada valor
+pub const MAX_KEY_CHARS: usize = 64;
+pub const MAX_VALUE_BYTES: usize = 16 * 1024;
+pub const MAX_TOTAL_BYTES: usize = 256 * 1024;
+
+// Datos de `guardar_dato` y `cargar_dato`, que duran entre ejecuciones del
+// programa. Se leen y escriben como un diccionario JSON; cada aplicacion decide
+// donde guardar ese texto. Las copias comparten los mismos datos.
+#[derive(Clone, Default)]
+pub struct Datos {
+    entries: Rc<RefCell<BTreeMap<String, Json>>>,
+    // Hubo cambios desde `from_json`
+    changed: Rc<Cell<bool>>,
+}
+
+impl Datos {
+    pub fn from_json(text: &str) -> Result<Self, JsonError> {
+        let Json::Dictionary(entries) = Json::parse(text)? else {
+            return Err(JsonError {
+                msg: "Los datos guardados deben ser un diccionario".to_string(),
+                line: 1,
+                col: 1,
+            });
+        };
+        Ok(Self {
+            entries: Rc::new(RefCell::new(entries.into_iter().collect())),
+            changed: Rc::default(),
+        })
+    }
+
+    pub fn to_json(&self) -> String {
+        let entries = self.entries.borrow();
+        let entries = entries
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        Json::Dictionary(entries).write(true)
+    }
+
+    pub fn changed(&self) -> bool {
+        self.changed.get()
+    }
+
+    pub fn cargar(&self, clave: &str) -> Option<Json> {
+        self.entries.borrow().get(clave).cloned()
+    }
+
+    // Guarda el valor si caben la llave, el valor y el total de los datos
+    pub fn guardar(&self, clave: &str, valor: Json) -> Result<(), String> {
+        if clave.is_empty() || clave.chars().count() > MAX_KEY_CHARS {
+            return Err(format!(
+                "La llave debe tener entre 1 y {} caracteres",
+                MAX_KEY_CHARS
+            ));
+        }
+        let size = valor.write(false).len();
+        if size > MAX_VALUE_BYTES {
+            return Err(format!(
+                "El valor ocupa {} bytes y el maximo es {}",
+                size, MAX_VALUE_BYTES
+            ));
+        }
+        let mut entries = self.entries.borrow_mut();
+        let total: usize = entries
+            .iter()
+            .filter(|(key, _)| key.as_str() != clave)
+            .map(|(key, value)| key.len() + value.write(false).len())
+            .sum();
+        if total + clave.len() + size > MAX_TOTAL_BYTES {
+            return Err(format!(
+                "No queda espacio, los datos guardados no pueden pasar de {} bytes",
+                MAX_TOTAL_BYTES
+            ));
+        }
+        entries.insert(clave.to_owned(), valor);
+        self.changed.set(true);
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+use super::{evaluator::Evaluator, tarea::Tarea};
+
+// Error de `esperar` y `siguiente_cuadro` cuando ya no hay mas cuadros. Sin
+// ventana es la forma normal de terminar un programa que no acaba solo.
+pub const SIN_CUADROS: &str = "No hay mas cuadros, el programa se detuvo";
+
+// Cuadros que pasan en medio del programa con `esperar` y `siguiente_cuadro`.
+// La aplicacion muestra lo dibujado hasta ahi y el programa sigue en un cuadro
+// posterior, desde la misma linea.
+pub trait Cuadros {
+    // Termina el cuadro actual y prepara el lienzo del siguiente. Con `keep`
+    // el cuadro nuevo empieza con lo que ya estaba dibujado. Retorna los
+    // segundos que duro el cuadro, o `None` si ya no van a haber mas cuadros.
+    fn siguiente_cuadro<'a>(
+        &'a mut self,
+        eval: &'a mut Evaluator,
+        keep: bool,
+    ) -> Tarea<'a, Option<f64>>;
+}
@@ -0,0 +1,9 @@
+use super::tarea::Tarea;
+
+// Origen del texto de `preguntar` y `leer_linea`. Cada aplicacion decide como
+// pedirlo: la terminal lee de la entrada estandar y la ventana muestra una caja
+// de texto. La llamada detiene el programa hasta que hay una respuesta.
+pub trait Entrada {
+    // Retorna la linea sin el salto final, o `None` si ya no hay mas texto
+    fn leer_linea<'a>(&'a mut self, pregunta: &'a str) -> Tarea<'a, Option<String>>;
+}
@@ -5,21 +5,82 @@ use std::{
 };
 
 use crate::buildins::{
+    camara::{camara_a_mundo, camara_posicion, camara_seguir, camara_zoom, usar_camara},
+    colores::{hsv, mezclar_color, rgb, rgba, NAMED_COLORS},
+    datos::{cargar_dato, guardar_dato},
+    entrada::{leer_linea, preguntar},
+    fecha::{ahora, formatear_fecha, marca_tiempo},
+    formas::{
+        dibujar_arco, dibujar_circulo, dibujar_elipse, dibujar_linea, dibujar_poligono,
+        dibujar_rectangulo, dibujar_triangulo, estilo_inicial, redondeo, relleno, sin_relleno,
+        sin_trazo, trazo,
+    },
+    geometria::{
+        angulo_entre, colisionan_circulos, colisionan_rectangulos, distancia, punto_en_rectangulo,
+        vector,
+    },
     internal::{
-        aleatorio, cadena, dibujar_circulo, dibujar_linea, dibujar_rectangulo, dibujar_texto,
-        lienzo_altura, lienzo_ancho, longitud, tipo, InternalFnPointer,
+        aleatorio, cadena, caracter_en, color, color_borde, color_texto, dibujar_texto,
+        documentacion, escribir, lienzo_altura, lienzo_ancho, limpiar_pantalla, longitud,
+        modo_pantalla, mostrar_cursor, obtener_pixel, poner_pixel, tipo, ubicar, InternalFn,
+    },
+    json::{a_json, desde_json},
+    mapas::{colisiona_mapa, crear_mapa, dibujar_mapa, obtener_tile, poner_tile, tile_en},
+    math::{
+        abs, atan2, coseno, interpolar, limitar, max, min, piso, potencia, raiz, redondear, seno,
+        tangente, techo,
     },
     member::match_member_fn,
+    particulas::{
+        cambiar_emisor, cantidad_particulas, crear_emisor, dibujar_emisor, emitir, mover_emisor,
+    },
+    pausa::{esperar, siguiente_cuadro},
+    tortuga::{
+        avanzar, bajar_lapiz, color_lapiz, girar, grosor, mostrar_tortuga, reiniciar_tortuga,
+        retroceder, subir_lapiz, velocidad_tortuga,
+    },
+    transformacion::{escalar, guardar_estado, restaurar_estado, rotar, trasladar},
+};
+use crate::lienzo::{
+    camara::Camara,
+    formas::Estilo,
+    fuente::GLYPH_SIZE,
+    paleta::C64,
+    rasterizador::{Rasterizador, BACKGROUND},
+    texto::{self, PantallaTexto},
+    tortuga::Tortuga,
+    transformacion::{Transformacion, Transformado},
+    Color, Lienzo,
 };
 use crate::parser::expression::{ExprType, Expression, FnParams};
 use crate::parser::statement::{BlockStatement, Statement};
-use crate::{token::TokenType, types::Numeric};
+use crate::{
+    token::TokenType,
+    types::{Numeric, Vector},
+};
 
+#[cfg(not(target_arch = "wasm32"))]
+use super::reloj::RelojSistema;
 use super::{
+    almacen::Datos,
+    cuadros::Cuadros,
+    entrada::Entrada,
     environment::{Environment, RcEnvironment},
-    objects::{new_rc_object, BuildinFnObj, FnExprObj, FnObj, Object, ResultObj},
+    objects::{new_rc_object, BuildinFnObj, FnArgs, FnExprObj, FnObj, Object, ResultObj},
+    reloj::Reloj,
+    tarea::Tarea,
 };
 
+// Llamadas anidadas a funciones del programa antes de dar error. Cada una ocupa
+// unos 2 KB de pila en el navegador y 3 KB en una compilacion optimizada, asi
+// cabe en la pila de 1 MB del navegador y de Windows.
+const MAX_DEPTH: usize = 200;
+// Las funciones que llaman `mapear`, `ordenar` y los demas metodos pasan por
+// mas marcos de la pila y cuentan como tres llamadas
+const CALLBACK_DEPTH: usize = 2;
+
+type SliceBound = Result<Option<i64>, ResultObj>;
+
 #[derive(PartialEq, Clone, Debug)]
 pub enum Context {
     Global,
@@ -29,104 +90,507 @@ pub enum Context {
 }
 
 pub struct CanvasSize {
-    pub top: f32,
     pub width: f32,
     pub height: f32,
 }
 
+// Pantalla de resolucion fija activada con `modo_pantalla`. La aplicacion la
+// escala en multiplos enteros y rellena el resto con el color del borde.
+pub struct ScreenMode {
+    pub width: usize,
+    pub height: usize,
+    pub border: Color,
+}
+
 pub struct Evaluator {
-    pub painter: Option<egui::Painter>,
+    pub lienzo: Option<Box<dyn Lienzo>>,
     pub canvas: CanvasSize,
-    buildins_internal_fn: HashMap<String, Box<dyn InternalFnPointer>>,
+    pub screen: Option<ScreenMode>,
+    // Bufer de `poner_pixel`. En `modo_pantalla` es la pantalla completa.
+    pub framebuffer: Option<Rasterizador>,
+    // Capa de texto de `escribir`, se dibuja encima de la pantalla
+    pub text: Option<PantallaTexto>,
+    pub tortuga: Option<Tortuga>,
+    pub estilo: Estilo,
+    // Matriz de `trasladar`, `rotar` y `escalar`, se reinicia en cada cuadro
+    pub transform: Transformacion,
+    // Estados de `guardar_estado`
+    pub saved_states: Vec<(Transformacion, Estilo)>,
+    // Se aplica antes de `transform` y se conserva entre cuadros
+    pub camara: Camara,
+    // `usar_camara(falso)` dibuja sin la camara, para marcadores y menus
+    pub camera_enabled: bool,
+    // Segundos que duro el cuadro anterior, para las particulas
+    pub frame_seconds: f64,
+    // Semilla del siguiente emisor de particulas. Empieza en 0 en cada
+    // ejecucion, asi el programa suelta las mismas particulas cada vez.
+    pub emitter_seed: u64,
+    // Texto de `preguntar` y `leer_linea`, `None` si la aplicacion no lo ofrece
+    pub entrada: Option<Box<dyn Entrada>>,
+    // Cuadros de `esperar` y `siguiente_cuadro`
+    pub cuadros: Option<Box<dyn Cuadros>>,
+    // Datos de `guardar_dato` y `cargar_dato`, `None` si la aplicacion no los guarda
+    pub datos: Option<Datos>,
+    // Hora de `ahora` y `marca_tiempo`. En el navegador la entrega la aplicacion.
+    pub reloj: Option<Box<dyn Reloj>>,
+    buildins_internal_fn: HashMap<String, InternalFn>,
+    buildins_constants: HashMap<String, Object>,
     stack_ctx: VecDeque<Context>,
+    // Llamadas a funciones del programa que no han retornado
+    depth: usize,
 }
 
 impl Evaluator {
-    pub fn new(painter: Option<egui::Painter>, width: f32, height: f32, top: f32) -> Self {
+    pub fn new(lienzo: Option<Box<dyn Lienzo>>, width: f32, height: f32) -> Self {
         Self {
-            painter,
-            canvas: CanvasSize { width, height, top },
+            lienzo,
+            canvas: CanvasSize { width, height },
+            screen: None,
+            framebuffer: None,
+            text: None,
+            tortuga: None,
+            estilo: Estilo::default(),
+            transform: Transformacion::IDENTITY,
+            saved_states: Vec::new(),
+            camara: Camara::default(),
+            camera_enabled: true,
+            frame_seconds: 0.0,
+            emitter_seed: 0,
+            entrada: None,
+            cuadros: None,
+            datos: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            reloj: Some(Box::new(RelojSistema)),
+            #[cfg(target_arch = "wasm32")]
+            reloj: None,
             buildins_internal_fn: HashMap::from([
+                ("longitud".to_owned(), InternalFn::Sync(longitud)),
+                ("tipo".to_owned(), InternalFn::Sync(tipo)),
+                ("documentacion".to_owned(), InternalFn::Sync(documentacion)),
+                ("dibujar_texto".to_owned(), InternalFn::Sync(dibujar_texto)),
+                ("dibujar_linea".to_owned(), InternalFn::Sync(dibujar_linea)),
                 (
-                    "longitud".to_owned(),
-                    Box::new(longitud) as Box<dyn InternalFnPointer>,
+                    "dibujar_rectangulo".to_owned(),
+                    InternalFn::Sync(dibujar_rectangulo),
                 ),
                 (
-                    "tipo".to_owned(),
-                    Box::new(tipo) as Box<dyn InternalFnPointer>,
+                    "dibujar_circulo".to_owned(),
+                    InternalFn::Sync(dibujar_circulo),
                 ),
+                ("lienzo_ancho".to_owned(), InternalFn::Sync(lienzo_ancho)),
+                ("lienzo_altura".to_owned(), InternalFn::Sync(lienzo_altura)),
+                ("aleatorio".to_owned(), InternalFn::Sync(aleatorio)),
+                ("cadena".to_owned(), InternalFn::Sync(cadena)),
+                ("seno".to_owned(), InternalFn::Sync(seno)),
+                ("coseno".to_owned(), InternalFn::Sync(coseno)),
+                ("tangente".to_owned(), InternalFn::Sync(tangente)),
+                ("atan2".to_owned(), InternalFn::Sync(atan2)),
+                ("raiz".to_owned(), InternalFn::Sync(raiz)),
+                ("potencia".to_owned(), InternalFn::Sync(potencia)),
+                ("abs".to_owned(), InternalFn::Sync(abs)),
+                ("piso".to_owned(), InternalFn::Sync(piso)),
+                ("techo".to_owned(), InternalFn::Sync(techo)),
+                ("redondear".to_owned(), InternalFn::Sync(redondear)),
+                ("min".to_owned(), InternalFn::Sync(min)),
+                ("max".to_owned(), InternalFn::Sync(max)),
+                ("limitar".to_owned(), InternalFn::Sync(limitar)),
+                ("interpolar".to_owned(), InternalFn::Sync(interpolar)),
+                ("modo_pantalla".to_owned(), InternalFn::Sync(modo_pantalla)),
+                ("color".to_owned(), InternalFn::Sync(color)),
+                ("color_borde".to_owned(), InternalFn::Sync(color_borde)),
+                ("poner_pixel".to_owned(), InternalFn::Sync(poner_pixel)),
+                ("obtener_pixel".to_owned(), InternalFn::Sync(obtener_pixel)),
                 (
-                    "dibujar_texto".to_owned(),
-                    Box::new(dibujar_texto) as Box<dyn InternalFnPointer>,
+                    "limpiar_pantalla".to_owned(),
+                    InternalFn::Sync(limpiar_pantalla),
                 ),
+                ("ubicar".to_owned(), InternalFn::Sync(ubicar)),
+                ("escribir".to_owned(), InternalFn::Sync(escribir)),
+                ("color_texto".to_owned(), InternalFn::Sync(color_texto)),
+                ("caracter_en".to_owned(), InternalFn::Sync(caracter_en)),
                 (
-                    "dibujar_linea".to_owned(),
-                    Box::new(dibujar_linea) as Box<dyn InternalFnPointer>,
+                    "mostrar_cursor".to_owned(),
+                    InternalFn::Sync(mostrar_cursor),
                 ),
+                ("avanzar".to_owned(), InternalFn::Sync(avanzar)),
+                ("retroceder".to_owned(), InternalFn::Sync(retroceder)),
+                ("girar".to_owned(), InternalFn::Sync(girar)),
+                ("subir_lapiz".to_owned(), InternalFn::Sync(subir_lapiz)),
+                ("bajar_lapiz".to_owned(), InternalFn::Sync(bajar_lapiz)),
+                ("color_lapiz".to_owned(), InternalFn::Sync(color_lapiz)),
+                ("grosor".to_owned(), InternalFn::Sync(grosor)),
                 (
-                    "dibujar_rectangulo".to_owned(),
-                    Box::new(dibujar_rectangulo) as Box<dyn InternalFnPointer>,
+                    "velocidad_tortuga".to_owned(),
+                    InternalFn::Sync(velocidad_tortuga),
                 ),
                 (
-                    "dibujar_circulo".to_owned(),
-                    Box::new(dibujar_circulo) as Box<dyn InternalFnPointer>,
+                    "mostrar_tortuga".to_owned(),
+                    InternalFn::Sync(mostrar_tortuga),
+                ),
+                (
+                    "reiniciar_tortuga".to_owned(),
+                    InternalFn::Sync(reiniciar_tortuga),
+                ),
+                (
+                    "dibujar_elipse".to_owned(),
+                    InternalFn::Sync(dibujar_elipse),
+                ),
+                ("dibujar_arco".to_owned(), InternalFn::Sync(dibujar_arco)),
+                (
+                    "dibujar_triangulo".to_owned(),
+                    InternalFn::Sync(dibujar_triangulo),
+                ),
+                (
+                    "dibujar_poligono".to_owned(),
+                    InternalFn::Sync(dibujar_poligono),
+                ),
+                ("relleno".to_owned(), InternalFn::Sync(relleno)),
+                ("sin_relleno".to_owned(), InternalFn::Sync(sin_relleno)),
+                ("trazo".to_owned(), InternalFn::Sync(trazo)),
+                ("sin_trazo".to_owned(), InternalFn::Sync(sin_trazo)),
+                ("redondeo".to_owned(), InternalFn::Sync(redondeo)),
+                (
+                    "estilo_inicial".to_owned(),
+                    InternalFn::Sync(estilo_inicial),
+                ),
+                ("trasladar".to_owned(), InternalFn::Sync(trasladar)),
+                ("rotar".to_owned(), InternalFn::Sync(rotar)),
+                ("escalar".to_owned(), InternalFn::Sync(escalar)),
+                (
+                    "guardar_estado".to_owned(),
+                    InternalFn::Sync(guardar_estado),
+                ),
+                (
+                    "restaurar_estado".to_owned(),
+                    InternalFn::Sync(restaurar_estado),
+                ),
+                ("rgb".to_owned(), InternalFn::Sync(rgb)),
+                ("rgba".to_owned(), InternalFn::Sync(rgba)),
+                ("hsv".to_owned(), InternalFn::Sync(hsv)),
+                ("mezclar_color".to_owned(), InternalFn::Sync(mezclar_color)),
+                ("preguntar".to_owned(), InternalFn::Async(preguntar)),
+                ("leer_linea".to_owned(), InternalFn::Async(leer_linea)),
+                ("esperar".to_owned(), InternalFn::Async(esperar)),
+                (
+                    "siguiente_cuadro".to_owned(),
+                    InternalFn::Async(siguiente_cuadro),
+                ),
+                ("guardar_dato".to_owned(), InternalFn::Sync(guardar_dato)),
+                ("cargar_dato".to_owned(), InternalFn::Sync(cargar_dato)),
+                ("a_json".to_owned(), InternalFn::Sync(a_json)),
+                ("desde_json".to_owned(), InternalFn::Sync(desde_json)),
+                ("ahora".to_owned(), InternalFn::Sync(ahora)),
+                ("marca_tiempo".to_owned(), InternalFn::Sync(marca_tiempo)),
+                (
+                    "formatear_fecha".to_owned(),
+                    InternalFn::Sync(formatear_fecha),
+                ),
+                ("vector".to_owned(), InternalFn::Sync(vector)),
+                ("distancia".to_owned(), InternalFn::Sync(distancia)),
+                ("angulo_entre".to_owned(), InternalFn::Sync(angulo_entre)),
+                (
+                    "punto_en_rectangulo".to_owned(),
+                    InternalFn::Sync(punto_en_rectangulo),
+                ),
+                (
+                    "colisionan_rectangulos".to_owned(),
+                    InternalFn::Sync(colisionan_rectangulos),
+                ),
+                (
+                    "colisionan_circulos".to_owned(),
+                    InternalFn::Sync(colisionan_circulos),
+                ),
+                ("crear_mapa".to_owned(), InternalFn::Sync(crear_mapa)),
+                ("poner_tile".to_owned(), InternalFn::Sync(poner_tile)),
+                ("obtener_tile".to_owned(), InternalFn::Sync(obtener_tile)),
+                ("dibujar_mapa".to_owned(), InternalFn::Sync(dibujar_mapa)),
+                ("tile_en".to_owned(), InternalFn::Sync(tile_en)),
+                (
+                    "colisiona_mapa".to_owned(),
+                    InternalFn::Sync(colisiona_mapa),
+                ),
+                (
+                    "camara_posicion".to_owned(),
+                    InternalFn::Sync(camara_posicion),
                 ),
+                ("camara_zoom".to_owned(), InternalFn::Sync(camara_zoom)),
+                ("camara_seguir".to_owned(), InternalFn::Sync(camara_seguir)),
+                ("usar_camara".to_owned(), InternalFn::Sync(usar_camara)),
                 (
-                    "lienzo_ancho".to_owned(),
-                    Box::new(lienzo_ancho) as Box<dyn InternalFnPointer>,
+                    "camara_a_mundo".to_owned(),
+                    InternalFn::Sync(camara_a_mundo),
                 ),
+                ("crear_emisor".to_owned(), InternalFn::Sync(crear_emisor)),
                 (
-                    "lienzo_altura".to_owned(),
-                    Box::new(lienzo_altura) as Box<dyn InternalFnPointer>,
+                    "cambiar_emisor".to_owned(),
+                    InternalFn::Sync(cambiar_emisor),
                 ),
+                ("mover_emisor".to_owned(), InternalFn::Sync(mover_emisor)),
+                ("emitir".to_owned(), InternalFn::Sync(emitir)),
                 (
-                    "aleatorio".to_owned(),
-                    Box::new(aleatorio) as Box<dyn InternalFnPointer>,
+                    "dibujar_emisor".to_owned(),
+                    InternalFn::Sync(dibujar_emisor),
                 ),
                 (
-                    "cadena".to_owned(),
-                    Box::new(cadena) as Box<dyn InternalFnPointer>,
+                    "cantidad_particulas".to_owned(),
+                    InternalFn::Sync(cantidad_particulas),
                 ),
             ]),
+            buildins_constants: [
+                (
+                    "PI".to_owned(),
+                    Object::Numeric(Numeric::Float(std::f64::consts::PI)),
+                ),
+                (
+                    "TAU".to_owned(),
+                    Object::Numeric(Numeric::Float(std::f64::consts::TAU)),
+                ),
+            ]
+            .into_iter()
+            .chain(
+                NAMED_COLORS
+                    .iter()
+                    .map(|(name, color)| (name.to_string(), Object::Color(*color))),
+            )
+            .collect(),
             stack_ctx: VecDeque::new(),
+            depth: 0,
+        }
+    }
+
+    // Retorna el bufer de pixeles, creandolo si el tamano del lienzo cambio.
+    // En el modo ventana empieza transparente para no tapar el fondo.
+    pub fn ensure_framebuffer(&mut self) -> Rasterizador {
+        let (width, height, color) = match &self.screen {
+            Some(screen) => (screen.width, screen.height, BACKGROUND),
+            None => (
+                self.canvas.width.max(1.0) as usize,
+                self.canvas.height.max(1.0) as usize,
+                Color::new(0, 0, 0, 0),
+            ),
+        };
+        match &self.framebuffer {
+            Some(framebuffer) if (framebuffer.width(), framebuffer.height()) == (width, height) => {
+                framebuffer.clone()
+            }
+            _ => {
+                let framebuffer = Rasterizador::with_color(width, height, color);
+                self.framebuffer = Some(framebuffer.clone());
+                framebuffer
+            }
+        }
+    }
+
+    // Activa una pantalla de resolucion fija. Lo que se dibuje desde ahora va
+    // a la pantalla y la capa de texto se ajusta a la nueva resolucion.
+    pub fn set_screen(&mut self, width: usize, height: usize) {
+        let border = self
+            .screen
+            .as_ref()
+            .map(|screen| screen.border)
+            .unwrap_or(C64[14]);
+        self.screen = Some(ScreenMode {
+            width,
+            height,
+            border,
+        });
+        self.canvas.width = width as f32;
+        self.canvas.height = height as f32;
+        self.text = None;
+        let framebuffer = self.ensure_framebuffer();
+        self.lienzo = Some(Box::new(framebuffer));
+    }
+
+    // Retorna la capa de texto, del tamano de la pantalla o, sin
+    // `modo_pantalla`, de 40x25 caracteres.
+    pub fn ensure_text(&mut self) -> &mut PantallaTexto {
+        let screen = self
+            .screen
+            .as_ref()
+            .map(|screen| (screen.width, screen.height));
+        self.text.get_or_insert_with(|| match screen {
+            Some((width, height)) => PantallaTexto::for_screen(width, height),
+            None => PantallaTexto::new(texto::COLS, texto::ROWS),
+        })
+    }
+
+    // Copia de la pantalla con la capa de texto encima, lista para mostrarse.
+    // `blink_on` indica si el cursor esta encendido en este cuadro.
+    pub fn screen_frame(&self, blink_on: bool) -> Option<Rasterizador> {
+        let (Some(_), Some(framebuffer)) = (&self.screen, &self.framebuffer) else {
+            return None;
+        };
+        let mut frame = framebuffer.snapshot();
+        if let Some(text) = &self.text {
+            let size = GLYPH_SIZE as f32;
+            text.render(&mut frame, (size, size), blink_on);
         }
+        if let Some(tortuga) = &self.tortuga {
+            tortuga.render_head(&mut frame);
+        }
+        Some(frame)
+    }
+
+    // Pixeles del modo ventana con el camino de la tortuga y la capa de texto
+    // encima. Sin ellos comparte el bufer en vez de copiarlo.
+    pub fn window_frame(&self, blink_on: bool) -> Option<Rasterizador> {
+        let layer = self
+            .tortuga
+            .as_ref()
+            .and_then(|tortuga| tortuga.layer.as_ref());
+        if self.text.is_none() && layer.is_none() {
+            return self.framebuffer.clone();
+        }
+        let mut frame = match &self.framebuffer {
+            Some(framebuffer) => framebuffer.snapshot(),
+            None => Rasterizador::with_color(
+                self.canvas.width.max(1.0) as usize,
+                self.canvas.height.max(1.0) as usize,
+                Color::new(0, 0, 0, 0),
+            ),
+        };
+        if let Some(layer) = layer {
+            frame.draw_over(layer);
+        }
+        if let Some(text) = &self.text {
+            let cell = (
+                self.canvas.width.max(1.0) / text.cols() as f32,
+                self.canvas.height.max(1.0) / text.rows() as f32,
+            );
+            text.render(&mut frame, cell, blink_on);
+        }
+        Some(frame)
+    }
+
+    // Se llama antes de cada cuadro con los segundos desde el anterior. Los
+    // cuadros empiezan sin transformacion y con la camara activa.
+    pub fn begin_frame(&mut self, seconds: f64) {
+        self.frame_seconds = seconds;
+        self.transform = Transformacion::IDENTITY;
+        self.saved_states.clear();
+        self.camera_enabled = true;
+    }
+
+    // Transformacion de las figuras: la camara y luego `transform`
+    pub fn view_transform(&self) -> Transformacion {
+        match self.camera_enabled {
+            true => self
+                .camara
+                .transform((self.canvas.width, self.canvas.height))
+                .then(self.transform),
+            false => self.transform,
+        }
+    }
+
+    // Dibuja en el lienzo aplicando la transformacion actual
+    pub fn draw(&mut self, f: impl FnOnce(&mut dyn Lienzo)) {
+        let transform = self.view_transform();
+        let Some(lienzo) = self.lienzo.as_mut() else {
+            return;
+        };
+        match transform.is_identity() {
+            true => f(lienzo.as_mut()),
+            false => f(&mut Transformado {
+                lienzo: lienzo.as_mut(),
+                transform,
+            }),
+        }
+    }
+
+    // Retorna la tortuga, creandola en el centro del lienzo
+    pub fn ensure_turtle(&mut self) -> &mut Tortuga {
+        let center = (self.canvas.width / 2.0, self.canvas.height / 2.0);
+        self.tortuga.get_or_insert_with(|| Tortuga::new(center))
+    }
+
+    // Avanza la animacion de la tortuga. Se llama al final de cada cuadro.
+    // La pantalla fija conserva los pixeles, asi que el camino se dibuja ahi y
+    // la tortuga en `screen_frame`. En el modo ventana el camino va a la capa
+    // de la tortuga y el triangulo al lienzo del cuadro.
+    pub fn draw_turtle(&mut self) {
+        let (Some(tortuga), Some(lienzo)) = (self.tortuga.as_mut(), self.lienzo.as_mut()) else {
+            return;
+        };
+        if self.screen.is_some() {
+            tortuga.advance(lienzo.as_mut());
+            return;
+        }
+        tortuga.advance_layer(
+            self.canvas.width.max(1.0) as usize,
+            self.canvas.height.max(1.0) as usize,
+        );
+        tortuga.render_head(lienzo.as_mut());
     }
 
+    // Busca la funcion `Bucle` en cualquier parte del programa y la remueve
     #[allow(clippy::result_unit_err)]
     pub fn extract_loop_fn(
         &mut self,
         statements: &mut BlockStatement,
     ) -> Result<BlockStatement, ()> {
-        for (i, stmt) in statements.iter().enumerate() {
-            if let Statement::Fn { name, body, .. } = stmt {
-                if name == "Bucle" {
-                    let body = body.clone();
-
-                    statements.remove(i);
-
-                    return Ok(body);
-                }
-                return Err(());
-            }
+        let position = statements
+            .iter()
+            .position(|stmt| matches!(stmt, Statement::Fn { name, .. } if name == "Bucle"));
+        match position {
+            Some(i) => match statements.remove(i) {
+                Statement::Fn { body, .. } => Ok(body),
+                _ => Err(()),
+            },
+            None => Err(()),
         }
+    }
 
-        Err(())
+    // Llama una funcion de ciclo de vida declarada por el programa, como `Inicio`,
+    // `AlTerminar` o `AlPresionarTecla`. Retorna `None` si el programa no la declaro.
+    pub async fn call_hook(
+        &mut self,
+        name: &str,
+        arguments: Vec<ResultObj>,
+        env: &RcEnvironment,
+    ) -> Option<ResultObj> {
+        // Sirve tanto `fn Inicio() {}` como `var Inicio = fn() {}`
+        let function = env.borrow().get(&name.to_owned())?;
+        let params = match &function {
+            ResultObj::Copy(Object::Fn(fn_obj)) => &fn_obj.params,
+            ResultObj::Copy(Object::FnExpr(fn_expr)) => &fn_expr.params,
+            _ => return None,
+        };
+        if params.len() != arguments.len() {
+            return Some(ResultObj::Copy(Object::Error(format!(
+                "Error de ejecución. La funcion `{}` debe recibir {} argumentos",
+                name,
+                arguments.len()
+            ))));
+        }
+        self.stack_ctx.clear();
+        self.stack_ctx.push_back(Context::Global);
+        self.depth = 0;
+        Some(self.call_fn_obj(&function, arguments, 0, 0).await)
     }
 
-    pub fn eval_program(&mut self, statements: &BlockStatement, env: &RcEnvironment) -> ResultObj {
+    pub async fn eval_program(
+        &mut self,
+        statements: &BlockStatement,
+        env: &RcEnvironment,
+    ) -> ResultObj {
         self.stack_ctx.clear();
         self.stack_ctx.push_back(Context::Global);
-        self.eval_block_statement(statements, env)
+        self.depth = 0;
+        self.eval_block_statement(statements, env).await
     }
 
-    fn eval_block_statement(&mut self, program: &BlockStatement, env: &RcEnvironment) -> ResultObj {
-        return match program.len() {
+    async fn eval_block_statement(
+        &mut self,
+        program: &BlockStatement,
+        env: &RcEnvironment,
+    ) -> ResultObj {
+        match program.len() {
             // Optimizar con referencias
             0 => ResultObj::Copy(Object::Void),
-            1 => self.eval_statement(program.get(0).unwrap(), env),
+            1 => self.eval_statement(program.get(0).unwrap(), env).await,
             _ => {
                 for stmt in program.iter() {
-                    let res_obj = self.eval_statement(stmt, env);
+                    let res_obj = self.eval_statement(stmt, env).await;
                     match res_obj {
                         ResultObj::Copy(Object::Return(returned_obj)) => return *returned_obj,
                         ResultObj::Copy(Object::Error(msg)) => {
@@ -139,17 +603,17 @@ impl Evaluator {
                 }
                 ResultObj::Copy(Object::Void)
             }
-        };
+        }
     }
 
-    fn eval_statement(&mut self, stmt: &Statement, env: &RcEnvironment) -> ResultObj {
+    async fn eval_statement(&mut self, stmt: &Statement, env: &RcEnvironment) -> ResultObj {
         match stmt {
-            Statement::Var { name, value } => self.eval_var(name, value, env),
+            Statement::Var { name, value } => self.eval_var(name, value, env).await,
             Statement::Return(expr, line, col) => {
                 while let Some(ctx) = self.stack_ctx.pop_back() {
                     if let Context::Fn = ctx {
                         return ResultObj::Copy(Object::Return(Box::new(
-                            self.eval_expression(expr, env),
+                            self.eval_expression(expr, env).await,
                         )));
                     }
                 }
@@ -185,19 +649,21 @@ impl Evaluator {
                     *col,
                 )))
             }
-            Statement::Expression(expr) => self.eval_expression(expr, env),
+            Statement::Expression(expr) => self.eval_expression(expr, env).await,
             Statement::Fn {
                 name,
                 params,
                 body,
                 line,
                 col,
+                doc,
             } => {
                 let obj = ResultObj::Copy(Object::Fn(Box::new(FnObj {
                     name: name.clone(),
                     params: params.clone(),
                     body: body.clone(),
                     env: env.clone(),
+                    doc: doc.clone(),
                 })));
                 match self.get_var_value(name, env, *line, *col) {
                     Some(obj) => obj,
@@ -207,21 +673,19 @@ impl Evaluator {
         }
     }
 
-    pub fn eval_expression(&mut self, expr: &Expression, env: &RcEnvironment) -> ResultObj {
+    pub async fn eval_expression(&mut self, expr: &Expression, env: &RcEnvironment) -> ResultObj {
+        if expr.sync {
+            return self.eval_sync_expression(expr, env);
+        }
+        self.eval_compound_expression(expr, env).await
+    }
+
+    // Las expresiones sin llamadas ni bloques no pueden detener el programa,
+    // asi que se evaluan directo, sin crear tareas
+    fn eval_sync_expression(&mut self, expr: &Expression, env: &RcEnvironment) -> ResultObj {
         match &expr.r#type {
             ExprType::NumericLiteral(numeric) => ResultObj::Copy(Object::Numeric(numeric.clone())),
             ExprType::BooleanLiteral(b) => ResultObj::Copy(Object::Boolean(*b)),
-            ExprType::Prefix { operator, right } => self.eval_prefix(operator, right, env),
-            ExprType::Infix {
-                left,
-                right,
-                operator,
-            } => self.eval_infix(operator, left, right, env),
-            ExprType::If {
-                condition,
-                consequence,
-                alternative,
-            } => self.eval_if(condition, consequence, alternative, env),
             ExprType::Identifier(ident) => self.eval_identifier(ident, env, expr.line, expr.col),
             ExprType::FnLiteral { params, body } => {
                 ResultObj::Copy(Object::FnExpr(Box::new(FnExprObj {
@@ -230,30 +694,166 @@ impl Evaluator {
                     env: env.clone(),
                 })))
             }
-            ExprType::Call {
-                function,
-                arguments,
-            } => self.eval_call(function, arguments, env),
-            ExprType::Assignment { left, right } => self.set_var(left, right, env),
             ExprType::StringLiteral(string) => {
                 ResultObj::Ref(new_rc_object(Object::String(string.to_string())))
             }
-            ExprType::ListLiteral { elements } => self.eval_list_literal(elements, env),
+            ExprType::NullLiteral => ResultObj::Copy(Object::Null),
+            ExprType::Prefix { operator, right } => {
+                let right = self.eval_sync_expression(right, env);
+                self.prefix_op(operator, right)
+            }
+            ExprType::Infix {
+                left,
+                right,
+                operator,
+            } => {
+                let left_obj = self.eval_sync_expression(left, env);
+                let right_obj = self.eval_sync_expression(right, env);
+                self.infix_op(operator, &left_obj, &right_obj, left.line, left.col)
+            }
+            ExprType::Assignment { left, right } => self.set_var_sync(left, right, env),
+            ExprType::ListLiteral { elements } => {
+                let mut objs = Vec::new();
+                for expr in elements {
+                    let obj = self.eval_sync_expression(expr, env);
+                    if self.is_error(&obj) {
+                        return obj;
+                    }
+                    objs.push(obj);
+                }
+                ResultObj::Ref(new_rc_object(Object::List(objs)))
+            }
             ExprType::Index { left, index } => {
-                self.eval_index_expression(left, index, None, env).clone()
+                let left_obj = self.eval_sync_expression(left, env);
+                let index_obj = self.eval_sync_expression(index, env);
+                self.index_obj(left_obj, index_obj, None, left, index)
+            }
+            ExprType::Slice {
+                left,
+                start,
+                end,
+                step,
+            } => {
+                let left_obj = self.eval_sync_expression(left, env);
+                let bounds = (
+                    self.eval_sync_slice_bound(start, env),
+                    self.eval_sync_slice_bound(end, env),
+                    self.eval_sync_slice_bound(step, env),
+                );
+                self.slice_obj(left_obj, bounds, None, left.line, left.col)
+            }
+            ExprType::DictionaryLiteral { pairs: expr_pairs } => {
+                let mut pairs = HashMap::new();
+                for (k, v) in expr_pairs {
+                    let obj_key = self.eval_sync_expression(k, env);
+                    if self.is_error(&obj_key) {
+                        return obj_key;
+                    }
+                    let obj_value = self.eval_sync_expression(v, env);
+                    if self.is_error(&obj_value) {
+                        return obj_value;
+                    }
+                    pairs.insert(obj_key, obj_value);
+                }
+                ResultObj::Ref(new_rc_object(Object::Dictionary(pairs)))
+            }
+            // `Expression::sync` descarta las expresiones que pueden llamar funciones
+            ExprType::If { .. }
+            | ExprType::While { .. }
+            | ExprType::Call { .. }
+            | ExprType::ForRange { .. } => {
+                unreachable!("{} no se puede evaluar sin tareas", expr.r#type)
+            }
+        }
+    }
+
+    fn eval_sync_slice_bound(
+        &mut self,
+        bound: &Option<Box<Expression>>,
+        env: &RcEnvironment,
+    ) -> SliceBound {
+        match bound {
+            Some(expr) => {
+                let obj = self.eval_sync_expression(expr, env);
+                self.slice_bound(obj, expr)
+            }
+            None => Ok(None),
+        }
+    }
+
+    // Las expresiones que pueden llamar funciones se evaluan en una tarea aparte,
+    // porque una funcion asincrona no puede contenerse a si misma. Cada tipo de
+    // expresion tiene su propia tarea para no reservar el espacio de la mayor.
+    fn eval_compound_expression<'a>(
+        &'a mut self,
+        expr: &'a Expression,
+        env: &'a RcEnvironment,
+    ) -> Tarea<'a, ResultObj> {
+        match &expr.r#type {
+            ExprType::Prefix { operator, right } => {
+                Box::pin(self.eval_prefix(operator, right, env))
+            }
+            // Las funciones miembro son mas pesadas, tienen su propia tarea
+            ExprType::Infix {
+                left,
+                right,
+                operator: TokenType::Dot,
+            } => Box::pin(self.eval_member_access(left, right, env)),
+            ExprType::Infix {
+                left,
+                right,
+                operator,
+            } => Box::pin(self.eval_infix(operator, left, right, env)),
+            ExprType::If {
+                condition,
+                consequence,
+                alternative,
+            } => Box::pin(self.eval_if(condition, consequence, alternative, env)),
+            ExprType::Call {
+                function,
+                arguments,
+            } => Box::pin(self.eval_call(function, arguments, env)),
+            ExprType::Assignment { left, right } => Box::pin(self.set_var(left, right, env)),
+            ExprType::ListLiteral { elements } => Box::pin(self.eval_list_literal(elements, env)),
+            ExprType::Index { left, index } => {
+                Box::pin(self.eval_index_expression(left, index, None, env))
+            }
+            ExprType::Slice {
+                left,
+                start,
+                end,
+                step,
+            } => Box::pin(self.eval_slice_expression(left, start, end, step, None, env)),
+            ExprType::DictionaryLiteral { pairs } => {
+                Box::pin(self.eval_dictionary_expression(pairs, env))
+            }
+            ExprType::While { condition, body } => {
+                Box::pin(self.eval_while_loop(condition, body, env))
             }
-            ExprType::NullLiteral => ResultObj::Copy(Object::Null),
-            ExprType::DictionaryLiteral { pairs } => self.eval_dictionary_expression(pairs, env),
-            ExprType::While { condition, body } => self.eval_while_loop(condition, body, env),
             ExprType::ForRange {
                 ident,
                 arguments,
                 body,
-            } => self.eval_for_range(ident.clone(), arguments, body, expr.line, expr.col, env),
+            } => Box::pin(self.eval_for_range(
+                ident.clone(),
+                arguments,
+                body,
+                expr.line,
+                expr.col,
+                env,
+            )),
+            ExprType::NumericLiteral(_)
+            | ExprType::BooleanLiteral(_)
+            | ExprType::Identifier(_)
+            | ExprType::FnLiteral { .. }
+            | ExprType::StringLiteral(_)
+            | ExprType::NullLiteral => {
+                Box::pin(std::future::ready(self.eval_sync_expression(expr, env)))
+            }
         }
     }
 
-    fn eval_if(
+    async fn eval_if(
         &mut self,
         condition: &Expression,
         consequence: &BlockStatement,
@@ -261,7 +861,7 @@ impl Evaluator {
         env: &RcEnvironment,
     ) -> ResultObj {
         self.stack_ctx.push_back(Context::If);
-        let condition = self.eval_expression(condition, env);
+        let condition = self.eval_expression(condition, env).await;
         let condition_res = {
             match condition {
                 ResultObj::Copy(Object::Numeric(numeric)) => numeric != Numeric::Int(0),
@@ -274,22 +874,26 @@ impl Evaluator {
         };
         let scope_env = Rc::new(RefCell::new(Environment::new(Some(env.clone()))));
         if condition_res {
-            return self.eval_block_statement(consequence, &scope_env);
+            return self.eval_block_statement(consequence, &scope_env).await;
         }
-        let obj = self.eval_block_statement(alternative, &scope_env);
+        let obj = self.eval_block_statement(alternative, &scope_env).await;
         if let Some(Context::If) = self.stack_ctx.back() {
             self.stack_ctx.pop_back();
         }
         obj
     }
 
-    fn eval_prefix(
+    async fn eval_prefix(
         &mut self,
         operator: &TokenType,
         right: &Expression,
         env: &RcEnvironment,
     ) -> ResultObj {
-        let right = self.eval_expression(right, env);
+        let right = self.eval_expression(right, env).await;
+        self.prefix_op(operator, right)
+    }
+
+    fn prefix_op(&self, operator: &TokenType, right: ResultObj) -> ResultObj {
         match operator {
             TokenType::Plus => right,
             TokenType::Minus => match right {
@@ -299,6 +903,7 @@ impl Evaluator {
                 ResultObj::Copy(Object::Boolean(b)) => {
                     ResultObj::Copy(Object::Numeric(Numeric::Int(-(b as i64))))
                 }
+                ResultObj::Copy(Object::Vector(vector)) => ResultObj::Copy(Object::Vector(-vector)),
                 _ => ResultObj::Copy(Object::Null),
             },
             TokenType::Bang => match right {
@@ -335,6 +940,20 @@ impl Evaluator {
                     &Numeric::Int(*b as i64),
                     operator,
                 ),
+            (ResultObj::Copy(Object::Color(a)), ResultObj::Copy(Object::Color(b))) => {
+                self.eval_infix_color_operation(a, b, operator)
+            }
+            (ResultObj::Copy(Object::Vector(a)), ResultObj::Copy(Object::Vector(b))) => {
+                self.eval_infix_vector_operation(a, b, operator)
+            }
+            (ResultObj::Copy(Object::Vector(a)), ResultObj::Copy(Object::Numeric(b))) => {
+                self.eval_infix_vector_numeric_operation(a, b, operator)
+            }
+            (ResultObj::Copy(Object::Numeric(a)), ResultObj::Copy(Object::Vector(b)))
+                if *operator == TokenType::Asterisk =>
+            {
+                self.eval_infix_vector_numeric_operation(b, a, operator)
+            }
             (ResultObj::Ref(a), ResultObj::Ref(b)) => match (&*a.borrow(), &*b.borrow()) {
                 (Object::String(a), Object::String(b)) => {
                     self.eval_infix_string_operation(a, b, operator)
@@ -407,7 +1026,7 @@ impl Evaluator {
         }
     }
 
-    fn eval_member_ops(
+    async fn eval_member_ops(
         &mut self,
         right: &Expression,
         left: &ResultObj,
@@ -424,16 +1043,20 @@ impl Evaluator {
                     if self.is_error(left) {
                         return left.clone();
                     }
+                    let arguments = match self.eval_arguments(arguments, env).await {
+                        Ok(arguments) => arguments,
+                        Err(err) => return err,
+                    };
                     // TODO: Eliminar el clone
                     match_member_fn(
                         self,
                         identifier,
-                        arguments.clone(),
+                        arguments,
                         left.clone(),
                         left_line,
                         left_col,
-                        env,
                     )
+                    .await
                 }
                 _ => ResultObj::Copy(Object::Error(create_msg_err(
                     "El operador de acceso de miembro espera un identicador o llamada".into(),
@@ -452,7 +1075,7 @@ impl Evaluator {
         }
     }
 
-    fn eval_infix(
+    async fn eval_infix(
         &mut self,
         operator: &TokenType,
         left: &Expression,
@@ -461,16 +1084,32 @@ impl Evaluator {
     ) -> ResultObj {
         let line = left.line;
         let col = left.col;
-        let left = self.eval_expression(left, env);
-
-        if *operator == TokenType::Dot {
-            return self.eval_member_ops(right, &left, line, col, env);
-        }
+        let left = self.eval_expression(left, env).await;
+        let right = self.eval_expression(right, env).await;
+        self.infix_op(operator, &left, &right, line, col)
+    }
 
-        let right = self.eval_expression(right, env);
+    async fn eval_member_access(
+        &mut self,
+        left: &Expression,
+        right: &Expression,
+        env: &RcEnvironment,
+    ) -> ResultObj {
+        let left_obj = self.eval_expression(left, env).await;
+        self.eval_member_ops(right, &left_obj, left.line, left.col, env)
+            .await
+    }
 
+    fn infix_op(
+        &mut self,
+        operator: &TokenType,
+        left: &ResultObj,
+        right: &ResultObj,
+        line: usize,
+        col: usize,
+    ) -> ResultObj {
         // match err
-        match self.match_infix_ops(&left, &right, operator) {
+        match self.match_infix_ops(left, right, operator) {
             ResultObj::Copy(Object::Error(err)) => {
                 ResultObj::Copy(Object::Error(create_msg_err(err, line, col)))
             }
@@ -486,6 +1125,7 @@ impl Evaluator {
     }
 
     fn eval_infix_numeric_operation(&self, a: &Numeric, b: &Numeric, op: &TokenType) -> ResultObj {
+        use std::cmp::Ordering::{Equal, Greater, Less};
         // TODO: Hay que ver si hay overflow!!! y mandar un mensaje de error adecuado
         let a = a.clone();
         let b = b.clone();
@@ -495,12 +1135,18 @@ impl Evaluator {
             TokenType::Slash => ResultObj::Copy(Object::Numeric(a / b)),
             TokenType::Asterisk => ResultObj::Copy(Object::Numeric(a * b)),
             TokenType::Percent => ResultObj::Copy(Object::Numeric(a % b)),
-            TokenType::Eq => ResultObj::Copy(Object::Boolean(a == b)),
-            TokenType::NotEq => ResultObj::Copy(Object::Boolean(a != b)),
-            TokenType::Lt => ResultObj::Copy(Object::Boolean(a < b)),
-            TokenType::Gt => ResultObj::Copy(Object::Boolean(a > b)),
-            TokenType::LtEq => ResultObj::Copy(Object::Boolean(a <= b)),
-            TokenType::GtEq => ResultObj::Copy(Object::Boolean(a >= b)),
+            TokenType::Eq => ResultObj::Copy(Object::Boolean(a.value_cmp(&b) == Some(Equal))),
+            TokenType::NotEq => ResultObj::Copy(Object::Boolean(a.value_cmp(&b) != Some(Equal))),
+            TokenType::Lt => ResultObj::Copy(Object::Boolean(a.value_cmp(&b) == Some(Less))),
+            TokenType::Gt => ResultObj::Copy(Object::Boolean(a.value_cmp(&b) == Some(Greater))),
+            TokenType::LtEq => ResultObj::Copy(Object::Boolean(matches!(
+                a.value_cmp(&b),
+                Some(Less | Equal)
+            ))),
+            TokenType::GtEq => ResultObj::Copy(Object::Boolean(matches!(
+                a.value_cmp(&b),
+                Some(Greater | Equal)
+            ))),
             _ => ResultObj::Copy(Object::Null),
         }
     }
@@ -585,6 +1231,58 @@ impl Evaluator {
         }
     }
 
+    fn eval_infix_color_operation(&self, a: &Color, b: &Color, operator: &TokenType) -> ResultObj {
+        match operator {
+            TokenType::Eq => ResultObj::Copy(Object::Boolean(a == b)),
+            TokenType::NotEq => ResultObj::Copy(Object::Boolean(a != b)),
+            _ => ResultObj::Copy(Object::Error(
+                "Los colores solo pueden hacer operaciones logicas de igualdad, usa `mezclar_color` para combinarlos".to_owned(),
+            )),
+        }
+    }
+
+    fn eval_infix_vector_operation(
+        &self,
+        a: &Vector,
+        b: &Vector,
+        operator: &TokenType,
+    ) -> ResultObj {
+        match operator {
+            TokenType::Plus => ResultObj::Copy(Object::Vector(*a + *b)),
+            TokenType::Minus => ResultObj::Copy(Object::Vector(*a - *b)),
+            TokenType::Eq => ResultObj::Copy(Object::Boolean(a == b)),
+            TokenType::NotEq => ResultObj::Copy(Object::Boolean(a != b)),
+            _ => ResultObj::Copy(Object::Error(format!(
+                "No se soporta operaciones vector {} vector",
+                operator
+            ))),
+        }
+    }
+
+    // vector * 2, vector / 2 y 2 * vector
+    fn eval_infix_vector_numeric_operation(
+        &self,
+        a: &Vector,
+        b: &Numeric,
+        operator: &TokenType,
+    ) -> ResultObj {
+        let b = match b {
+            Numeric::Int(int) => *int as f64,
+            Numeric::Float(float) => *float,
+        };
+        match operator {
+            TokenType::Asterisk => ResultObj::Copy(Object::Vector(*a * b)),
+            TokenType::Slash if b == 0.0 => ResultObj::Copy(Object::Error(
+                "No se puede dividir un vector entre 0".to_owned(),
+            )),
+            TokenType::Slash => ResultObj::Copy(Object::Vector(*a * (1.0 / b))),
+            _ => ResultObj::Copy(Object::Error(format!(
+                "No se soporta operaciones vector {} numerico",
+                operator
+            ))),
+        }
+    }
+
     fn eval_infix_null_object_operation(&self, operator: &TokenType) -> ResultObj {
         match operator {
             TokenType::Eq => ResultObj::Copy(Object::Boolean(false)),
@@ -595,53 +1293,133 @@ impl Evaluator {
         }
     }
 
-    fn eval_var(&mut self, name: &String, value: &Expression, env: &RcEnvironment) -> ResultObj {
+    async fn eval_var(
+        &mut self,
+        name: &String,
+        value: &Expression,
+        env: &RcEnvironment,
+    ) -> ResultObj {
         if let Some(obj) = self.get_var_value(name, env, value.line, value.col) {
             return obj;
         }
 
-        self.insert_var(name, value, env)
+        self.insert_var(name, value, env).await
     }
 
-    fn set_var(&mut self, left: &Expression, right: &Expression, env: &RcEnvironment) -> ResultObj {
+    async fn set_var(
+        &mut self,
+        left: &Expression,
+        right: &Expression,
+        env: &RcEnvironment,
+    ) -> ResultObj {
         return match &left.r#type {
             ExprType::Identifier(ident) => {
                 if !self.exist_var(ident, env) {
-                    return ResultObj::Copy(Object::Error(create_msg_err(
-                        format!("El no existe referencias hacia `{}`", ident),
-                        left.line,
-                        left.col,
-                    )));
+                    return self.missing_var_err(ident, left);
                 }
 
-                let obj = self.eval_expression(right, env);
-                let mut env_ref = RefCell::borrow_mut(env);
-
-                env_ref.update(ident, obj.clone());
+                let obj = self.eval_expression(right, env).await;
+                RefCell::borrow_mut(env).update(ident, obj.clone());
                 obj
             }
             ExprType::Index { left, index } => {
-                let right_obj = self.eval_expression(right, env);
+                let right_obj = self.eval_expression(right, env).await;
                 if self.is_error(&right_obj) {
                     return right_obj;
                 }
 
                 self.eval_index_expression(left, index, Some(&right_obj), env)
+                    .await
             }
-            _ => ResultObj::Copy(Object::Error(create_msg_err(
-                format!(
-                    "No se puede realizar operaciones de asignacion a {}",
-                    left.r#type
-                ),
-                left.line,
-                left.col,
-            ))),
-        };
-    }
-
-    fn get_var_value(
-        &self,
-        name: &String,
+            ExprType::Slice {
+                left,
+                start,
+                end,
+                step,
+            } => {
+                let right_obj = self.eval_expression(right, env).await;
+                if self.is_error(&right_obj) {
+                    return right_obj;
+                }
+
+                self.eval_slice_expression(left, start, end, step, Some(&right_obj), env)
+                    .await
+            }
+            _ => self.assignment_err(left),
+        };
+    }
+
+    fn set_var_sync(
+        &mut self,
+        left: &Expression,
+        right: &Expression,
+        env: &RcEnvironment,
+    ) -> ResultObj {
+        match &left.r#type {
+            ExprType::Identifier(ident) => {
+                if !self.exist_var(ident, env) {
+                    return self.missing_var_err(ident, left);
+                }
+
+                let obj = self.eval_sync_expression(right, env);
+                RefCell::borrow_mut(env).update(ident, obj.clone());
+                obj
+            }
+            ExprType::Index { left, index } => {
+                let right_obj = self.eval_sync_expression(right, env);
+                if self.is_error(&right_obj) {
+                    return right_obj;
+                }
+
+                let left_obj = self.eval_sync_expression(left, env);
+                let index_obj = self.eval_sync_expression(index, env);
+                self.index_obj(left_obj, index_obj, Some(&right_obj), left, index)
+            }
+            ExprType::Slice {
+                left,
+                start,
+                end,
+                step,
+            } => {
+                let right_obj = self.eval_sync_expression(right, env);
+                if self.is_error(&right_obj) {
+                    return right_obj;
+                }
+
+                let left_obj = self.eval_sync_expression(left, env);
+                let bounds = (
+                    self.eval_sync_slice_bound(start, env),
+                    self.eval_sync_slice_bound(end, env),
+                    self.eval_sync_slice_bound(step, env),
+                );
+                self.slice_obj(left_obj, bounds, Some(&right_obj), left.line, left.col)
+            }
+            _ => self.assignment_err(left),
+        }
+    }
+
+    fn missing_var_err(&self, ident: &String, expr: &Expression) -> ResultObj {
+        ResultObj::Copy(Object::Error(create_msg_err(
+            format!("El no existe referencias hacia `{}`", ident),
+            expr.line,
+            expr.col,
+        )))
+    }
+
+    fn assignment_err(&self, expr: &Expression) -> ResultObj {
+        ResultObj::Copy(Object::Error(create_msg_err(
+            format!(
+                "No se puede realizar operaciones de asignacion a {}",
+                expr.r#type
+            ),
+            expr.line,
+            expr.col,
+        )))
+    }
+
+    fn get_var_value(
+        &self,
+        name: &String,
         env: &RcEnvironment,
         line: usize,
         col: usize,
@@ -661,10 +1439,15 @@ impl Evaluator {
         env_ref.exist(name)
     }
 
-    fn insert_var(&mut self, name: &str, value: &Expression, env: &RcEnvironment) -> ResultObj {
+    async fn insert_var(
+        &mut self,
+        name: &str,
+        value: &Expression,
+        env: &RcEnvironment,
+    ) -> ResultObj {
         let line = value.line;
         let col = value.col;
-        let mut value_obj = self.eval_expression(value, env);
+        let mut value_obj = self.eval_expression(value, env).await;
         if let ResultObj::Copy(ref obj) = value_obj {
             match obj {
                 Object::Error(_) => return value_obj,
@@ -701,9 +1484,12 @@ impl Evaluator {
                 if let Some(func) = self.buildins_internal_fn.get(ident) {
                     return ResultObj::Copy(Object::BuildinFn(Box::new(BuildinFnObj {
                         name: ident.clone(),
-                        func: func.clone_box(),
+                        func: *func,
                     })));
                 }
+                if let Some(obj) = self.buildins_constants.get(ident) {
+                    return ResultObj::Copy(obj.clone());
+                }
                 ResultObj::Copy(Object::Error(create_msg_err(
                     format!("El identicador `{}` no existe.", ident),
                     line,
@@ -713,7 +1499,7 @@ impl Evaluator {
         }
     }
 
-    pub fn eval_call(
+    pub async fn eval_call(
         &mut self,
         function: &Expression,
         arguments: &Vec<Expression>,
@@ -721,48 +1507,117 @@ impl Evaluator {
     ) -> ResultObj {
         let line = function.line;
         let col = function.col;
-        let obj = self.eval_expression(function, env);
-        match obj {
-            ResultObj::Copy(Object::FnExpr(fn_expr)) => self.eval_fn_expr(
-                arguments,
-                &fn_expr.params,
-                &fn_expr.body,
-                &fn_expr.env,
-                line,
-                col,
-            ),
-            ResultObj::Copy(Object::Fn(fn_expr)) => self.eval_fn_expr(
-                arguments,
-                &fn_expr.params,
-                &fn_expr.body,
-                &fn_expr.env,
-                line,
-                col,
-            ),
+        let obj = self.eval_expression(function, env).await;
+        // Un solo llamado a `eval_fn_expr` para las dos clases de funciones, cada
+        // copia agranda la pila que usa cada nivel de recursion
+        let (params, body, fn_env) = match &obj {
+            ResultObj::Copy(Object::FnExpr(fn_expr)) => {
+                (&fn_expr.params, &fn_expr.body, &fn_expr.env)
+            }
+            ResultObj::Copy(Object::Fn(fn_expr)) => (&fn_expr.params, &fn_expr.body, &fn_expr.env),
             ResultObj::Copy(Object::BuildinFn(f)) => {
-                let func = f.func;
-                func(self, arguments.clone(), env)
+                return match self.eval_arguments(arguments, env).await {
+                    Ok(arguments) => self.call_buildin_fn(f.func, arguments).await,
+                    Err(err) => err,
+                };
             }
             // TODO(Retornar errores previo)
-            _ => ResultObj::Copy(Object::Error(create_msg_err(
-                "La operacion de llamada solo puede ser aplicada a objetos que sean funciones"
-                    .to_owned(),
-                line,
-                col,
-            ))),
+            _ => {
+                return ResultObj::Copy(Object::Error(create_msg_err(
+                    "La operacion de llamada solo puede ser aplicada a objetos que sean funciones"
+                        .to_owned(),
+                    line,
+                    col,
+                )))
+            }
+        };
+        match self.eval_arguments(arguments, env).await {
+            Ok(arguments) => {
+                self.eval_fn_expr(arguments, params, body, fn_env, line, col)
+                    .await
+            }
+            Err(err) => err,
         }
     }
 
-    pub fn eval_fn_expr(
+    // Los argumentos se evaluan en el contexto de quien llama, no en el de la funcion
+    async fn eval_arguments(
         &mut self,
         arguments: &FnParams,
+        env: &RcEnvironment,
+    ) -> Result<Vec<ResultObj>, ResultObj> {
+        let mut objs = Vec::new();
+        for arg in arguments {
+            match self.eval_expression(arg, env).await {
+                ResultObj::Copy(Object::Error(msg)) => {
+                    return Err(ResultObj::Copy(Object::Error(msg)))
+                }
+                ResultObj::Copy(Object::Return(returned_obj)) => objs.push(*returned_obj),
+                ResultObj::Copy(Object::Void) => {
+                    return Err(ResultObj::Copy(Object::Error(create_msg_err(
+                        "No se puede pasar el tipo de dato vacio como argumento".to_owned(),
+                        arg.line,
+                        arg.col,
+                    ))))
+                }
+                obj => objs.push(obj),
+            }
+        }
+        Ok(objs)
+    }
+
+    // Llama a un objeto funcion con argumentos ya evaluados. Lo usan las funciones
+    // miembro que reciben otra funcion, como `mapear` o `filtrar`.
+    pub async fn call_fn_obj(
+        &mut self,
+        function: &ResultObj,
+        arguments: Vec<ResultObj>,
+        line: usize,
+        col: usize,
+    ) -> ResultObj {
+        let (params, body, env) = match function {
+            ResultObj::Copy(Object::FnExpr(fn_expr)) => {
+                (&fn_expr.params, &fn_expr.body, &fn_expr.env)
+            }
+            ResultObj::Copy(Object::Fn(fn_expr)) => (&fn_expr.params, &fn_expr.body, &fn_expr.env),
+            ResultObj::Copy(Object::BuildinFn(f)) => {
+                return self.call_buildin_fn(f.func, arguments).await;
+            }
+            obj => {
+                return ResultObj::Copy(Object::Error(create_msg_err(
+                    format!("Se espera una funcion, no {}", obj.get_type()),
+                    line,
+                    col,
+                )))
+            }
+        };
+        self.depth += CALLBACK_DEPTH;
+        let res_obj = self
+            .eval_fn_expr(arguments, params, body, env, line, col)
+            .await;
+        self.depth -= CALLBACK_DEPTH;
+        match res_obj {
+            ResultObj::Copy(Object::Return(returned_obj)) => *returned_obj,
+            obj => obj,
+        }
+    }
+
+    async fn call_buildin_fn(&mut self, func: InternalFn, arguments: FnArgs) -> ResultObj {
+        match func {
+            InternalFn::Sync(func) => func(self, arguments),
+            InternalFn::Async(func) => func(self, arguments).await,
+        }
+    }
+
+    pub async fn eval_fn_expr(
+        &mut self,
+        arguments: Vec<ResultObj>,
         params: &FnParams,
         body: &BlockStatement,
         env: &RcEnvironment,
         line: usize,
         col: usize,
     ) -> ResultObj {
-        self.stack_ctx.push_back(Context::Fn);
         let scope_env = Rc::new(RefCell::new(Environment::new(Some(env.clone()))));
         if arguments.len() != params.len() {
             return ResultObj::Copy(Object::Error(create_msg_err(
@@ -775,22 +1630,38 @@ impl Evaluator {
                 col,
             )));
         }
-        for (arg, param) in arguments.iter().zip(params) {
+        // Cada llamada anidada ocupa la pila del sistema, se da un error antes
+        // de agotarla
+        if self.depth >= MAX_DEPTH {
+            return ResultObj::Copy(Object::Error(create_msg_err(
+                "recursion demasiado profunda".to_owned(),
+                line,
+                col,
+            )));
+        }
+        self.depth += 1;
+        self.stack_ctx.push_back(Context::Fn);
+        for (arg, param) in arguments.into_iter().zip(params) {
             if let ExprType::Identifier(param_name) = &param.r#type {
-                self.insert_var(param_name, arg, &scope_env);
+                self.insert_obj(param_name, arg, &scope_env);
             }
         }
-        let res_obj = self.eval_block_statement(body, &scope_env);
+        let res_obj = self.eval_block_statement(body, &scope_env).await;
         if let Some(Context::Fn) = self.stack_ctx.back() {
             self.stack_ctx.pop_back();
         }
+        self.depth -= 1;
         res_obj
     }
 
-    fn eval_list_literal(&mut self, elements: &Vec<Expression>, env: &RcEnvironment) -> ResultObj {
+    async fn eval_list_literal(
+        &mut self,
+        elements: &Vec<Expression>,
+        env: &RcEnvironment,
+    ) -> ResultObj {
         let mut objs = Vec::new();
         for expr in elements {
-            let obj = self.eval_expression(expr, env);
+            let obj = self.eval_expression(expr, env).await;
             if self.is_error(&obj) {
                 return obj;
             }
@@ -799,19 +1670,30 @@ impl Evaluator {
         ResultObj::Ref(new_rc_object(Object::List(objs)))
     }
 
-    fn eval_index_expression(
+    async fn eval_index_expression(
         &mut self,
         left: &Expression,
         index: &Expression,
         new_value: Option<&ResultObj>,
         env: &RcEnvironment,
+    ) -> ResultObj {
+        let left_obj = self.eval_expression(left, env).await;
+        let index_obj = self.eval_expression(index, env).await;
+        self.index_obj(left_obj, index_obj, new_value, left, index)
+    }
+
+    fn index_obj(
+        &mut self,
+        left_obj: ResultObj,
+        index_obj: ResultObj,
+        new_value: Option<&ResultObj>,
+        left: &Expression,
+        index: &Expression,
     ) -> ResultObj {
         let line = left.line;
         let col = left.col;
         let index_line = index.line;
         let index_col = index.col;
-        let left_obj = self.eval_expression(left, env);
-        let index_obj = self.eval_expression(index, env);
         if self.is_error(&index_obj) {
             return index_obj;
         }
@@ -828,15 +1710,37 @@ impl Evaluator {
             ResultObj::Ref(obj) => match *obj.borrow_mut() {
                 Object::List(ref mut objs) => {
                     if let ResultObj::Copy(Object::Numeric(Numeric::Int(index))) = index_obj {
+                        // Los indices negativos cuentan desde el final: lista[-1]
+                        let index = match normalize_index(index, objs.len()) {
+                            Some(index) => index,
+                            None => return ResultObj::Copy(Object::Null),
+                        };
                         if let Some(new_value) = new_value {
-                            if (index as usize) < objs.len() {
-                                objs[index as usize] = new_value.clone();
-                                return new_value.clone();
-                            }
-                            return ResultObj::Copy(Object::Null);
+                            objs[index] = new_value.clone();
+                            return new_value.clone();
                         }
-                        return match objs.get(index as usize) {
-                            Some(obj) => obj.clone(),
+                        return objs[index].clone();
+                    }
+                    ResultObj::Copy(Object::Error(create_msg_err(
+                        "El operador de indexar solo opera con enteros".to_owned(),
+                        index_line,
+                        index_col,
+                    )))
+                }
+                Object::String(ref string) => {
+                    if new_value.is_some() {
+                        return ResultObj::Copy(Object::Error(create_msg_err(
+                            "No se puede asignar a un caracter de una cadena".to_owned(),
+                            line,
+                            col,
+                        )));
+                    }
+                    if let ResultObj::Copy(Object::Numeric(Numeric::Int(index))) = index_obj {
+                        let chars = string.chars().collect::<Vec<char>>();
+                        return match normalize_index(index, chars.len()) {
+                            Some(index) => {
+                                ResultObj::Ref(new_rc_object(Object::String(chars[index].into())))
+                            }
                             None => ResultObj::Copy(Object::Null),
                         };
                     }
@@ -855,7 +1759,7 @@ impl Evaluator {
                     ))),
                 },
                 _ => ResultObj::Copy(Object::Error(create_msg_err(
-                    "Solo se puede usar el operador de indexar en listas y dicccionarios"
+                    "Solo se puede usar el operador de indexar en listas, cadenas y dicccionarios"
                         .to_owned(),
                     line,
                     col,
@@ -864,18 +1768,180 @@ impl Evaluator {
         }
     }
 
-    fn eval_dictionary_expression(
+    async fn eval_slice_bound(
+        &mut self,
+        bound: &Option<Box<Expression>>,
+        env: &RcEnvironment,
+    ) -> SliceBound {
+        match bound {
+            Some(expr) => {
+                let obj = self.eval_expression(expr, env).await;
+                self.slice_bound(obj, expr)
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn slice_bound(&self, obj: ResultObj, expr: &Expression) -> SliceBound {
+        match obj {
+            ResultObj::Copy(Object::Numeric(Numeric::Int(int))) => Ok(Some(int)),
+            ResultObj::Copy(Object::Null) => Ok(None),
+            ResultObj::Copy(Object::Error(msg)) => Err(ResultObj::Copy(Object::Error(msg))),
+            _ => Err(ResultObj::Copy(Object::Error(create_msg_err(
+                "Los limites de la rebanada deben ser enteros".to_owned(),
+                expr.line,
+                expr.col,
+            )))),
+        }
+    }
+
+    async fn eval_slice_expression(
+        &mut self,
+        left: &Expression,
+        start: &Option<Box<Expression>>,
+        end: &Option<Box<Expression>>,
+        step: &Option<Box<Expression>>,
+        new_value: Option<&ResultObj>,
+        env: &RcEnvironment,
+    ) -> ResultObj {
+        let left_obj = self.eval_expression(left, env).await;
+        let bounds = (
+            self.eval_slice_bound(start, env).await,
+            self.eval_slice_bound(end, env).await,
+            self.eval_slice_bound(step, env).await,
+        );
+        self.slice_obj(left_obj, bounds, new_value, left.line, left.col)
+    }
+
+    fn slice_obj(
+        &mut self,
+        left_obj: ResultObj,
+        bounds: (SliceBound, SliceBound, SliceBound),
+        new_value: Option<&ResultObj>,
+        line: usize,
+        col: usize,
+    ) -> ResultObj {
+        let (start, end, step) = match bounds {
+            (Ok(start), Ok(end), Ok(step)) => (start, end, step.unwrap_or(1)),
+            (Err(err), _, _) | (_, Err(err), _) | (_, _, Err(err)) => return err,
+        };
+        if step == 0 {
+            return ResultObj::Copy(Object::Error(create_msg_err(
+                "Los pasos de la rebanada deben ser distintos de 0".to_owned(),
+                line,
+                col,
+            )));
+        }
+
+        // Se copian los nuevos elementos antes de tomar prestada la lista,
+        // por si se asigna la lista a si misma: lista[0:1] = lista;
+        let new_objs = match new_value {
+            Some(ResultObj::Ref(new_obj)) => match *new_obj.borrow() {
+                Object::List(ref objs) => Some(objs.clone()),
+                ref obj => {
+                    return ResultObj::Copy(Object::Error(create_msg_err(
+                        format!(
+                            "Solo se puede asignar una lista a una rebanada, no {}",
+                            obj.get_type()
+                        ),
+                        line,
+                        col,
+                    )))
+                }
+            },
+            Some(ResultObj::Copy(obj)) => {
+                return ResultObj::Copy(Object::Error(create_msg_err(
+                    format!(
+                        "Solo se puede asignar una lista a una rebanada, no {}",
+                        obj.get_type()
+                    ),
+                    line,
+                    col,
+                )))
+            }
+            None => None,
+        };
+
+        match left_obj {
+            ResultObj::Copy(Object::Error(msg)) => ResultObj::Copy(Object::Error(msg)),
+            ResultObj::Ref(obj) => match *obj.borrow_mut() {
+                Object::List(ref mut objs) => {
+                    let indices = slice_indices(objs.len(), start, end, step);
+                    let new_objs = match new_objs {
+                        Some(new_objs) => new_objs,
+                        None => {
+                            return ResultObj::Ref(new_rc_object(Object::List(
+                                indices.into_iter().map(|i| objs[i].clone()).collect(),
+                            )))
+                        }
+                    };
+                    if step == 1 {
+                        // Con pasos de 1 la rebanada puede cambiar de tamano
+                        let from = start.map_or(0, |i| normalize_bound(i, objs.len()));
+                        let to = end
+                            .map_or(objs.len(), |i| normalize_bound(i, objs.len()))
+                            .max(from);
+                        objs.splice(from..to, new_objs);
+                    } else {
+                        if indices.len() != new_objs.len() {
+                            return ResultObj::Copy(Object::Error(create_msg_err(
+                                format!(
+                                    "La rebanada tiene {} elementos y se intento asignar {}",
+                                    indices.len(),
+                                    new_objs.len()
+                                ),
+                                line,
+                                col,
+                            )));
+                        }
+                        for (i, new_obj) in indices.into_iter().zip(new_objs) {
+                            objs[i] = new_obj;
+                        }
+                    }
+                    new_value.cloned().unwrap_or(ResultObj::Copy(Object::Void))
+                }
+                Object::String(ref string) => {
+                    if new_objs.is_some() {
+                        return ResultObj::Copy(Object::Error(create_msg_err(
+                            "No se puede asignar a una rebanada de una cadena".to_owned(),
+                            line,
+                            col,
+                        )));
+                    }
+                    let chars = string.chars().collect::<Vec<char>>();
+                    ResultObj::Ref(new_rc_object(Object::String(
+                        slice_indices(chars.len(), start, end, step)
+                            .into_iter()
+                            .map(|i| chars[i])
+                            .collect(),
+                    )))
+                }
+                ref obj => ResultObj::Copy(Object::Error(create_msg_err(
+                    format!("No se puede rebanar el tipo de dato {}", obj.get_type()),
+                    line,
+                    col,
+                ))),
+            },
+            ResultObj::Copy(obj) => ResultObj::Copy(Object::Error(create_msg_err(
+                format!("No se puede rebanar el tipo de dato {}", obj.get_type()),
+                line,
+                col,
+            ))),
+        }
+    }
+
+    async fn eval_dictionary_expression(
         &mut self,
         expr_pairs: &HashMap<Expression, Expression>,
         env: &RcEnvironment,
     ) -> ResultObj {
         let mut pairs = HashMap::new();
         for (k, v) in expr_pairs {
-            let obj_key = self.eval_expression(k, env);
+            let obj_key = self.eval_expression(k, env).await;
             if self.is_error(&obj_key) {
                 return obj_key;
             }
-            let obj_value = self.eval_expression(v, env);
+            let obj_value = self.eval_expression(v, env).await;
             if self.is_error(&obj_value) {
                 return obj_value;
             }
@@ -891,15 +1957,14 @@ impl Evaluator {
         false
     }
 
-    fn eval_while_loop(
+    async fn eval_while_loop(
         &mut self,
         condition: &Expression,
         body: &BlockStatement,
         env: &RcEnvironment,
     ) -> ResultObj {
         self.stack_ctx.push_back(Context::Loop);
-        let condition_ref = Rc::new(RefCell::new(condition));
-        let condition_obj = self.eval_expression(&condition_ref.borrow(), env);
+        let condition_obj = self.eval_expression(condition, env).await;
         let mut condition_res = {
             match condition_obj {
                 ResultObj::Copy(Object::Numeric(numeric)) => numeric != Numeric::Int(0),
@@ -913,7 +1978,7 @@ impl Evaluator {
         let body = Box::new(body);
         while condition_res {
             let scope_env = Rc::new(RefCell::new(Environment::new(Some(env.clone()))));
-            let res_obj = self.eval_block_statement(&body, &scope_env);
+            let res_obj = self.eval_block_statement(&body, &scope_env).await;
             if self.is_error(&res_obj) {
                 return res_obj;
             }
@@ -924,7 +1989,7 @@ impl Evaluator {
                     _ => {}
                 }
             }
-            let condition_obj = self.eval_expression(&condition_ref.borrow(), env);
+            let condition_obj = self.eval_expression(condition, env).await;
             condition_res = {
                 match condition_obj {
                     ResultObj::Copy(Object::Numeric(numeric)) => numeric != Numeric::Int(0),
@@ -942,14 +2007,14 @@ impl Evaluator {
         ResultObj::Copy(Object::Void)
     }
 
-    fn extract_numeric_int(
+    async fn extract_numeric_int(
         &mut self,
         expr: &Expression,
         env: &RcEnvironment,
     ) -> Result<i64, ResultObj> {
         let line = expr.line;
         let col = expr.col;
-        match self.eval_expression(expr, env) {
+        match self.eval_expression(expr, env).await {
             ResultObj::Copy(obj) => match obj {
                 Object::Numeric(Numeric::Int(int)) => Ok(int),
                 Object::Error(err) => Err(ResultObj::Copy(Object::Error(err))),
@@ -976,7 +2041,7 @@ impl Evaluator {
         }
     }
 
-    fn eval_for_range(
+    async fn eval_for_range(
         &mut self,
         ident: String,
         arguments: &Vec<Expression>,
@@ -1002,7 +2067,7 @@ impl Evaluator {
                 let expr = arguments.get(0).unwrap();
                 line = expr.line;
                 col = expr.col;
-                iter_obj = self.eval_expression(expr, env);
+                iter_obj = self.eval_expression(expr, env).await;
                 if self.is_error(&iter_obj) {
                     return iter_obj;
                 }
@@ -1011,13 +2076,13 @@ impl Evaluator {
                 let expr = arguments.get(0).unwrap();
                 line = expr.line;
                 col = expr.col;
-                iter_obj = self.eval_expression(expr, env);
+                iter_obj = self.eval_expression(expr, env).await;
                 if self.is_error(&iter_obj) {
                     return iter_obj;
                 }
 
                 let expr = arguments.get(1).unwrap();
-                match self.extract_numeric_int(expr, env) {
+                match self.extract_numeric_int(expr, env).await {
                     Ok(int) => end = int,
                     Err(res_obj) => return res_obj,
                 }
@@ -1026,19 +2091,19 @@ impl Evaluator {
                 let expr = arguments.get(0).unwrap();
                 line = expr.line;
                 col = expr.col;
-                iter_obj = self.eval_expression(expr, env);
+                iter_obj = self.eval_expression(expr, env).await;
                 if self.is_error(&iter_obj) {
                     return iter_obj;
                 }
 
                 let expr = arguments.get(1).unwrap();
-                match self.extract_numeric_int(expr, env) {
+                match self.extract_numeric_int(expr, env).await {
                     Ok(int) => end = int,
                     Err(res_obj) => return res_obj,
                 }
 
                 let expr = arguments.get(2).unwrap();
-                match self.extract_numeric_int(expr, env) {
+                match self.extract_numeric_int(expr, env).await {
                     Ok(int) => steps = int,
                     Err(res_obj) => return res_obj,
                 }
@@ -1082,19 +2147,21 @@ impl Evaluator {
                     };
                     for i in range.step_by(steps as usize) {
                         let scope_env = Rc::new(RefCell::new(Environment::new(Some(env.clone()))));
-                        let elem_obj = self.eval_var(
-                            &ident,
-                            &Expression::new(
-                                ExprType::NumericLiteral(Numeric::Int(i as i64)),
-                                line,
-                                col + 1,
-                            ),
-                            &scope_env,
-                        );
+                        let elem_obj = self
+                            .eval_var(
+                                &ident,
+                                &Expression::new(
+                                    ExprType::NumericLiteral(Numeric::Int(i as i64)),
+                                    line,
+                                    col + 1,
+                                ),
+                                &scope_env,
+                            )
+                            .await;
                         if self.is_error(&elem_obj) {
                             return elem_obj;
                         }
-                        let res_obj = self.eval_block_statement(body, &scope_env);
+                        let res_obj = self.eval_block_statement(body, &scope_env).await;
                         if self.is_error(&res_obj) {
                             return res_obj;
                         }
@@ -1138,6 +2205,54 @@ impl Evaluator {
     }
 }
 
+// Los indices negativos cuentan desde el final, None si queda fuera del rango
+fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { index + len as i64 } else { index };
+    if index < 0 || index >= len as i64 {
+        return None;
+    }
+    Some(index as usize)
+}
+
+// Igual que en python, los limites de una rebanada que se salen del rango se recortan
+fn normalize_bound(index: i64, len: usize) -> usize {
+    let len = len as i64;
+    let index = if index < 0 { index + len } else { index };
+    index.clamp(0, len) as usize
+}
+
+fn slice_indices(len: usize, start: Option<i64>, end: Option<i64>, step: i64) -> Vec<usize> {
+    let mut indices = Vec::new();
+    if step > 0 {
+        let mut i = start.map_or(0, |i| normalize_bound(i, len)) as i64;
+        let end = end.map_or(len, |i| normalize_bound(i, len)) as i64;
+        while i < end {
+            indices.push(i as usize);
+            // Un paso enorme como `l[1::9223372036854775807]` se sale de i64
+            match i.checked_add(step) {
+                Some(next) => i = next,
+                None => break,
+            }
+        }
+    } else {
+        // Con pasos negativos se recorre desde el final, -1 marca "antes del inicio"
+        let clamp = |i: i64| {
+            let i = if i < 0 { i + len as i64 } else { i };
+            i.clamp(-1, len as i64 - 1)
+        };
+        let mut i = start.map_or(len as i64 - 1, clamp);
+        let end = end.map_or(-1, clamp);
+        while i > end {
+            indices.push(i as usize);
+            match i.checked_add(step) {
+                Some(next) => i = next,
+                None => break,
+            }
+        }
+    }
+    indices
+}
+
 pub fn create_msg_err(msg: String, line: usize, col: usize) -> String {
     if let Some(stripped) = msg.strip_prefix('^') {
         return stripped.to_string();
@@ -1147,3 +2262,348 @@ pub fn create_msg_err(msg: String, line: usize, col: usize) -> String {
         msg, line, col
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use std::task::Poll;
+
+    use super::*;
+    use crate::{
+        eval::{environment::Environment, tarea},
+        lexer::Lexer,
+        parser::Parser,
+    };
+
+    fn parse(code: &str) -> BlockStatement {
+        let mut parser = Parser::new(Lexer::new(code.chars().collect()));
+        let program = parser.parse();
+        assert!(parser.error.is_none());
+        program
+    }
+
+    fn show(res: ResultObj) -> String {
+        match res {
+            ResultObj::Copy(obj) => obj.to_string(),
+            ResultObj::Ref(obj) => obj.borrow().to_string(),
+        }
+    }
+
+    fn run(code: &str) -> String {
+        let program = parse(code);
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        show(tarea::block_on(eval.eval_program(&program, &env)))
+    }
+
+    // Entrada que detiene el programa hasta que la prueba deja una respuesta
+    struct EntradaPendiente(Rc<RefCell<Option<String>>>);
+
+    impl Entrada for EntradaPendiente {
+        fn leer_linea<'a>(&'a mut self, _pregunta: &'a str) -> Tarea<'a, Option<String>> {
+            let respuesta = self.0.clone();
+            Box::pin(std::future::poll_fn(move |_| {
+                match respuesta.borrow_mut().take() {
+                    Some(line) => Poll::Ready(Some(line)),
+                    None => Poll::Pending,
+                }
+            }))
+        }
+    }
+
+    #[test]
+    fn slice_indices_steps() {
+        assert_eq!(slice_indices(5, None, None, 2), vec![0, 2, 4]);
+        assert_eq!(slice_indices(5, None, None, -2), vec![4, 2, 0]);
+        assert_eq!(slice_indices(5, Some(-2), None, 1), vec![3, 4]);
+    }
+
+    #[test]
+    fn slice_indices_huge_step() {
+        assert_eq!(slice_indices(5, Some(1), None, i64::MAX), vec![1]);
+        assert_eq!(slice_indices(5, Some(3), None, i64::MIN), vec![3]);
+    }
+
+    // Cuadros que detienen el programa hasta que la prueba deja los segundos
+    struct CuadrosPendientes(Rc<RefCell<Option<f64>>>);
+
+    impl Cuadros for CuadrosPendientes {
+        fn siguiente_cuadro<'a>(
+            &'a mut self,
+            _eval: &'a mut Evaluator,
+            _keep: bool,
+        ) -> Tarea<'a, Option<f64>> {
+            let seconds = self.0.clone();
+            Box::pin(std::future::poll_fn(move |_| {
+                match seconds.borrow_mut().take() {
+                    Some(seconds) => Poll::Ready(Some(seconds)),
+                    None => Poll::Pending,
+                }
+            }))
+        }
+    }
+
+    #[test]
+    fn sort_with_user_fn() {
+        assert_eq!(
+            run("[4, 2, 8, 6, 1].ordenar(fn(a, b) { a > b; });"),
+            "[8, 6, 4, 2, 1]"
+        );
+        assert_eq!(run("[3, 1, 2].ordenar(fn(a, b) { a - b; });"), "[1, 2, 3]");
+    }
+
+    #[test]
+    fn program_pauses_in_preguntar() {
+        let program = parse("\"Hola \" + preguntar(\"Nombre\");");
+        let respuesta = Rc::new(RefCell::new(None));
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        eval.entrada = Some(Box::new(EntradaPendiente(respuesta.clone())));
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        let mut task: Tarea<ResultObj> = Box::pin(eval.eval_program(&program, &env));
+
+        assert!(tarea::poll(task.as_mut()).is_pending());
+        *respuesta.borrow_mut() = Some("Ana".to_string());
+        match tarea::poll(task.as_mut()) {
+            Poll::Ready(res) => assert_eq!(show(res), "Hola Ana"),
+            Poll::Pending => panic!("El programa sigue detenido"),
+        }
+    }
+
+    // Corre `code` y contesta su `preguntar` despues de revisar que el programa
+    // se detuvo esperando la respuesta
+    fn run_with_answer(code: &str, answer: &str) -> (String, RcEnvironment) {
+        let program = parse(code);
+        let respuesta = Rc::new(RefCell::new(None));
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        eval.entrada = Some(Box::new(EntradaPendiente(respuesta.clone())));
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        let mut task: Tarea<ResultObj> = Box::pin(eval.eval_program(&program, &env));
+        assert!(tarea::poll(task.as_mut()).is_pending(), "{}", code);
+        *respuesta.borrow_mut() = Some(answer.to_string());
+        let res = match tarea::poll(task.as_mut()) {
+            Poll::Ready(res) => show(res),
+            Poll::Pending => panic!("`{}` sigue detenido", code),
+        };
+        drop(task);
+        (res, env)
+    }
+
+    fn var_value(env: &RcEnvironment, name: &str) -> String {
+        show(env.borrow().get(&name.to_owned()).unwrap())
+    }
+
+    #[test]
+    fn documentacion_reads_fn_docs() {
+        let code = "###\nSuma dos numeros\n###\nfn sumar(a, b) { a + b; }\n\
+            fn restar(a, b) { a - b; }\n\
+            #### Seccion ####\nfn mitad(a) { a / 2; }\n\
+            var suma = documentacion(sumar);\n\
+            var resta = documentacion(restar);\n\
+            var banner = documentacion(mitad);\n\
+            var literal = documentacion(fn(x) { x; });\n\
+            var interna = documentacion(tipo);";
+        let program = parse(code);
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        tarea::block_on(eval.eval_program(&program, &env));
+        assert_eq!(var_value(&env, "suma"), "Suma dos numeros");
+        for name in ["resta", "banner", "literal", "interna"] {
+            assert_eq!(var_value(&env, name), "nulo", "{}", name);
+        }
+        assert!(run("documentacion(1);").contains("Se espera una funcion"));
+    }
+
+    fn run_hook(
+        code: &str,
+        hook: &str,
+        arguments: Vec<ResultObj>,
+    ) -> (Option<String>, RcEnvironment) {
+        let program = parse(code);
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        tarea::block_on(eval.eval_program(&program, &env));
+        let res = tarea::block_on(eval.call_hook(hook, arguments, &env)).map(show);
+        (res, env)
+    }
+
+    #[test]
+    fn hook_declared_with_fn() {
+        let code = "var clics = 0;\nfn AlHacerClic(x, y) { clics = clics + x + y; }";
+        let arguments = vec![
+            ResultObj::Copy(Object::Numeric(Numeric::Int(2))),
+            ResultObj::Copy(Object::Numeric(Numeric::Int(3))),
+        ];
+        let (res, env) = run_hook(code, "AlHacerClic", arguments);
+        assert!(res.is_some());
+        assert_eq!(var_value(&env, "clics"), "5");
+        let (res, _) = run_hook(code, "AlHacerClic", Vec::new());
+        assert!(res.unwrap().contains("debe recibir 0 argumentos"));
+        assert!(run_hook(code, "Inicio", Vec::new()).0.is_none());
+    }
+
+    #[test]
+    fn hook_assigned_with_fn_literal() {
+        let code = "var listo = falso;\nvar Inicio = fn() { listo = verdad; };";
+        let (res, env) = run_hook(code, "Inicio", Vec::new());
+        assert!(res.is_some());
+        assert_eq!(var_value(&env, "listo"), "verdad");
+        let code = "var Inicio = fn(a) { a; };";
+        let (res, _) = run_hook(code, "Inicio", Vec::new());
+        assert!(res.unwrap().contains("debe recibir 0 argumentos"));
+        // Una variable que no es funcion no cuenta como gancho
+        assert!(run_hook("var Inicio = 1;", "Inicio", Vec::new())
+            .0
+            .is_none());
+    }
+
+    #[test]
+    fn preguntar_pauses_in_every_expression() {
+        let cases = [
+            ("\"<\" + preguntar(\"x\") + \">\";", "<Ana>"),
+            ("-longitud(preguntar(\"x\"));", "-3"),
+            ("![preguntar(\"x\")];", "nulo"),
+            ("longitud([1, preguntar(\"x\"), 3]);", "3"),
+            ("{\"a\": preguntar(\"x\")}[\"a\"];", "Ana"),
+            ("[10, 20, 30][longitud(preguntar(\"x\")) - 1];", "30"),
+            ("\"abcdef\"[longitud(preguntar(\"x\")):];", "def"),
+            ("\"abcdef\"[::longitud(preguntar(\"x\"))];", "ad"),
+            ("[[preguntar(\"x\")]][0][0];", "Ana"),
+            ("longitud([1].mapear(fn(x) { preguntar(\"x\"); }));", "1"),
+            ("si preguntar(\"x\") == \"Ana\" { 1; } sino { 2; }", "1"),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(run_with_answer(code, "Ana").0, expected, "{}", code);
+        }
+
+        let (_, env) = run_with_answer("var a = 0; a = preguntar(\"x\");", "Ana");
+        assert_eq!(var_value(&env, "a"), "Ana");
+        let (_, env) = run_with_answer(
+            "var l = [0, 0, 0, 0]; l[longitud(preguntar(\"x\")) - 1] = 5;",
+            "Ana",
+        );
+        assert_eq!(var_value(&env, "l"), "[0, 0, 5, 0]");
+        let (_, env) = run_with_answer(
+            "var l = [0, 0, 0, 0]; l[longitud(preguntar(\"x\")):] = [7];",
+            "Ana",
+        );
+        assert_eq!(var_value(&env, "l"), "[0, 0, 0, 7]");
+    }
+
+    // Las pruebas corren sin optimizar y cada llamada ocupa varias veces mas
+    // pila que en la aplicacion
+    fn run_with_big_stack(code: String, name: &'static str) -> String {
+        std::thread::Builder::new()
+            .stack_size(64 * 1024 * 1024)
+            .spawn(move || {
+                let program = parse(&code);
+                let mut eval = Evaluator::new(None, 100.0, 100.0);
+                let env = Rc::new(RefCell::new(Environment::new(None)));
+                match tarea::block_on(eval.eval_program(&program, &env)) {
+                    ResultObj::Copy(Object::Error(msg)) => msg,
+                    _ => var_value(&env, name),
+                }
+            })
+            .unwrap()
+            .join()
+            .unwrap()
+    }
+
+    #[test]
+    fn deep_recursion_is_an_error() {
+        let code = |n: usize| {
+            format!(
+                "fn f(n) {{ si n == 0 {{ retornar 0; }} retornar f(n - 1) + 1; }} var r = f({});",
+                n
+            )
+        };
+        let res = run_with_big_stack(code(100000), "r");
+        assert!(res.contains("recursion demasiado profunda"), "{}", res);
+
+        // El limite no toca la recursion que cabe
+        assert_eq!(
+            run_with_big_stack(code(MAX_DEPTH - 1), "r"),
+            (MAX_DEPTH - 1).to_string()
+        );
+    }
+
+    #[test]
+    fn deep_recursion_through_callbacks_is_an_error() {
+        let res = run_with_big_stack(
+            "fn f(n) { si n == 0 { 0; } sino { longitud([n].mapear(fn(x) { f(x - 1); })); } }
+            var r = f(100000);"
+                .to_owned(),
+            "r",
+        );
+        assert!(res.contains("recursion demasiado profunda"), "{}", res);
+    }
+
+    #[test]
+    fn esperar_pauses_until_enough_frames() {
+        let program = parse("esperar(0.5);");
+        let seconds = Rc::new(RefCell::new(None));
+        let mut eval = Evaluator::new(None, 100.0, 100.0);
+        eval.cuadros = Some(Box::new(CuadrosPendientes(seconds.clone())));
+        let env = Rc::new(RefCell::new(Environment::new(None)));
+        let mut task: Tarea<ResultObj> = Box::pin(eval.eval_program(&program, &env));
+
+        assert!(tarea::poll(task.as_mut()).is_pending());
+        *seconds.borrow_mut() = Some(0.3);
+        assert!(tarea::poll(task.as_mut()).is_pending());
+        *seconds.borrow_mut() = Some(0.3);
+        assert!(tarea::poll(task.as_mut()).is_ready());
+    }
+
+    #[test]
+    fn negative_slices() {
+        let cases = [
+            ("[0, 1, 2, 3, 4, 5][-2:];", "[4, 5]"),
+            ("[0, 1, 2, 3, 4, 5][:-2];", "[0, 1, 2, 3]"),
+            ("[0, 1, 2, 3, 4, 5][-4:-1];", "[2, 3, 4]"),
+            ("[0, 1, 2, 3, 4, 5][::-1];", "[5, 4, 3, 2, 1, 0]"),
+            ("[0, 1, 2, 3, 4, 5][-1:-4:-1];", "[5, 4, 3]"),
+            ("[0, 1, 2, 3, 4, 5][::-2];", "[5, 3, 1]"),
+            ("\"hola\"[-3:];", "ola"),
+            ("\"hola\"[::-1];", "aloh"),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(run(code), expected, "{}", code);
+        }
+    }
+
+    #[test]
+    fn out_of_range_slices() {
+        let cases = [
+            ("[0, 1, 2, 3, 4, 5][-100:2];", "[0, 1]"),
+            ("[0, 1, 2, 3, 4, 5][2:100];", "[2, 3, 4, 5]"),
+            ("[0, 1, 2, 3, 4, 5][100:];", "[]"),
+            ("[0, 1, 2, 3, 4, 5][:-100];", "[]"),
+            ("[0, 1, 2, 3, 4, 5][4:2];", "[]"),
+            ("[0, 1, 2, 3, 4, 5][-100:100:2];", "[0, 2, 4]"),
+            ("[0, 1, 2, 3, 4, 5][100:-100:-2];", "[5, 3, 1]"),
+            ("[][-1:1];", "[]"),
+            ("\"hola\"[10:];", ""),
+            ("\"hola\"[-10:-2];", "ho"),
+        ];
+        for (code, expected) in cases {
+            assert_eq!(run(code), expected, "{}", code);
+        }
+        assert!(run("[0, 1][::0];").contains("distintos de 0"));
+    }
+
+    #[test]
+    fn slice_assignment_bounds() {
+        let cases = [
+            ("l[-2:] = [9];", "[0, 1, 9]"),
+            ("l[:-3] = [];", "[1, 2, 3]"),
+            ("l[10:] = [9];", "[0, 1, 2, 3, 9]"),
+            ("l[-10:1] = [9, 9];", "[9, 9, 1, 2, 3]"),
+        ];
+        for (assignment, expected) in cases {
+            let code = format!("var l = [0, 1, 2, 3];\n{}", assignment);
+            let program = parse(&code);
+            let mut eval = Evaluator::new(None, 100.0, 100.0);
+            let env = Rc::new(RefCell::new(Environment::new(None)));
+            tarea::block_on(eval.eval_program(&program, &env));
+            assert_eq!(var_value(&env, "l"), expected, "{}", assignment);
+        }
+    }
+}
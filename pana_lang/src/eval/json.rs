@@ -0,0 +1,570 @@
+use std::fmt::Write;
+
+use crate::types::Numeric;
+
+use super::objects::{new_rc_object, Object, ResultObj};
+
+// Niveles maximos de listas y diccionarios. Tambien detiene a una lista que
+// se contiene a si misma.
+const MAX_DEPTH: usize = 128;
+
+// Valor JSON. Los diccionarios conservan el orden de sus llaves.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Json {
+    Null,
+    Boolean(bool),
+    Numeric(Numeric),
+    String(String),
+    List(Vec<Json>),
+    Dictionary(Vec<(String, Json)>),
+}
+
+// Error de `Json::parse`, con la posicion en el texto contando desde 1
+#[derive(Clone, Debug, PartialEq)]
+pub struct JsonError {
+    pub msg: String,
+    pub line: usize,
+    pub col: usize,
+}
+
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}. Linea {}, columna {}.",
+            self.msg, self.line, self.col
+        )
+    }
+}
+
+impl Json {
+    // Convierte un valor de Pana. Solo se admiten nulo, logicos, numeros,
+    // cadenas, listas y diccionarios con llaves de tipo cadena.
+    pub fn from_obj(obj: &ResultObj) -> Result<Self, String> {
+        Self::from_obj_depth(obj, 0)
+    }
+
+    fn from_obj_depth(obj: &ResultObj, depth: usize) -> Result<Self, String> {
+        if depth > MAX_DEPTH {
+            return Err(format!(
+                "El valor tiene mas de {} niveles o se contiene a si mismo",
+                MAX_DEPTH
+            ));
+        }
+        match obj {
+            ResultObj::Copy(obj) => match obj {
+                Object::Null => Ok(Json::Null),
+                Object::Boolean(b) => Ok(Json::Boolean(*b)),
+                Object::Numeric(Numeric::Float(float)) if !float.is_finite() => {
+                    Err(format!("El numero {} no se puede convertir a JSON", float))
+                }
+                Object::Numeric(num) => Ok(Json::Numeric(num.clone())),
+                Object::String(string) => Ok(Json::String(string.clone())),
+                Object::Return(obj) => Self::from_obj_depth(obj, depth),
+                obj => Err(format!(
+                    "Un valor de tipo {} no se puede convertir a JSON",
+                    obj.get_type()
+                )),
+            },
+            ResultObj::Ref(obj) => match &*obj.borrow() {
+                Object::String(string) => Ok(Json::String(string.clone())),
+                Object::List(items) => items
+                    .iter()
+                    .map(|item| Self::from_obj_depth(item, depth + 1))
+                    .collect::<Result<_, _>>()
+                    .map(Json::List),
+                Object::Dictionary(pairs) => {
+                    let mut entries = Vec::with_capacity(pairs.len());
+                    for (key, value) in pairs {
+                        let key = match key {
+                            ResultObj::Copy(Object::String(key)) => key.clone(),
+                            ResultObj::Ref(key) if matches!(&*key.borrow(), Object::String(_)) => {
+                                key.borrow().to_string()
+                            }
+                            key => {
+                                return Err(format!(
+                                    "Las llaves de un diccionario deben ser cadenas para convertirlo a JSON, no {}",
+                                    key.get_type()
+                                ))
+                            }
+                        };
+                        entries.push((key, Self::from_obj_depth(value, depth + 1)?));
+                    }
+                    // Los diccionarios de Pana no tienen orden, asi el texto no cambia
+                    // entre ejecuciones
+                    entries.sort_by(|a, b| a.0.cmp(&b.0));
+                    Ok(Json::Dictionary(entries))
+                }
+                obj => Err(format!(
+                    "Un valor de tipo {} no se puede convertir a JSON",
+                    obj.get_type()
+                )),
+            },
+        }
+    }
+
+    pub fn to_obj(&self) -> ResultObj {
+        match self {
+            Json::Null => ResultObj::Copy(Object::Null),
+            Json::Boolean(b) => ResultObj::Copy(Object::Boolean(*b)),
+            Json::Numeric(num) => ResultObj::Copy(Object::Numeric(num.clone())),
+            Json::String(string) => ResultObj::Ref(new_rc_object(Object::String(string.clone()))),
+            Json::List(items) => ResultObj::Ref(new_rc_object(Object::List(
+                items.iter().map(Json::to_obj).collect(),
+            ))),
+            Json::Dictionary(entries) => ResultObj::Ref(new_rc_object(Object::Dictionary(
+                entries
+                    .iter()
+                    .map(|(key, value)| {
+                        (
+                            ResultObj::Ref(new_rc_object(Object::String(key.clone()))),
+                            value.to_obj(),
+                        )
+                    })
+                    .collect(),
+            ))),
+        }
+    }
+
+    // Texto JSON, con `pretty` cada elemento va en su linea con sangria de 2 espacios
+    pub fn write(&self, pretty: bool) -> String {
+        let mut out = String::new();
+        self.write_to(&mut out, pretty, 0);
+        out
+    }
+
+    fn write_to(&self, out: &mut String, pretty: bool, indent: usize) {
+        match self {
+            Json::Null => out.push_str("null"),
+            Json::Boolean(b) => out.push_str(if *b { "true" } else { "false" }),
+            Json::Numeric(Numeric::Int(int)) => write!(out, "{}", int).unwrap(),
+            // Los flotantes enteros llevan `.0` para seguir siendo flotantes al leerlos
+            Json::Numeric(Numeric::Float(float)) if float.fract() == 0.0 => {
+                write!(out, "{:.1}", float).unwrap()
+            }
+            Json::Numeric(Numeric::Float(float)) => write!(out, "{}", float).unwrap(),
+            Json::String(string) => write_string(out, string),
+            Json::List(items) => {
+                write_seq(out, pretty, indent, '[', ']', items, |out, item, indent| {
+                    item.write_to(out, pretty, indent)
+                })
+            }
+            Json::Dictionary(entries) => write_seq(
+                out,
+                pretty,
+                indent,
+                '{',
+                '}',
+                entries,
+                |out, (key, value), indent| {
+                    write_string(out, key);
+                    out.push_str(if pretty { ": " } else { ":" });
+                    value.write_to(out, pretty, indent);
+                },
+            ),
+        }
+    }
+
+    pub fn parse(text: &str) -> Result<Self, JsonError> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            pos: 0,
+            line: 1,
+            col: 1,
+        };
+        parser.skip_whitespace();
+        let value = parser.value(0)?;
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(value),
+            Some(c) => Err(parser.error(format!("Sobra el simbolo `{}` despues del valor", c))),
+        }
+    }
+}
+
+fn write_string(out: &mut String, string: &str) {
+    out.push('"');
+    for c in string.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+fn write_seq<T>(
+    out: &mut String,
+    pretty: bool,
+    indent: usize,
+    open: char,
+    close: char,
+    items: &[T],
+    mut write_item: impl FnMut(&mut String, &T, usize),
+) {
+    out.push(open);
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        if pretty {
+            out.push('\n');
+            out.push_str(&"  ".repeat(indent + 1));
+        }
+        write_item(out, item, indent + 1);
+    }
+    if pretty && !items.is_empty() {
+        out.push('\n');
+        out.push_str(&"  ".repeat(indent));
+    }
+    out.push(close);
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    col: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        if c == '\n' {
+            self.line += 1;
+            self.col = 1;
+        } else {
+            self.col += 1;
+        }
+        Some(c)
+    }
+
+    fn error(&self, msg: String) -> JsonError {
+        JsonError {
+            msg,
+            line: self.line,
+            col: self.col,
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> JsonError {
+        match self.peek() {
+            Some(c) => self.error(format!("Se esperaba {}, no `{}`", expected, c)),
+            None => self.error(format!("Se esperaba {}, pero el texto termino", expected)),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.next();
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), JsonError> {
+        match self.peek() == Some(c) {
+            true => {
+                self.next();
+                Ok(())
+            }
+            false => Err(self.unexpected(&format!("`{}`", c))),
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Json, JsonError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error(format!("El texto tiene mas de {} niveles", MAX_DEPTH)));
+        }
+        match self.peek() {
+            Some('n') => self.literal("null", Json::Null),
+            Some('t') => self.literal("true", Json::Boolean(true)),
+            Some('f') => self.literal("false", Json::Boolean(false)),
+            Some('"') => self.string().map(Json::String),
+            Some('-' | '0'..='9') => self.number(),
+            Some('[') => {
+                self.next();
+                let mut items = Vec::new();
+                self.seq(']', |parser| {
+                    items.push(parser.value(depth + 1)?);
+                    Ok(())
+                })?;
+                Ok(Json::List(items))
+            }
+            Some('{') => {
+                self.next();
+                let mut entries: Vec<(String, Json)> = Vec::new();
+                self.seq('}', |parser| {
+                    if parser.peek() != Some('"') {
+                        return Err(parser.unexpected("una llave entre comillas"));
+                    }
+                    let key = parser.string()?;
+                    parser.skip_whitespace();
+                    parser.expect(':')?;
+                    parser.skip_whitespace();
+                    let value = parser.value(depth + 1)?;
+                    // Con llaves repetidas queda la ultima
+                    entries.retain(|(k, _)| *k != key);
+                    entries.push((key, value));
+                    Ok(())
+                })?;
+                Ok(Json::Dictionary(entries))
+            }
+            _ => Err(self.unexpected("un valor")),
+        }
+    }
+
+    // Elementos separados por comas hasta `close`
+    fn seq(
+        &mut self,
+        close: char,
+        mut item: impl FnMut(&mut Self) -> Result<(), JsonError>,
+    ) -> Result<(), JsonError> {
+        self.skip_whitespace();
+        if self.peek() == Some(close) {
+            self.next();
+            return Ok(());
+        }
+        loop {
+            item(self)?;
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => {
+                    self.next();
+                    self.skip_whitespace();
+                }
+                Some(c) if c == close => {
+                    self.next();
+                    return Ok(());
+                }
+                _ => return Err(self.unexpected(&format!("`,` o `{}`", close))),
+            }
+        }
+    }
+
+    fn literal(&mut self, word: &str, value: Json) -> Result<Json, JsonError> {
+        for expected in word.chars() {
+            if self.peek() != Some(expected) {
+                return Err(self.unexpected(&format!("`{}`", word)));
+            }
+            self.next();
+        }
+        Ok(value)
+    }
+
+    // Gramatica de RFC 8259: `-` opcional, entero sin ceros a la izquierda,
+    // fraccion y exponente opcionales, cada uno con al menos un digito
+    fn number(&mut self) -> Result<Json, JsonError> {
+        let (line, col) = (self.line, self.col);
+        let start = self.pos;
+        let mut float = false;
+        if self.peek() == Some('-') {
+            self.next();
+        }
+        match self.peek() {
+            Some('0') => {
+                self.next();
+                if matches!(self.peek(), Some('0'..='9')) {
+                    return Err(self.error("Los numeros no pueden empezar con 0".to_string()));
+                }
+            }
+            _ => self.digits()?,
+        }
+        if self.peek() == Some('.') {
+            self.next();
+            self.digits()?;
+            float = true;
+        }
+        if matches!(self.peek(), Some('e' | 'E')) {
+            self.next();
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.next();
+            }
+            self.digits()?;
+            float = true;
+        }
+        let text: String = self.chars[start..self.pos].iter().collect();
+        if !float {
+            if let Ok(int) = text.parse::<i64>() {
+                return Ok(Json::Numeric(Numeric::Int(int)));
+            }
+        }
+        match text.parse::<f64>() {
+            Ok(float) if float.is_finite() => Ok(Json::Numeric(Numeric::Float(float))),
+            _ => Err(JsonError {
+                msg: format!("El numero `{}` es demasiado grande", text),
+                line,
+                col,
+            }),
+        }
+    }
+
+    // Uno o mas digitos
+    fn digits(&mut self) -> Result<(), JsonError> {
+        if !matches!(self.peek(), Some('0'..='9')) {
+            return Err(self.unexpected("un digito"));
+        }
+        while matches!(self.peek(), Some('0'..='9')) {
+            self.next();
+        }
+        Ok(())
+    }
+
+    // Error en una posicion anterior, como el inicio de una secuencia de escape
+    fn error_at(&self, msg: String, (line, col): (usize, usize)) -> JsonError {
+        JsonError { msg, line, col }
+    }
+
+    fn string(&mut self) -> Result<String, JsonError> {
+        self.expect('"')?;
+        let mut string = String::new();
+        loop {
+            let pos = (self.line, self.col);
+            match self.next() {
+                Some('"') => return Ok(string),
+                Some('\\') => {
+                    let escaped = match self.next() {
+                        Some('"') => '"',
+                        Some('\\') => '\\',
+                        Some('/') => '/',
+                        Some('b') => '\u{8}',
+                        Some('f') => '\u{c}',
+                        Some('n') => '\n',
+                        Some('r') => '\r',
+                        Some('t') => '\t',
+                        Some('u') => self.unicode_escape(pos)?,
+                        Some(c) => {
+                            return Err(
+                                self.error_at(format!("La secuencia `\\{}` no es valida", c), pos)
+                            )
+                        }
+                        None => return Err(self.unexpected("una secuencia de escape")),
+                    };
+                    string.push(escaped);
+                }
+                Some(c) if (c as u32) < 0x20 => {
+                    return Err(self.error_at(
+                        "Las cadenas no pueden tener saltos de linea, usa `\\n`".to_string(),
+                        pos,
+                    ))
+                }
+                Some(c) => string.push(c),
+                None => return Err(self.unexpected("`\"` para cerrar la cadena")),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, JsonError> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let Some(digit) = self.peek().and_then(|c| c.to_digit(16)) else {
+                return Err(self.unexpected("un digito hexadecimal"));
+            };
+            self.next();
+            code = code * 16 + digit;
+        }
+        Ok(code)
+    }
+
+    // \uXXXX que empieza en `pos`, los caracteres fuera del plano basico vienen
+    // en dos partes
+    fn unicode_escape(&mut self, pos: (usize, usize)) -> Result<char, JsonError> {
+        let high = self.hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if self.peek() != Some('\\') {
+                return Err(self.error("Falta la segunda parte del caracter `\\u`".to_string()));
+            }
+            self.next();
+            self.expect('u')?;
+            let low = self.hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error_at(
+                    "La segunda parte del caracter `\\u` no es valida".to_string(),
+                    pos,
+                ));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code)
+            .ok_or_else(|| self.error_at("El caracter `\\u` no es valido".to_string(), pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(text: &str) -> (usize, usize) {
+        let err = Json::parse(text).unwrap_err();
+        (err.line, err.col)
+    }
+
+    #[test]
+    fn numbers_follow_rfc_8259() {
+        let num = |text: &str| Json::parse(text).map(|json| json.write(false));
+        assert_eq!(num("0"), Ok("0".to_string()));
+        assert_eq!(num("-0"), Ok("0".to_string()));
+        assert_eq!(num("120"), Ok("120".to_string()));
+        assert_eq!(num("0.5"), Ok("0.5".to_string()));
+        assert_eq!(num("-1.25e2"), Ok("-125.0".to_string()));
+        assert_eq!(num("1E+2"), Ok("100.0".to_string()));
+        assert_eq!(num("25e-1"), Ok("2.5".to_string()));
+        for text in [
+            "01", "-01", "00", "1.", "1.e3", ".5", "-", "1e", "1e+", "+1", "1-2",
+        ] {
+            assert!(num(text).is_err(), "{} deberia ser un error", text);
+        }
+    }
+
+    #[test]
+    fn number_error_position() {
+        assert_eq!(error("[1, 01]"), (1, 6));
+        assert_eq!(error("[1.]"), (1, 4));
+        assert_eq!(error("{\"a\":\n  2e}"), (2, 5));
+    }
+
+    #[test]
+    fn unterminated_string() {
+        assert_eq!(error("\"hola"), (1, 6));
+        assert_eq!(error("[\n  \"a\",\n  \"b\n]"), (3, 5));
+    }
+
+    #[test]
+    fn trailing_comma() {
+        assert_eq!(error("[1, 2,]"), (1, 7));
+        assert_eq!(error("{\n  \"a\": 1,\n}"), (3, 1));
+    }
+
+    #[test]
+    fn nesting_at_max_depth() {
+        let nested = |levels: usize, inner: &str| {
+            format!("{}{}{}", "[".repeat(levels), inner, "]".repeat(levels))
+        };
+        assert!(Json::parse(&nested(MAX_DEPTH, "1")).is_ok());
+        // Un cierre que no corresponde en el nivel mas profundo
+        let text = format!("{}}}", "[".repeat(MAX_DEPTH));
+        assert_eq!(error(&text), (1, MAX_DEPTH + 1));
+        // Un nivel de mas
+        assert_eq!(error(&nested(MAX_DEPTH + 1, "1")), (1, MAX_DEPTH + 2));
+        let err = Json::parse(&nested(MAX_DEPTH + 1, "1")).unwrap_err();
+        assert!(err.msg.contains("niveles"));
+    }
+
+    #[test]
+    fn bad_escape() {
+        assert_eq!(error(r#""a\qb""#), (1, 3));
+        assert_eq!(error("[\"ok\",\n \"\\u12g4\"]"), (2, 7));
+        assert_eq!(error(r#""\ud800x""#), (1, 8));
+        assert_eq!(error(r#""\ud800\u0041""#), (1, 2));
+    }
+}
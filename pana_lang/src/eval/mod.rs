@@ -1,3 +1,9 @@
+pub mod almacen;
+pub mod cuadros;
+pub mod entrada;
 pub mod environment;
 pub mod evaluator;
+pub mod json;
 pub mod objects;
+pub mod reloj;
+pub mod tarea;
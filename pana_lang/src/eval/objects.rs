@@ -1,12 +1,13 @@
-use std::{cell::RefCell, collections::HashMap, fmt::Display, hash::Hash, rc::Rc};
+use std::{cell::RefCell, cmp::Ordering, collections::HashMap, fmt::Display, hash::Hash, rc::Rc};
 
 use crate::{
-    buildins::internal::InternalFnPointer,
+    buildins::internal::InternalFn,
+    lienzo::{mapa::Mapa, particulas::Emisor, Color},
     parser::{
         expression::{format_arguments, FnParams},
         statement::BlockStatement,
     },
-    types::Numeric,
+    types::{Numeric, Vector},
 };
 
 use super::environment::Environment;
@@ -29,12 +30,17 @@ pub struct FnObj {
     pub params: FnParams,
     pub body: BlockStatement,
     pub env: Rc<RefCell<Environment>>,
+    // Comentario `###` antes de la declaracion
+    pub doc: Option<String>,
 }
 
+// Argumentos ya evaluados de una funcion interna
+pub type FnArgs = Vec<ResultObj>;
+
 #[derive(Clone)]
 pub struct BuildinFnObj {
     pub name: String,
-    pub func: Box<dyn InternalFnPointer>,
+    pub func: InternalFn,
 }
 
 #[derive(Clone)]
@@ -43,6 +49,10 @@ pub enum Object {
     Boolean(bool),
     Error(String),
     String(String),
+    Color(Color),
+    Vector(Vector),
+    Mapa(Mapa),
+    Emisor(Emisor),
     Return(Box<ResultObj>),
     List(Vec<ResultObj>),
     Dictionary(HashMap<ResultObj, ResultObj>),
@@ -64,6 +74,10 @@ impl PartialEq for Object {
             (Self::Boolean(l0), Self::Boolean(r0)) => l0 == r0,
             (Self::Error(l0), Self::Error(r0)) => l0 == r0,
             (Self::String(l0), Self::String(r0)) => l0 == r0,
+            (Self::Color(l0), Self::Color(r0)) => l0 == r0,
+            (Self::Vector(l0), Self::Vector(r0)) => l0 == r0,
+            (Self::Mapa(l0), Self::Mapa(r0)) => l0 == r0,
+            (Self::Emisor(l0), Self::Emisor(r0)) => l0 == r0,
             (Self::Return(_), Self::Return(_)) => panic!("No se peude comparar un return"),
             (Self::List(l0), Self::List(r0)) => l0 == r0,
             (Self::FnExpr { .. }, Self::FnExpr { .. }) => panic!("No se puede comparar funciones"),
@@ -83,6 +97,10 @@ impl Object {
             Object::Boolean(_) => "logico".to_owned(),
             Object::Error(_) => "error".to_owned(),
             Object::String(_) => "cadena".to_owned(),
+            Object::Color(_) => "color".to_owned(),
+            Object::Vector(_) => "vector".to_owned(),
+            Object::Mapa(_) => "mapa".to_owned(),
+            Object::Emisor(_) => "emisor".to_owned(),
             Object::Return(obj) => {
                 match obj.as_ref() {
                     ResultObj::Copy(obj) => obj.get_type(),
@@ -102,6 +120,46 @@ impl Object {
             Object::Continue => unreachable!(),
         }
     }
+
+    fn sort_rank(&self) -> u8 {
+        match self {
+            Object::Null => 0,
+            Object::Boolean(_) => 1,
+            Object::Numeric(_) => 2,
+            Object::String(_) => 3,
+            Object::List(_) => 4,
+            Object::Dictionary(_) => 5,
+            Object::Color(_) => 6,
+            Object::Vector(_) => 7,
+            Object::Mapa(_) => 8,
+            Object::Emisor(_) => 9,
+            Object::FnExpr(_) | Object::Fn(_) | Object::BuildinFn(_) => 10,
+            _ => 11,
+        }
+    }
+
+    // Orden total usado por `ordenar`: los valores de distinto tipo se ordenan
+    // nulo < logico < numerico < cadena < lista < diccionario < color < vector < mapa < emisor < funcion
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Object::Numeric(a), Object::Numeric(b)) => a.total_cmp(b),
+            (Object::Boolean(a), Object::Boolean(b)) => a.cmp(b),
+            (Object::String(a), Object::String(b)) => a.cmp(b),
+            (Object::List(a), Object::List(b)) => {
+                for (x, y) in a.iter().zip(b) {
+                    let ord = x.total_cmp(y);
+                    if ord != Ordering::Equal {
+                        return ord;
+                    }
+                }
+                a.len().cmp(&b.len())
+            }
+            (Object::Dictionary(a), Object::Dictionary(b)) => a.len().cmp(&b.len()),
+            (Object::Color(a), Object::Color(b)) => (a.r, a.g, a.b, a.a).cmp(&(b.r, b.g, b.b, b.a)),
+            (Object::Vector(a), Object::Vector(b)) => a.total_cmp(b),
+            (a, b) => a.sort_rank().cmp(&b.sort_rank()),
+        }
+    }
 }
 
 impl Display for Object {
@@ -123,6 +181,17 @@ impl Display for Object {
             Object::FnExpr(obj) => write!(f, "fn({}) {{...}}", format_arguments(&obj.params)),
             Object::BuildinFn(obj) => write!(f, "fn {}(...) {{...}}", obj.name),
             Object::String(string) => write!(f, "{}", string),
+            Object::Color(color) if color.a == 0xFF => {
+                write!(f, "rgb({}, {}, {})", color.r, color.g, color.b)
+            }
+            Object::Color(color) => write!(
+                f,
+                "rgba({}, {}, {}, {})",
+                color.r, color.g, color.b, color.a
+            ),
+            Object::Vector(vector) => write!(f, "{}", vector),
+            Object::Mapa(mapa) => write!(f, "{}", mapa),
+            Object::Emisor(emisor) => write!(f, "{}", emisor),
             Object::Void => write!(f, ""),
             Object::List(objs) => write!(
                 f,
@@ -157,8 +226,8 @@ fn bool_to_spanish(b: bool) -> String {
 /*
 ResultObj, como su nombre dice es el resultado de del Evaluator,
 este puede retornar una copia de un objeto como son los:
-Int, Bool, Null, String, Error, Return y Void. O retornar una referencia
-a un objeto como: List, Dictionary.
+Int, Bool, Null, String, Color, Vector, Error, Return y Void. O retornar una referencia
+a un objeto como: List, Dictionary, Mapa, Emisor.
 */
 #[derive(Clone)]
 pub enum ResultObj {
@@ -173,6 +242,20 @@ impl ResultObj {
             ResultObj::Ref(obj) => obj.borrow().get_type(),
         }
     }
+
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (ResultObj::Copy(a), ResultObj::Copy(b)) => a.total_cmp(b),
+            (ResultObj::Copy(a), ResultObj::Ref(b)) => a.total_cmp(&b.borrow()),
+            (ResultObj::Ref(a), ResultObj::Copy(b)) => a.borrow().total_cmp(b),
+            (ResultObj::Ref(a), ResultObj::Ref(b)) => {
+                if Rc::ptr_eq(a, b) {
+                    return Ordering::Equal;
+                }
+                a.borrow().total_cmp(&b.borrow())
+            }
+        }
+    }
 }
 
 impl Hash for ResultObj {
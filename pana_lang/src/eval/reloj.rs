@@ -0,0 +1,55 @@
+// Hora de `ahora`, `marca_tiempo` y `formatear_fecha`. En el navegador no
+// existe `SystemTime`, asi que cada aplicacion entrega su reloj.
+pub trait Reloj {
+    // Segundos desde el 1 de enero de 1970 a las 00:00 UTC
+    fn marca_tiempo(&self) -> f64;
+    // Segundos que se suman a UTC para tener la hora local
+    fn desfase(&self) -> i64 {
+        0
+    }
+}
+
+// Reloj del sistema, con la hora local. Fuera de Unix no hay de donde leer la
+// zona horaria sin mas dependencias y queda en UTC.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct RelojSistema;
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Reloj for RelojSistema {
+    fn marca_tiempo(&self) -> f64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default()
+    }
+
+    #[cfg(unix)]
+    fn desfase(&self) -> i64 {
+        let now = self.marca_tiempo() as libc::time_t;
+        let mut tm = std::mem::MaybeUninit::<libc::tm>::zeroed();
+        // `localtime_r` solo escribe en `tm` y devuelve nulo si falla
+        let tm = unsafe {
+            if libc::localtime_r(&now, tm.as_mut_ptr()).is_null() {
+                return 0;
+            }
+            tm.assume_init()
+        };
+        tm.tm_gmtoff as i64
+    }
+}
+
+// Reloj detenido en una hora, para que el programa haga lo mismo en cada ejecucion
+pub struct RelojFijo {
+    pub marca: f64,
+    pub desfase: i64,
+}
+
+impl Reloj for RelojFijo {
+    fn marca_tiempo(&self) -> f64 {
+        self.marca
+    }
+
+    fn desfase(&self) -> i64 {
+        self.desfase
+    }
+}
@@ -0,0 +1,34 @@
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+};
+
+// Evaluar es asincrono para que `preguntar`, `esperar` y `siguiente_cuadro`
+// puedan detener el programa a mitad de una funcion. La tarea queda con el
+// programa detenido y la aplicacion la sigue cuando tiene la respuesta o el
+// cuadro siguiente, sin hilos.
+pub type Tarea<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+// Nadie despierta a las tareas: quien las detuvo las vuelve a sondear
+struct Despertador;
+
+impl Wake for Despertador {
+    fn wake(self: Arc<Self>) {}
+}
+
+// Avanza la tarea hasta que termina o se detiene
+pub fn poll<T>(task: Pin<&mut dyn Future<Output = T>>) -> Poll<T> {
+    let waker = Waker::from(Arc::new(Despertador));
+    task.poll(&mut Context::from_waker(&waker))
+}
+
+// Corre una tarea que nunca se detiene, como las de la terminal, donde la
+// entrada y los cuadros responden enseguida
+pub fn block_on<T>(task: impl Future<Output = T>) -> T {
+    match poll(pin!(task)) {
+        Poll::Ready(value) => value,
+        Poll::Pending => panic!("La tarea se detuvo y nadie la puede seguir"),
+    }
+}
@@ -46,7 +46,7 @@ impl Lexer {
         }
 
         self.current_pos = self.read_pos;
-        self.read_pos += char::len_utf8(self.current_char);
+        self.read_pos += 1;
         self.col += 1;
     }
 
@@ -54,6 +54,23 @@ impl Lexer {
         self.input.get(self.read_pos)
     }
 
+    // `###` abre o cierra un comentario de bloque solo si esta solo en su linea,
+    // asi `####` o `### Titulo ###` siguen siendo comentarios de una linea
+    fn at_block_comment(&self) -> bool {
+        let start = self.input[..self.current_pos]
+            .iter()
+            .rposition(|c| *c == '\n')
+            .map_or(0, |i| i + 1);
+        let end = self.input[self.current_pos..]
+            .iter()
+            .position(|c| *c == '\n')
+            .map_or(self.input.len(), |i| self.current_pos + i);
+        self.input[start..end]
+            .iter()
+            .filter(|c| !matches!(c, ' ' | '\t' | '\r'))
+            .eq(['#'; 3].iter())
+    }
+
     fn skip_whitespace(&mut self) {
         while self.current_char == ' ' || self.current_char == '\t' || self.current_char == '\r'
         // || self.current_char == '\n'
@@ -246,13 +263,7 @@ impl Lexer {
             end += 1;
         }
 
-        let str_size: usize = self.input[start..end]
-            .iter()
-            .fold(0, |c1, c2| c1 + char::len_utf8(*c2));
-
-        let ident = &self.input[start..start + str_size]
-            .iter()
-            .collect::<String>();
+        let ident = &self.input[start..end].iter().collect::<String>();
         if self.identifier_regex.is_match(ident) {
             return Token::new(keywords_to_tokens(ident), self.line, self.col);
         }
@@ -267,19 +278,31 @@ impl Lexer {
     }
 
     fn read_string(&mut self) -> Token {
-        let start = self.current_pos;
-        let mut end = start + 1;
-
-        while self.current_char != '\0' {
+        let mut string = String::new();
+        loop {
             self.read_char();
-            if self.current_char == '"' {
-                let mut string = String::from_iter(self.input[start + 1..end].iter());
-                string = string.replace("\\n", "\n");
-                string = string.replace("\\t", "\t");
-                string = string.replace("\\r", "\r");
-                return Token::new(TokenType::String(string), self.line, self.col);
+            match self.current_char {
+                '\0' => break,
+                '"' => return Token::new(TokenType::String(string), self.line, self.col),
+                // `\"` pone comillas dentro de la cadena, por ejemplo para escribir JSON
+                '\\' => {
+                    self.read_char();
+                    match self.current_char {
+                        '\0' => break,
+                        'n' => string.push('\n'),
+                        't' => string.push('\t'),
+                        'r' => string.push('\r'),
+                        '"' => string.push('"'),
+                        '\\' => string.push('\\'),
+                        // Un escape desconocido se queda tal cual
+                        c => {
+                            string.push('\\');
+                            string.push(c);
+                        }
+                    }
+                }
+                c => string.push(c),
             }
-            end += 1;
         }
         Token::new(
             TokenType::IllegalMsg("Falta el simbolo `\"` para delimitar la cadena".to_string()),
@@ -316,6 +339,48 @@ impl Lexer {
         Token::new(TokenType::CommentLine, self.line, 0)
     }
 
+    // ###
+    // Comentario de bloque
+    // ###
+    // El texto se conserva para poder documentar la funcion que le sigue
+    fn read_block_comment(&mut self) -> Token {
+        let line = self.line;
+        let col = self.col;
+        self.read_char();
+        self.read_char();
+
+        let mut comment = String::new();
+        loop {
+            self.read_char();
+            match self.current_char {
+                '\0' => {
+                    return Token::new(
+                        TokenType::IllegalMsg(
+                            "Falta el simbolo `###` para cerrar el comentario".to_owned(),
+                        ),
+                        line,
+                        col,
+                    )
+                }
+                '#' if self.at_block_comment() => {
+                    self.read_char();
+                    self.read_char();
+                    return Token::new(
+                        TokenType::CommentBlock(comment.trim().to_owned()),
+                        line,
+                        col,
+                    );
+                }
+                '\n' => {
+                    self.col = 0;
+                    self.line += 1;
+                    comment.push('\n');
+                }
+                c => comment.push(c),
+            }
+        }
+    }
+
     pub fn next_token(&mut self) -> Token {
         self.read_char();
         self.skip_whitespace();
@@ -338,7 +403,12 @@ impl Lexer {
             '}' => Token::new(TokenType::RBrace, self.line, self.col),
             '[' => Token::new(TokenType::LBracket, self.line, self.col),
             ']' => Token::new(TokenType::RBracket, self.line, self.col),
-            '#' => self.read_to_end_line(),
+            '#' => {
+                if self.at_block_comment() {
+                    return self.read_block_comment();
+                }
+                self.read_to_end_line()
+            }
             ':' => Token::new(TokenType::Colon, self.line, self.col),
             '"' => self.read_string(),
             '\n' => {
@@ -358,3 +428,129 @@ impl Lexer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex_string(source: &str) -> TokenType {
+        Lexer::new(source.chars().collect()).next_token().r#type
+    }
+
+    #[test]
+    fn string_without_escapes() {
+        assert_eq!(
+            lex_string(r#""hola mundo""#),
+            TokenType::String("hola mundo".to_string())
+        );
+    }
+
+    #[test]
+    fn string_escapes() {
+        assert_eq!(
+            lex_string(r#""a\nb""#),
+            TokenType::String("a\nb".to_string())
+        );
+        assert_eq!(
+            lex_string(r#""a\tb""#),
+            TokenType::String("a\tb".to_string())
+        );
+        assert_eq!(
+            lex_string(r#""a\rb""#),
+            TokenType::String("a\rb".to_string())
+        );
+        assert_eq!(
+            lex_string(r#""Dijo \"hola\"""#),
+            TokenType::String("Dijo \"hola\"".to_string())
+        );
+    }
+
+    #[test]
+    fn string_ending_in_backslash() {
+        assert_eq!(
+            lex_string(r#""C:\\""#),
+            TokenType::String("C:\\".to_string())
+        );
+    }
+
+    #[test]
+    fn escaped_backslash_before_n() {
+        assert_eq!(
+            lex_string(r#""x\\ny""#),
+            TokenType::String("x\\ny".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_escape_is_kept() {
+        assert_eq!(
+            lex_string(r#""a\qb""#),
+            TokenType::String("a\\qb".to_string())
+        );
+    }
+
+    #[test]
+    fn unterminated_string() {
+        assert!(matches!(lex_string(r#""abc"#), TokenType::IllegalMsg(_)));
+        assert!(matches!(lex_string(r#""abc\""#), TokenType::IllegalMsg(_)));
+    }
+
+    #[test]
+    fn tokens_after_string() {
+        let mut lexer = Lexer::new(r#""C:\\" ;"#.chars().collect());
+        assert_eq!(
+            lexer.next_token().r#type,
+            TokenType::String("C:\\".to_string())
+        );
+        assert_eq!(lexer.next_token().r#type, TokenType::SemiColon);
+    }
+
+    fn tokens(source: &str) -> Vec<TokenType> {
+        let mut lexer = Lexer::new(source.chars().collect());
+        let mut tokens = Vec::new();
+        loop {
+            match lexer.next_token().r#type {
+                TokenType::Eof => return tokens,
+                TokenType::NewLine | TokenType::CommentLine => {}
+                token => tokens.push(token),
+            }
+        }
+    }
+
+    #[test]
+    fn block_comment_on_its_own_lines() {
+        let source = "###\n  Suma dos numeros\n###\nfn";
+        assert_eq!(
+            tokens(source),
+            vec![
+                TokenType::CommentBlock("Suma dos numeros".to_string()),
+                TokenType::Func
+            ]
+        );
+        // Con espacios alrededor de las marcas
+        let source = "  ###  \r\n a ### b\n\t###\nfn";
+        assert_eq!(
+            tokens(source),
+            vec![
+                TokenType::CommentBlock("a ### b".to_string()),
+                TokenType::Func
+            ]
+        );
+    }
+
+    #[test]
+    fn banners_are_line_comments() {
+        let source = "####################\n### Titulo ###\n#### Seccion ####\nfn";
+        assert_eq!(tokens(source), vec![TokenType::Func]);
+        let source = "var a = 1; ###\nfn";
+        assert_eq!(tokens(source).last(), Some(&TokenType::Func));
+    }
+
+    #[test]
+    fn unterminated_block_comment() {
+        assert!(matches!(
+            tokens("###\nfn\n### fin")[..],
+            [TokenType::IllegalMsg(_)]
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 mod buildins;
 pub mod eval;
 pub mod lexer;
+pub mod lienzo;
 pub mod parser;
 mod token;
-mod types;
+pub mod types;
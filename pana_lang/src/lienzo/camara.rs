@@ -0,0 +1,50 @@
+use super::transformacion::Transformacion;
+
+// Camara de `camara_posicion`, `camara_zoom` y `camara_seguir`. Muestra el
+// mundo alrededor de `center` y, a diferencia de las transformaciones, se
+// conserva entre cuadros.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camara {
+    // Punto del mundo que se ve en el centro del lienzo. Sin posicion la
+    // camara sigue al centro del lienzo y no mueve nada.
+    pub center: Option<(f32, f32)>,
+    pub zoom: f32,
+}
+
+impl Default for Camara {
+    fn default() -> Self {
+        Self {
+            center: None,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl Camara {
+    pub fn center(&self, (width, height): (f32, f32)) -> (f32, f32) {
+        self.center.unwrap_or((width / 2.0, height / 2.0))
+    }
+
+    // Pasa de coordenadas del mundo a coordenadas del lienzo
+    pub fn transform(&self, canvas: (f32, f32)) -> Transformacion {
+        if *self == Self::default() {
+            return Transformacion::IDENTITY;
+        }
+        let center = self.center(canvas);
+        Transformacion::IDENTITY
+            .translate(canvas.0 / 2.0, canvas.1 / 2.0)
+            .scale(self.zoom, self.zoom)
+            .translate(-center.0, -center.1)
+    }
+
+    // Acerca la camara a `target`. `smoothing` es la parte de la distancia que
+    // queda por recorrer: 0 llega de inmediato, cerca de 1 avanza despacio.
+    pub fn follow(&mut self, target: (f32, f32), canvas: (f32, f32), smoothing: f32) {
+        let center = self.center(canvas);
+        let t = 1.0 - smoothing.clamp(0.0, 1.0);
+        self.center = Some((
+            center.0 + (target.0 - center.0) * t,
+            center.1 + (target.1 - center.1) * t,
+        ));
+    }
+}
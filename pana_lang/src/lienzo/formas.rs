@@ -0,0 +1,227 @@
+use std::f32::consts::TAU;
+
+use super::{Color, Lienzo};
+
+// Estilo de las figuras: `relleno`, `sin_relleno`, `trazo` y `redondeo`.
+// Se conserva entre cuadros hasta que el programa lo cambie.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Estilo {
+    pub fill: Option<Color>,
+    // Color y grosor del borde
+    pub stroke: Option<(Color, f32)>,
+    // Radio de las esquinas de los rectangulos
+    pub radius: f32,
+}
+
+impl Default for Estilo {
+    fn default() -> Self {
+        Self {
+            fill: Some(Color::new(0xFF, 0xFF, 0xFF, 0xFF)),
+            stroke: None,
+            radius: 0.0,
+        }
+    }
+}
+
+impl Estilo {
+    // El color que se pasa directo a una figura reemplaza al del relleno, o al
+    // del borde si la figura no tiene relleno.
+    pub fn with_color(&self, color: Option<Color>) -> Self {
+        let Some(color) = color else {
+            return *self;
+        };
+        match (self.fill, self.stroke) {
+            (None, Some((_, width))) => Self {
+                stroke: Some((color, width)),
+                ..*self
+            },
+            _ => Self {
+                fill: Some(color),
+                ..*self
+            },
+        }
+    }
+
+    // Color y grosor de las lineas sueltas, que no tienen relleno
+    pub fn line_stroke(&self, color: Option<Color>) -> (Color, f32) {
+        match (color, self.stroke) {
+            (Some(color), Some((_, width))) => (color, width),
+            (Some(color), None) => (color, 1.0),
+            (None, Some(stroke)) => stroke,
+            (None, None) => (Color::new(0xFF, 0xFF, 0xFF, 0xFF), 1.0),
+        }
+    }
+
+    // Dibuja una figura cerrada con el relleno y el borde del estilo
+    pub fn draw_polygon(&self, lienzo: &mut dyn Lienzo, points: &[(f32, f32)]) {
+        if let Some(fill) = self.fill {
+            lienzo.polygon_filled(points, fill);
+        }
+        if let Some((color, width)) = self.stroke {
+            lienzo.polyline(points, true, width, color);
+        }
+    }
+
+    pub fn draw_rect(&self, lienzo: &mut dyn Lienzo, min: (f32, f32), max: (f32, f32)) {
+        if self.radius > 0.0 {
+            return self.draw_polygon(lienzo, &rounded_rect_points(min, max, self.radius));
+        }
+        if let Some(fill) = self.fill {
+            lienzo.rect_filled(min, max, fill);
+        }
+        if let Some((color, width)) = self.stroke {
+            let corners = [min, (max.0, min.1), max, (min.0, max.1)];
+            lienzo.polyline(&corners, true, width, color);
+        }
+    }
+
+    pub fn draw_ellipse(&self, lienzo: &mut dyn Lienzo, center: (f32, f32), radii: (f32, f32)) {
+        let points = full_ellipse_points(center, radii);
+        if let Some(fill) = self.fill {
+            match radii.0 == radii.1 {
+                true => lienzo.circle_filled(center, radii.0, fill),
+                false => lienzo.polygon_filled(&points, fill),
+            }
+        }
+        if let Some((color, width)) = self.stroke {
+            lienzo.polyline(&points, true, width, color);
+        }
+    }
+
+    // El relleno de un arco es una rebanada desde el centro, el borde solo
+    // sigue la curva.
+    pub fn draw_arc(
+        &self,
+        lienzo: &mut dyn Lienzo,
+        center: (f32, f32),
+        radius: f32,
+        start: f32,
+        end: f32,
+    ) {
+        let points = ellipse_points(center, (radius, radius), start, end);
+        if let Some(fill) = self.fill {
+            let mut slice = Vec::with_capacity(points.len() + 1);
+            slice.push(center);
+            slice.extend_from_slice(&points);
+            lienzo.polygon_filled(&slice, fill);
+        }
+        if let Some((color, width)) = self.stroke {
+            lienzo.polyline(&points, false, width, color);
+        }
+    }
+}
+
+// Cantidad de segmentos para que cada uno mida unos pocos pixeles
+fn segments(radius: f32, sweep: f32) -> usize {
+    ((radius * sweep.abs() / 3.0).ceil() as usize).clamp(8, 128)
+}
+
+// Puntos de un arco de elipse. Los angulos estan en radianes y crecen en el
+// sentido del reloj, porque el eje y apunta hacia abajo.
+pub fn ellipse_points(
+    center: (f32, f32),
+    radii: (f32, f32),
+    start: f32,
+    end: f32,
+) -> Vec<(f32, f32)> {
+    let sweep = end - start;
+    let n = segments(radii.0.max(radii.1), sweep);
+    (0..=n)
+        .map(|i| {
+            let angle = start + sweep * i as f32 / n as f32;
+            (
+                center.0 + radii.0 * angle.cos(),
+                center.1 + radii.1 * angle.sin(),
+            )
+        })
+        .collect()
+}
+
+// Elipse completa, sin repetir el primer punto al final
+pub fn full_ellipse_points(center: (f32, f32), radii: (f32, f32)) -> Vec<(f32, f32)> {
+    let mut points = ellipse_points(center, radii, 0.0, TAU);
+    points.pop();
+    points
+}
+
+// Contorno de un rectangulo con las esquinas redondeadas
+pub fn rounded_rect_points(min: (f32, f32), max: (f32, f32), radius: f32) -> Vec<(f32, f32)> {
+    let radius = radius
+        .min((max.0 - min.0) / 2.0)
+        .min((max.1 - min.1) / 2.0)
+        .max(0.0);
+    let corners = [
+        ((max.0 - radius, min.1 + radius), -TAU / 4.0),
+        ((max.0 - radius, max.1 - radius), 0.0),
+        ((min.0 + radius, max.1 - radius), TAU / 4.0),
+        ((min.0 + radius, min.1 + radius), TAU / 2.0),
+    ];
+    corners
+        .iter()
+        .flat_map(|&(center, start)| {
+            ellipse_points(center, (radius, radius), start, start + TAU / 4.0)
+        })
+        .collect()
+}
+
+fn cross(a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> f32 {
+    (b.0 - a.0) * (c.1 - b.1) - (b.1 - a.1) * (c.0 - b.0)
+}
+
+fn in_triangle(p: (f32, f32), a: (f32, f32), b: (f32, f32), c: (f32, f32)) -> bool {
+    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+}
+
+// Divide un poligono simple, convexo o no, en triangulos cortando orejas.
+// Los poligonos que se cruzan a si mismos terminan como un abanico.
+pub fn triangulate(points: &[(f32, f32)]) -> Vec<[usize; 3]> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+    let area: f32 = (0..points.len())
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % points.len()]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum();
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    if area < 0.0 {
+        remaining.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(points.len() - 2);
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let ear = (0..len).find_map(|i| {
+            let (a, b, c) = (
+                remaining[(i + len - 1) % len],
+                remaining[i],
+                remaining[(i + 1) % len],
+            );
+            let turn = cross(points[a], points[b], points[c]);
+            if turn.abs() <= f32::EPSILON {
+                // Punto alineado con sus vecinos, se quita sin triangulo
+                return Some((i, None));
+            }
+            let blocked = turn < 0.0
+                || remaining.iter().any(|&p| {
+                    p != a
+                        && p != b
+                        && p != c
+                        && in_triangle(points[p], points[a], points[b], points[c])
+                });
+            (!blocked).then_some((i, Some([a, b, c])))
+        });
+        match ear {
+            Some((i, triangle)) => {
+                triangles.extend(triangle);
+                remaining.remove(i);
+            }
+            None => break,
+        }
+    }
+    for i in 1..remaining.len().saturating_sub(1) {
+        triangles.push([remaining[0], remaining[i], remaining[i + 1]]);
+    }
+    triangles
+}
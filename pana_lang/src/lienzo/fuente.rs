@@ -0,0 +1,174 @@
+// Fuente de mapa de bits de 8x8 para el rasterizador y el modo texto.
+// Cada glifo son 8 filas, el bit mas alto de cada fila es el pixel de la izquierda.
+
+pub const GLYPH_SIZE: usize = 8;
+
+// Caracteres ASCII imprimibles, desde ' ' hasta '~'
+const ASCII: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x00, 0x10, 0x00], // '!'
+    [0x28, 0x28, 0x28, 0x00, 0x00, 0x00, 0x00, 0x00], // '"'
+    [0x28, 0x28, 0x7C, 0x28, 0x7C, 0x28, 0x28, 0x00], // '#'
+    [0x10, 0x3C, 0x50, 0x38, 0x14, 0x78, 0x10, 0x00], // '$'
+    [0x60, 0x64, 0x08, 0x10, 0x20, 0x4C, 0x0C, 0x00], // '%'
+    [0x30, 0x48, 0x50, 0x20, 0x54, 0x48, 0x34, 0x00], // '&'
+    [0x10, 0x10, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00], // "'"
+    [0x08, 0x10, 0x20, 0x20, 0x20, 0x10, 0x08, 0x00], // '('
+    [0x20, 0x10, 0x08, 0x08, 0x08, 0x10, 0x20, 0x00], // ')'
+    [0x00, 0x10, 0x54, 0x38, 0x54, 0x10, 0x00, 0x00], // '*'
+    [0x00, 0x10, 0x10, 0x7C, 0x10, 0x10, 0x00, 0x00], // '+'
+    [0x00, 0x00, 0x00, 0x00, 0x30, 0x10, 0x20, 0x00], // ','
+    [0x00, 0x00, 0x00, 0x7C, 0x00, 0x00, 0x00, 0x00], // '-'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x30, 0x30, 0x00], // '.'
+    [0x00, 0x04, 0x08, 0x10, 0x20, 0x40, 0x00, 0x00], // '/'
+    [0x38, 0x44, 0x4C, 0x54, 0x64, 0x44, 0x38, 0x00], // '0'
+    [0x10, 0x30, 0x10, 0x10, 0x10, 0x10, 0x38, 0x00], // '1'
+    [0x38, 0x44, 0x04, 0x08, 0x10, 0x20, 0x7C, 0x00], // '2'
+    [0x7C, 0x08, 0x10, 0x08, 0x04, 0x44, 0x38, 0x00], // '3'
+    [0x08, 0x18, 0x28, 0x48, 0x7C, 0x08, 0x08, 0x00], // '4'
+    [0x7C, 0x40, 0x78, 0x04, 0x04, 0x44, 0x38, 0x00], // '5'
+    [0x18, 0x20, 0x40, 0x78, 0x44, 0x44, 0x38, 0x00], // '6'
+    [0x7C, 0x04, 0x08, 0x10, 0x20, 0x20, 0x20, 0x00], // '7'
+    [0x38, 0x44, 0x44, 0x38, 0x44, 0x44, 0x38, 0x00], // '8'
+    [0x38, 0x44, 0x44, 0x3C, 0x04, 0x08, 0x30, 0x00], // '9'
+    [0x00, 0x30, 0x30, 0x00, 0x30, 0x30, 0x00, 0x00], // ':'
+    [0x00, 0x30, 0x30, 0x00, 0x30, 0x10, 0x20, 0x00], // ';'
+    [0x08, 0x10, 0x20, 0x40, 0x20, 0x10, 0x08, 0x00], // '<'
+    [0x00, 0x00, 0x7C, 0x00, 0x7C, 0x00, 0x00, 0x00], // '='
+    [0x20, 0x10, 0x08, 0x04, 0x08, 0x10, 0x20, 0x00], // '>'
+    [0x38, 0x44, 0x04, 0x08, 0x10, 0x00, 0x10, 0x00], // '?'
+    [0x38, 0x44, 0x04, 0x34, 0x54, 0x54, 0x38, 0x00], // '@'
+    [0x38, 0x44, 0x44, 0x7C, 0x44, 0x44, 0x44, 0x00], // 'A'
+    [0x78, 0x44, 0x44, 0x78, 0x44, 0x44, 0x78, 0x00], // 'B'
+    [0x38, 0x44, 0x40, 0x40, 0x40, 0x44, 0x38, 0x00], // 'C'
+    [0x70, 0x48, 0x44, 0x44, 0x44, 0x48, 0x70, 0x00], // 'D'
+    [0x7C, 0x40, 0x40, 0x78, 0x40, 0x40, 0x7C, 0x00], // 'E'
+    [0x7C, 0x40, 0x40, 0x78, 0x40, 0x40, 0x40, 0x00], // 'F'
+    [0x38, 0x44, 0x40, 0x5C, 0x44, 0x44, 0x3C, 0x00], // 'G'
+    [0x44, 0x44, 0x44, 0x7C, 0x44, 0x44, 0x44, 0x00], // 'H'
+    [0x38, 0x10, 0x10, 0x10, 0x10, 0x10, 0x38, 0x00], // 'I'
+    [0x1C, 0x08, 0x08, 0x08, 0x08, 0x48, 0x30, 0x00], // 'J'
+    [0x44, 0x48, 0x50, 0x60, 0x50, 0x48, 0x44, 0x00], // 'K'
+    [0x40, 0x40, 0x40, 0x40, 0x40, 0x40, 0x7C, 0x00], // 'L'
+    [0x44, 0x6C, 0x54, 0x54, 0x44, 0x44, 0x44, 0x00], // 'M'
+    [0x44, 0x44, 0x64, 0x54, 0x4C, 0x44, 0x44, 0x00], // 'N'
+    [0x38, 0x44, 0x44, 0x44, 0x44, 0x44, 0x38, 0x00], // 'O'
+    [0x78, 0x44, 0x44, 0x78, 0x40, 0x40, 0x40, 0x00], // 'P'
+    [0x38, 0x44, 0x44, 0x44, 0x54, 0x48, 0x34, 0x00], // 'Q'
+    [0x78, 0x44, 0x44, 0x78, 0x50, 0x48, 0x44, 0x00], // 'R'
+    [0x3C, 0x40, 0x40, 0x38, 0x04, 0x04, 0x78, 0x00], // 'S'
+    [0x7C, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00], // 'T'
+    [0x44, 0x44, 0x44, 0x44, 0x44, 0x44, 0x38, 0x00], // 'U'
+    [0x44, 0x44, 0x44, 0x44, 0x44, 0x28, 0x10, 0x00], // 'V'
+    [0x44, 0x44, 0x44, 0x54, 0x54, 0x54, 0x28, 0x00], // 'W'
+    [0x44, 0x44, 0x28, 0x10, 0x28, 0x44, 0x44, 0x00], // 'X'
+    [0x44, 0x44, 0x44, 0x28, 0x10, 0x10, 0x10, 0x00], // 'Y'
+    [0x7C, 0x04, 0x08, 0x10, 0x20, 0x40, 0x7C, 0x00], // 'Z'
+    [0x38, 0x20, 0x20, 0x20, 0x20, 0x20, 0x38, 0x00], // '['
+    [0x00, 0x40, 0x20, 0x10, 0x08, 0x04, 0x00, 0x00], // '\\'
+    [0x38, 0x08, 0x08, 0x08, 0x08, 0x08, 0x38, 0x00], // ']'
+    [0x10, 0x28, 0x44, 0x00, 0x00, 0x00, 0x00, 0x00], // '^'
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x7C, 0x00], // '_'
+    [0x20, 0x10, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00], // '`'
+    [0x00, 0x00, 0x38, 0x04, 0x3C, 0x44, 0x3C, 0x00], // 'a'
+    [0x40, 0x40, 0x58, 0x64, 0x44, 0x44, 0x78, 0x00], // 'b'
+    [0x00, 0x00, 0x38, 0x40, 0x40, 0x44, 0x38, 0x00], // 'c'
+    [0x04, 0x04, 0x34, 0x4C, 0x44, 0x44, 0x3C, 0x00], // 'd'
+    [0x00, 0x00, 0x38, 0x44, 0x7C, 0x40, 0x38, 0x00], // 'e'
+    [0x18, 0x24, 0x20, 0x70, 0x20, 0x20, 0x20, 0x00], // 'f'
+    [0x00, 0x3C, 0x44, 0x44, 0x3C, 0x04, 0x38, 0x00], // 'g'
+    [0x40, 0x40, 0x58, 0x64, 0x44, 0x44, 0x44, 0x00], // 'h'
+    [0x10, 0x00, 0x30, 0x10, 0x10, 0x10, 0x38, 0x00], // 'i'
+    [0x08, 0x00, 0x18, 0x08, 0x08, 0x48, 0x30, 0x00], // 'j'
+    [0x40, 0x40, 0x48, 0x50, 0x60, 0x50, 0x48, 0x00], // 'k'
+    [0x30, 0x10, 0x10, 0x10, 0x10, 0x10, 0x38, 0x00], // 'l'
+    [0x00, 0x00, 0x68, 0x54, 0x54, 0x44, 0x44, 0x00], // 'm'
+    [0x00, 0x00, 0x58, 0x64, 0x44, 0x44, 0x44, 0x00], // 'n'
+    [0x00, 0x00, 0x38, 0x44, 0x44, 0x44, 0x38, 0x00], // 'o'
+    [0x00, 0x00, 0x78, 0x44, 0x78, 0x40, 0x40, 0x00], // 'p'
+    [0x00, 0x00, 0x34, 0x4C, 0x3C, 0x04, 0x04, 0x00], // 'q'
+    [0x00, 0x00, 0x58, 0x64, 0x40, 0x40, 0x40, 0x00], // 'r'
+    [0x00, 0x00, 0x38, 0x40, 0x38, 0x04, 0x78, 0x00], // 's'
+    [0x20, 0x20, 0x70, 0x20, 0x20, 0x24, 0x18, 0x00], // 't'
+    [0x00, 0x00, 0x44, 0x44, 0x44, 0x4C, 0x34, 0x00], // 'u'
+    [0x00, 0x00, 0x44, 0x44, 0x44, 0x28, 0x10, 0x00], // 'v'
+    [0x00, 0x00, 0x44, 0x44, 0x54, 0x54, 0x28, 0x00], // 'w'
+    [0x00, 0x00, 0x44, 0x28, 0x10, 0x28, 0x44, 0x00], // 'x'
+    [0x00, 0x00, 0x44, 0x44, 0x3C, 0x04, 0x38, 0x00], // 'y'
+    [0x00, 0x00, 0x7C, 0x08, 0x10, 0x20, 0x7C, 0x00], // 'z'
+    [0x08, 0x10, 0x10, 0x20, 0x10, 0x10, 0x08, 0x00], // '{'
+    [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00], // '|'
+    [0x20, 0x10, 0x10, 0x08, 0x10, 0x10, 0x20, 0x00], // '}'
+    [0x00, 0x00, 0x20, 0x54, 0x08, 0x00, 0x00, 0x00], // '~'
+];
+
+const EXTRA: [(char, [u8; 8]); 10] = [
+    ('á', [0x08, 0x10, 0x38, 0x04, 0x3C, 0x44, 0x3C, 0x00]),
+    ('é', [0x08, 0x10, 0x38, 0x44, 0x7C, 0x40, 0x38, 0x00]),
+    ('í', [0x08, 0x10, 0x30, 0x10, 0x10, 0x10, 0x38, 0x00]),
+    ('ó', [0x08, 0x10, 0x38, 0x44, 0x44, 0x44, 0x38, 0x00]),
+    ('ú', [0x08, 0x10, 0x44, 0x44, 0x44, 0x4C, 0x34, 0x00]),
+    ('ñ', [0x34, 0x48, 0x58, 0x64, 0x44, 0x44, 0x44, 0x00]),
+    ('ü', [0x28, 0x00, 0x44, 0x44, 0x44, 0x4C, 0x34, 0x00]),
+    ('Ñ', [0x34, 0x48, 0x44, 0x64, 0x54, 0x4C, 0x44, 0x00]),
+    ('¿', [0x10, 0x00, 0x10, 0x20, 0x40, 0x44, 0x38, 0x00]),
+    ('¡', [0x10, 0x00, 0x10, 0x10, 0x10, 0x10, 0x10, 0x00]),
+];
+
+// Graficos de bloques parecidos a los de PETSCII, ocupan toda la celda
+const BLOCKS: [(char, [u8; 8]); 39] = [
+    ('█', [0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]),
+    ('▀', [0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00]),
+    ('▄', [0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF]),
+    ('▌', [0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0, 0xF0]),
+    ('▐', [0x0F, 0x0F, 0x0F, 0x0F, 0x0F, 0x0F, 0x0F, 0x0F]),
+    ('▘', [0xF0, 0xF0, 0xF0, 0xF0, 0x00, 0x00, 0x00, 0x00]),
+    ('▝', [0x0F, 0x0F, 0x0F, 0x0F, 0x00, 0x00, 0x00, 0x00]),
+    ('▖', [0x00, 0x00, 0x00, 0x00, 0xF0, 0xF0, 0xF0, 0xF0]),
+    ('▗', [0x00, 0x00, 0x00, 0x00, 0x0F, 0x0F, 0x0F, 0x0F]),
+    ('▚', [0xF0, 0xF0, 0xF0, 0xF0, 0x0F, 0x0F, 0x0F, 0x0F]),
+    ('▞', [0x0F, 0x0F, 0x0F, 0x0F, 0xF0, 0xF0, 0xF0, 0xF0]),
+    ('░', [0x88, 0x00, 0x22, 0x00, 0x88, 0x00, 0x22, 0x00]),
+    ('▒', [0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55, 0xAA, 0x55]),
+    ('▓', [0xEE, 0x7D, 0xBB, 0x7F, 0xEE, 0x7D, 0xBB, 0x7F]),
+    ('─', [0x00, 0x00, 0x00, 0xFF, 0x00, 0x00, 0x00, 0x00]),
+    ('│', [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x10]),
+    ('┌', [0x00, 0x00, 0x00, 0x1F, 0x10, 0x10, 0x10, 0x10]),
+    ('┐', [0x00, 0x00, 0x00, 0xF0, 0x10, 0x10, 0x10, 0x10]),
+    ('└', [0x10, 0x10, 0x10, 0x1F, 0x00, 0x00, 0x00, 0x00]),
+    ('┘', [0x10, 0x10, 0x10, 0xF0, 0x00, 0x00, 0x00, 0x00]),
+    ('├', [0x10, 0x10, 0x10, 0x1F, 0x10, 0x10, 0x10, 0x10]),
+    ('┤', [0x10, 0x10, 0x10, 0xF0, 0x10, 0x10, 0x10, 0x10]),
+    ('┬', [0x00, 0x00, 0x00, 0xFF, 0x10, 0x10, 0x10, 0x10]),
+    ('┴', [0x10, 0x10, 0x10, 0xFF, 0x00, 0x00, 0x00, 0x00]),
+    ('┼', [0x10, 0x10, 0x10, 0xFF, 0x10, 0x10, 0x10, 0x10]),
+    ('╱', [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80]),
+    ('╲', [0x80, 0x40, 0x20, 0x10, 0x08, 0x04, 0x02, 0x01]),
+    ('╳', [0x81, 0x42, 0x24, 0x18, 0x18, 0x24, 0x42, 0x81]),
+    ('●', [0x3C, 0x7E, 0xFF, 0xFF, 0xFF, 0xFF, 0x7E, 0x3C]),
+    ('○', [0x3C, 0x42, 0x81, 0x81, 0x81, 0x81, 0x42, 0x3C]),
+    ('♥', [0x66, 0xFF, 0xFF, 0xFF, 0x7E, 0x3C, 0x18, 0x00]),
+    ('♦', [0x18, 0x3C, 0x7E, 0xFF, 0x7E, 0x3C, 0x18, 0x00]),
+    ('♣', [0x18, 0x3C, 0x18, 0x66, 0xFF, 0x66, 0x18, 0x3C]),
+    ('♠', [0x18, 0x3C, 0x7E, 0xFF, 0xFF, 0x66, 0x18, 0x3C]),
+    ('▔', [0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('▁', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF]),
+    ('▏', [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80]),
+    ('▕', [0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01, 0x01]),
+    ('π', [0x00, 0x00, 0x7E, 0x24, 0x24, 0x24, 0x66, 0x00]),
+];
+
+// Recuadro para los caracteres que no estan en la fuente
+const UNKNOWN: [u8; 8] = [0x7C, 0x44, 0x44, 0x44, 0x44, 0x44, 0x7C, 0x00];
+
+pub fn glyph(c: char) -> &'static [u8; 8] {
+    match c {
+        ' '..='~' => &ASCII[c as usize - ' ' as usize],
+        _ => EXTRA
+            .iter()
+            .chain(BLOCKS.iter())
+            .find(|(extra, _)| *extra == c)
+            .map(|(_, glyph)| glyph)
+            .unwrap_or(&UNKNOWN),
+    }
+}
@@ -0,0 +1,264 @@
+use std::collections::HashMap;
+
+use super::{
+    paleta::{nearest, C64},
+    rasterizador::Rasterizador,
+};
+
+// Retraso entre cuadros en centesimas de segundo, lo mas cercano a 60 cuadros por segundo
+pub const FRAME_DELAY_CS: u16 = 2;
+
+// Colores de la paleta: 2^(MIN_CODE_SIZE)
+const MIN_CODE_SIZE: u8 = 4;
+const MAX_CODE_SIZE: u8 = 12;
+const MAX_CODES: u16 = 1 << MAX_CODE_SIZE;
+
+// El ancho y el alto se guardan en 16 bits
+pub const MAX_SIZE: usize = u16::MAX as usize;
+
+// GIF animado con la paleta de la C64. Cada cuadro se cuantiza y comprime al
+// agregarlo, asi solo se guarda en memoria el archivo ya codificado.
+pub struct AnimacionGif {
+    width: usize,
+    height: usize,
+    delay_cs: u16,
+    frames: usize,
+    out: Vec<u8>,
+}
+
+impl AnimacionGif {
+    // `delay_cs`: centesimas de segundo entre cuadros
+    pub fn new(width: usize, height: usize, delay_cs: u16) -> Result<Self, String> {
+        if width > MAX_SIZE || height > MAX_SIZE {
+            return Err(format!(
+                "Un GIF mide como maximo {}x{} pixeles, no {}x{}",
+                MAX_SIZE, MAX_SIZE, width, height
+            ));
+        }
+        let mut out = b"GIF89a".to_vec();
+        out.extend_from_slice(&(width as u16).to_le_bytes());
+        out.extend_from_slice(&(height as u16).to_le_bytes());
+        // Tabla de colores global de 16 colores con 8 bits por canal
+        out.extend_from_slice(&[0xF3, 0, 0]);
+        for color in C64 {
+            out.extend_from_slice(&[color.r, color.g, color.b]);
+        }
+        // Extension NETSCAPE2.0: repetir para siempre
+        out.extend_from_slice(&[0x21, 0xFF, 0x0B]);
+        out.extend_from_slice(b"NETSCAPE2.0");
+        out.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+        Ok(Self {
+            width,
+            height,
+            delay_cs,
+            frames: 0,
+            out,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    // El rasterizador debe tener el mismo tamano que la animacion
+    pub fn push_frame(&mut self, rasterizador: &Rasterizador) {
+        let indices: Vec<u8> = rasterizador
+            .pixels()
+            .chunks(4)
+            .map(|p| nearest(p[0], p[1], p[2], p[3]))
+            .collect();
+
+        // Extension de control grafico con el retraso del cuadro
+        self.out.extend_from_slice(&[0x21, 0xF9, 0x04, 0x00]);
+        self.out.extend_from_slice(&self.delay_cs.to_le_bytes());
+        self.out.extend_from_slice(&[0x00, 0x00]);
+
+        // Descriptor de imagen que cubre todo el lienzo
+        self.out.extend_from_slice(&[0x2C, 0, 0, 0, 0]);
+        self.out
+            .extend_from_slice(&(self.width as u16).to_le_bytes());
+        self.out
+            .extend_from_slice(&(self.height as u16).to_le_bytes());
+        self.out.push(0x00);
+
+        self.out.push(MIN_CODE_SIZE);
+        for block in lzw_encode(&indices).chunks(255) {
+            self.out.push(block.len() as u8);
+            self.out.extend_from_slice(block);
+        }
+        self.out.push(0x00);
+        self.frames += 1;
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        self.out.push(0x3B);
+        self.out
+    }
+}
+
+// Escribe codigos de ancho variable empezando por el bit menos significativo
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u8) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+fn lzw_encode(indices: &[u8]) -> Vec<u8> {
+    let clear = 1u16 << MIN_CODE_SIZE;
+    let end = clear + 1;
+    let mut writer = BitWriter {
+        bytes: Vec::new(),
+        buffer: 0,
+        bits: 0,
+    };
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next_code = end + 1;
+    let mut code_size = MIN_CODE_SIZE + 1;
+
+    writer.write(clear, code_size);
+    let mut iter = indices.iter();
+    let Some(first) = iter.next() else {
+        writer.write(end, code_size);
+        return writer.finish();
+    };
+    let mut prefix = *first as u16;
+    for index in iter {
+        if let Some(code) = table.get(&(prefix, *index)) {
+            prefix = *code;
+            continue;
+        }
+        writer.write(prefix, code_size);
+        if next_code == MAX_CODES {
+            // Tabla llena: se reinicia el diccionario
+            writer.write(clear, code_size);
+            table.clear();
+            next_code = end + 1;
+            code_size = MIN_CODE_SIZE + 1;
+        } else {
+            table.insert((prefix, *index), next_code);
+            if next_code == 1 << code_size {
+                code_size += 1;
+            }
+            next_code += 1;
+        }
+        prefix = *index as u16;
+    }
+    writer.write(prefix, code_size);
+    writer.write(end, code_size);
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Decodificador LZW de GIF; retorna los indices y cuantas veces se reinicio el diccionario
+    fn lzw_decode(bytes: &[u8]) -> (Vec<u8>, usize) {
+        let clear = 1u16 << MIN_CODE_SIZE;
+        let end = clear + 1;
+        let reset = || -> Vec<Vec<u8>> { (0..=end).map(|i| vec![i as u8]).collect() };
+        let (mut table, mut code_size) = (reset(), MIN_CODE_SIZE + 1);
+        let (mut out, mut clears, mut prev): (Vec<u8>, usize, Option<usize>) = (vec![], 0, None);
+        let mut bit = 0;
+        loop {
+            let mut code = 0usize;
+            for i in 0..code_size as usize {
+                let byte = bytes[(bit + i) / 8];
+                code |= (((byte >> ((bit + i) % 8)) & 1) as usize) << i;
+            }
+            bit += code_size as usize;
+            if code == clear as usize {
+                (table, code_size, prev) = (reset(), MIN_CODE_SIZE + 1, None);
+                clears += 1;
+                continue;
+            }
+            if code == end as usize {
+                return (out, clears);
+            }
+            let entry = match (table.get(code), prev) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(prev)) => {
+                    let mut entry = table[prev].clone();
+                    entry.push(table[prev][0]);
+                    entry
+                }
+                (None, None) => panic!("Codigo {} sin entrada", code),
+            };
+            if let Some(prev) = prev {
+                if table.len() < MAX_CODES as usize {
+                    let mut new_entry = table[prev].clone();
+                    new_entry.push(entry[0]);
+                    table.push(new_entry);
+                    if table.len() == 1 << code_size && code_size < MAX_CODE_SIZE {
+                        code_size += 1;
+                    }
+                }
+            }
+            out.extend_from_slice(&entry);
+            prev = Some(code);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip() {
+        for indices in [
+            vec![],
+            vec![3],
+            vec![7; 1000],
+            (0..16).cycle().take(500).collect(),
+        ] {
+            let (decoded, clears) = lzw_decode(&lzw_encode(&indices));
+            assert_eq!(decoded, indices);
+            assert_eq!(clears, 1);
+        }
+    }
+
+    #[test]
+    fn lzw_round_trip_with_dictionary_reset() {
+        // Indices pseudoaleatorios: llenan los 4096 codigos varias veces
+        let mut state = 1u32;
+        let indices: Vec<u8> = (0..100_000)
+            .map(|_| {
+                state = state.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+                (state >> 16) as u8 % 16
+            })
+            .collect();
+        let (decoded, clears) = lzw_decode(&lzw_encode(&indices));
+        assert_eq!(decoded, indices);
+        assert!(clears > 2);
+    }
+
+    #[test]
+    fn size_limit() {
+        assert!(AnimacionGif::new(MAX_SIZE, 1, FRAME_DELAY_CS).is_ok());
+        assert!(AnimacionGif::new(MAX_SIZE + 1, 1, FRAME_DELAY_CS).is_err());
+        assert!(AnimacionGif::new(1, 70_000, FRAME_DELAY_CS).is_err());
+    }
+}
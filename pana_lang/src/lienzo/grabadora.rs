@@ -0,0 +1,413 @@
+use std::{cell::RefCell, rc::Rc};
+
+use super::{Color, Lienzo, Rectangulo};
+
+// Llamada de dibujo registrada por la `Grabadora`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Comando {
+    Linea {
+        from: (f32, f32),
+        to: (f32, f32),
+        width: f32,
+        color: Color,
+    },
+    Rectangulo {
+        min: (f32, f32),
+        max: (f32, f32),
+        color: Color,
+    },
+    Rectangulos(Vec<Rectangulo>),
+    Circulo {
+        center: (f32, f32),
+        radius: f32,
+        color: Color,
+    },
+    Poligono {
+        points: Vec<(f32, f32)>,
+        color: Color,
+    },
+    Polilinea {
+        points: Vec<(f32, f32)>,
+        closed: bool,
+        width: f32,
+        color: Color,
+    },
+    Texto {
+        text: String,
+        pos: (f32, f32),
+        font_size: f32,
+        color: Color,
+    },
+}
+
+// Lienzo que no dibuja, solo guarda la lista de comandos. Los clones comparten
+// la misma lista, asi se puede entregar uno al `Evaluator` y leer desde el otro.
+#[derive(Clone, Default, Debug)]
+pub struct Grabadora {
+    comandos: Rc<RefCell<Vec<Comando>>>,
+}
+
+impl Grabadora {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn comandos(&self) -> Vec<Comando> {
+        self.comandos.borrow().clone()
+    }
+
+    pub fn limpiar(&self) {
+        self.comandos.borrow_mut().clear();
+    }
+
+    // Repite los comandos grabados sobre otro lienzo
+    pub fn replay(&self, lienzo: &mut dyn Lienzo) {
+        for comando in self.comandos.borrow().iter() {
+            match comando {
+                Comando::Linea {
+                    from,
+                    to,
+                    width,
+                    color,
+                } => lienzo.line(*from, *to, *width, *color),
+                Comando::Rectangulo { min, max, color } => lienzo.rect_filled(*min, *max, *color),
+                Comando::Rectangulos(rects) => lienzo.rects_filled(rects),
+                Comando::Circulo {
+                    center,
+                    radius,
+                    color,
+                } => lienzo.circle_filled(*center, *radius, *color),
+                Comando::Poligono { points, color } => lienzo.polygon_filled(points, *color),
+                Comando::Polilinea {
+                    points,
+                    closed,
+                    width,
+                    color,
+                } => lienzo.polyline(points, *closed, *width, *color),
+                Comando::Texto {
+                    text,
+                    pos,
+                    font_size,
+                    color,
+                } => lienzo.text(text, *pos, *font_size, *color),
+            }
+        }
+    }
+
+    fn push(&mut self, comando: Comando) {
+        self.comandos.borrow_mut().push(comando);
+    }
+}
+
+impl Lienzo for Grabadora {
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        self.push(Comando::Linea {
+            from,
+            to,
+            width,
+            color,
+        });
+    }
+
+    fn rect_filled(&mut self, min: (f32, f32), max: (f32, f32), color: Color) {
+        self.push(Comando::Rectangulo { min, max, color });
+    }
+
+    fn circle_filled(&mut self, center: (f32, f32), radius: f32, color: Color) {
+        self.push(Comando::Circulo {
+            center,
+            radius,
+            color,
+        });
+    }
+
+    fn polygon_filled(&mut self, points: &[(f32, f32)], color: Color) {
+        self.push(Comando::Poligono {
+            points: points.to_vec(),
+            color,
+        });
+    }
+
+    fn polyline(&mut self, points: &[(f32, f32)], closed: bool, width: f32, color: Color) {
+        self.push(Comando::Polilinea {
+            points: points.to_vec(),
+            closed,
+            width,
+            color,
+        });
+    }
+
+    fn text(&mut self, text: &str, pos: (f32, f32), font_size: f32, color: Color) {
+        self.push(Comando::Texto {
+            text: text.to_owned(),
+            pos,
+            font_size,
+            color,
+        });
+    }
+
+    fn rects_filled(&mut self, rects: &[Rectangulo]) {
+        self.push(Comando::Rectangulos(rects.to_vec()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+    use crate::lienzo::{
+        formas::Estilo,
+        transformacion::{Transformacion, Transformado},
+    };
+
+    const RED: Color = Color::new(255, 0, 0, 255);
+    const BLUE: Color = Color::new(0, 0, 255, 255);
+
+    fn record(draw: impl FnOnce(&mut dyn Lienzo)) -> Vec<Comando> {
+        let grabadora = Grabadora::new();
+        draw(&mut grabadora.clone());
+        grabadora.comandos()
+    }
+
+    fn transformed(transform: Transformacion, draw: impl FnOnce(&mut dyn Lienzo)) -> Vec<Comando> {
+        record(|lienzo| {
+            draw(&mut Transformado { lienzo, transform });
+        })
+    }
+
+    fn close(a: (f32, f32), b: (f32, f32)) -> bool {
+        (a.0 - b.0).abs() < 1e-4 && (a.1 - b.1).abs() < 1e-4
+    }
+
+    #[test]
+    fn records_every_call() {
+        let rect = Rectangulo {
+            min: (0.0, 0.0),
+            max: (1.0, 1.0),
+            color: RED,
+        };
+        let comandos = record(|lienzo| {
+            lienzo.line((0.0, 0.0), (10.0, 5.0), 2.0, RED);
+            lienzo.rect_filled((1.0, 2.0), (3.0, 4.0), BLUE);
+            lienzo.circle_filled((5.0, 5.0), 3.0, RED);
+            lienzo.polygon_filled(&[(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)], BLUE);
+            lienzo.polyline(&[(0.0, 0.0), (4.0, 4.0)], false, 1.0, RED);
+            lienzo.text("hola", (2.0, 3.0), 16.0, BLUE);
+            lienzo.rects_filled(&[rect]);
+        });
+        assert_eq!(
+            comandos,
+            vec![
+                Comando::Linea {
+                    from: (0.0, 0.0),
+                    to: (10.0, 5.0),
+                    width: 2.0,
+                    color: RED
+                },
+                Comando::Rectangulo {
+                    min: (1.0, 2.0),
+                    max: (3.0, 4.0),
+                    color: BLUE
+                },
+                Comando::Circulo {
+                    center: (5.0, 5.0),
+                    radius: 3.0,
+                    color: RED
+                },
+                Comando::Poligono {
+                    points: vec![(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)],
+                    color: BLUE
+                },
+                Comando::Polilinea {
+                    points: vec![(0.0, 0.0), (4.0, 4.0)],
+                    closed: false,
+                    width: 1.0,
+                    color: RED
+                },
+                Comando::Texto {
+                    text: "hola".to_owned(),
+                    pos: (2.0, 3.0),
+                    font_size: 16.0,
+                    color: BLUE
+                },
+                Comando::Rectangulos(vec![rect]),
+            ]
+        );
+    }
+
+    #[test]
+    fn replay_and_clear() {
+        let grabadora = Grabadora::new();
+        grabadora.clone().line((0.0, 0.0), (1.0, 1.0), 1.0, RED);
+        grabadora.clone().circle_filled((2.0, 2.0), 1.0, BLUE);
+        let copy = Grabadora::new();
+        grabadora.replay(&mut copy.clone());
+        assert_eq!(copy.comandos(), grabadora.comandos());
+        grabadora.limpiar();
+        assert!(grabadora.comandos().is_empty());
+        assert_eq!(copy.comandos().len(), 2);
+    }
+
+    #[test]
+    fn shapes_with_fill_and_stroke() {
+        let estilo = Estilo {
+            fill: Some(RED),
+            stroke: Some((BLUE, 2.0)),
+            radius: 0.0,
+        };
+        let comandos =
+            record(|lienzo| estilo.draw_polygon(lienzo, &[(0.0, 0.0), (4.0, 0.0), (0.0, 4.0)]));
+        assert!(matches!(&comandos[..], [
+            Comando::Poligono { color: RED, .. },
+            Comando::Polilinea { closed: true, width, color: BLUE, .. },
+        ] if *width == 2.0));
+
+        let comandos = record(|lienzo| estilo.draw_rect(lienzo, (0.0, 0.0), (4.0, 2.0)));
+        assert!(matches!(&comandos[..], [
+            Comando::Rectangulo { color: RED, .. },
+            Comando::Polilinea { points, closed: true, .. },
+        ] if points.len() == 4));
+    }
+
+    #[test]
+    fn rounded_rect_is_a_polygon() {
+        let estilo = Estilo {
+            radius: 1.0,
+            ..Estilo::default()
+        };
+        let comandos = record(|lienzo| estilo.draw_rect(lienzo, (0.0, 0.0), (10.0, 10.0)));
+        assert!(matches!(&comandos[..], [Comando::Poligono { points, .. }] if points.len() > 4));
+    }
+
+    #[test]
+    fn ellipse_and_circle() {
+        let estilo = Estilo::default();
+        let comandos = record(|lienzo| estilo.draw_ellipse(lienzo, (5.0, 5.0), (3.0, 3.0)));
+        assert!(matches!(&comandos[..], [Comando::Circulo { radius, .. }] if *radius == 3.0));
+
+        let comandos = record(|lienzo| estilo.draw_ellipse(lienzo, (5.0, 5.0), (6.0, 3.0)));
+        let [Comando::Poligono { points, .. }] = &comandos[..] else {
+            panic!("Se esperaba un poligono: {:?}", comandos);
+        };
+        assert!(close(points[0], (11.0, 5.0)));
+        assert!(points.iter().all(|p| {
+            let (x, y) = ((p.0 - 5.0) / 6.0, (p.1 - 5.0) / 3.0);
+            (x * x + y * y - 1.0).abs() < 1e-4
+        }));
+    }
+
+    #[test]
+    fn arc_fill_is_a_slice() {
+        let estilo = Estilo {
+            fill: Some(RED),
+            stroke: Some((BLUE, 1.0)),
+            radius: 0.0,
+        };
+        let comandos = record(|lienzo| estilo.draw_arc(lienzo, (0.0, 0.0), 10.0, 0.0, PI / 2.0));
+        let [Comando::Poligono { points: slice, .. }, Comando::Polilinea {
+            points: curve,
+            closed: false,
+            ..
+        }] = &comandos[..]
+        else {
+            panic!("Se esperaba un relleno y un borde: {:?}", comandos);
+        };
+        assert_eq!(slice[0], (0.0, 0.0));
+        assert_eq!(slice[1..], curve[..]);
+        assert!(close(curve[0], (10.0, 0.0)));
+        assert!(close(*curve.last().unwrap(), (0.0, 10.0)));
+    }
+
+    #[test]
+    fn transform_moves_and_scales() {
+        let transform = Transformacion::IDENTITY
+            .translate(10.0, 20.0)
+            .scale(2.0, 2.0);
+        let comandos = transformed(transform, |lienzo| {
+            lienzo.line((1.0, 1.0), (2.0, 1.0), 1.0, RED);
+            lienzo.rect_filled((0.0, 0.0), (1.0, 1.0), RED);
+            lienzo.circle_filled((0.0, 0.0), 3.0, RED);
+            lienzo.text("a", (1.0, 0.0), 8.0, RED);
+        });
+        assert_eq!(
+            comandos,
+            vec![
+                Comando::Linea {
+                    from: (12.0, 22.0),
+                    to: (14.0, 22.0),
+                    width: 2.0,
+                    color: RED
+                },
+                Comando::Rectangulo {
+                    min: (10.0, 20.0),
+                    max: (12.0, 22.0),
+                    color: RED
+                },
+                Comando::Circulo {
+                    center: (10.0, 20.0),
+                    radius: 6.0,
+                    color: RED
+                },
+                Comando::Texto {
+                    text: "a".to_owned(),
+                    pos: (12.0, 20.0),
+                    font_size: 16.0,
+                    color: RED
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rotation_turns_rects_into_polygons() {
+        let transform = Transformacion::IDENTITY.rotate(PI / 2.0);
+        let comandos = transformed(transform, |lienzo| {
+            lienzo.rect_filled((0.0, 0.0), (2.0, 1.0), RED);
+        });
+        let [Comando::Poligono { points, .. }] = &comandos[..] else {
+            panic!("Se esperaba un poligono: {:?}", comandos);
+        };
+        let expected = [(0.0, 0.0), (0.0, 2.0), (-1.0, 2.0), (-1.0, 0.0)];
+        assert!(points.iter().zip(expected).all(|(p, e)| close(*p, e)));
+    }
+
+    #[test]
+    fn uneven_scale_turns_circles_into_polygons() {
+        let transform = Transformacion::IDENTITY.scale(2.0, 1.0);
+        let comandos = transformed(transform, |lienzo| {
+            Estilo::default().draw_ellipse(lienzo, (0.0, 0.0), (1.0, 1.0));
+        });
+        let [Comando::Poligono { points, .. }] = &comandos[..] else {
+            panic!("Se esperaba un poligono: {:?}", comandos);
+        };
+        assert!(close(points[0], (2.0, 0.0)));
+    }
+
+    #[test]
+    fn transformed_rects_batch() {
+        let rect = Rectangulo {
+            min: (0.0, 0.0),
+            max: (1.0, 1.0),
+            color: RED,
+        };
+        let comandos = transformed(Transformacion::IDENTITY.translate(5.0, 5.0), |lienzo| {
+            lienzo.rects_filled(&[rect]);
+        });
+        assert_eq!(
+            comandos,
+            vec![Comando::Rectangulos(vec![Rectangulo {
+                min: (5.0, 5.0),
+                max: (6.0, 6.0),
+                color: RED
+            }])]
+        );
+        let comandos = transformed(Transformacion::IDENTITY.rotate(0.5), |lienzo| {
+            lienzo.rects_filled(&[rect, rect]);
+        });
+        assert!(comandos
+            .iter()
+            .all(|comando| matches!(comando, Comando::Poligono { .. })));
+        assert_eq!(comandos.len(), 2);
+    }
+}
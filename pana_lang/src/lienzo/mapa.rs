@@ -0,0 +1,112 @@
+use std::{fmt::Display, ops::Range};
+
+use super::{Color, Rectangulo};
+
+// Cuadricula de tiles de `crear_mapa`. Cada tile es un numero entero y el 0
+// es el vacio: no choca con nada. Las posiciones en pixeles son relativas a la
+// esquina superior izquierda del mapa.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mapa {
+    pub columns: usize,
+    pub rows: usize,
+    // Lado de cada tile en pixeles
+    pub size: f32,
+    tiles: Vec<i64>,
+}
+
+impl Mapa {
+    pub fn new(columns: usize, rows: usize, size: f32) -> Self {
+        Self {
+            columns,
+            rows,
+            size,
+            tiles: vec![0; columns * rows],
+        }
+    }
+
+    fn index(&self, column: i64, row: i64) -> Option<usize> {
+        let column = usize::try_from(column).ok().filter(|c| *c < self.columns)?;
+        let row = usize::try_from(row).ok().filter(|r| *r < self.rows)?;
+        Some(row * self.columns + column)
+    }
+
+    // `None` fuera del mapa
+    pub fn get(&self, column: i64, row: i64) -> Option<i64> {
+        self.index(column, row).map(|i| self.tiles[i])
+    }
+
+    // Retorna `false` si la celda esta fuera del mapa
+    pub fn set(&mut self, column: i64, row: i64, tile: i64) -> bool {
+        match self.index(column, row) {
+            Some(i) => {
+                self.tiles[i] = tile;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Tile en la posicion `x, y`, `None` fuera del mapa
+    pub fn tile_at(&self, (x, y): (f32, f32)) -> Option<i64> {
+        let column = (x / self.size).floor();
+        let row = (y / self.size).floor();
+        if !column.is_finite() || !row.is_finite() {
+            return None;
+        }
+        self.get(column as i64, row as i64)
+    }
+
+    // Celdas del mapa que se superponen con el rango `min..max` de un eje
+    fn cells(&self, min: f32, max: f32, count: usize) -> Range<usize> {
+        let start = (min / self.size).floor().clamp(0.0, count as f32) as usize;
+        let end = (max / self.size).ceil().clamp(0.0, count as f32) as usize;
+        start..end.max(start)
+    }
+
+    // Si el rectangulo se superpone con algun tile distinto de 0. Igual que en
+    // `colisionan_rectangulos`, tocar el borde de un tile no es chocar.
+    pub fn collides(&self, min: (f32, f32), max: (f32, f32)) -> bool {
+        let columns = self.cells(min.0, max.0, self.columns);
+        self.cells(min.1, max.1, self.rows).any(|row| {
+            self.tiles[row * self.columns..(row + 1) * self.columns][columns.clone()]
+                .iter()
+                .any(|tile| *tile != 0)
+        })
+    }
+
+    // Rectangulos de los tiles que tienen color, con el mapa en `origin`. Solo
+    // se incluyen los tiles que caen dentro del rectangulo `visible`.
+    pub fn rects(
+        &self,
+        origin: (f32, f32),
+        (min, max): ((f32, f32), (f32, f32)),
+        color: impl Fn(i64) -> Option<Color>,
+    ) -> Vec<Rectangulo> {
+        let columns = self.cells(min.0 - origin.0, max.0 - origin.0, self.columns);
+        let rows = self.cells(min.1 - origin.1, max.1 - origin.1, self.rows);
+        let mut rects = Vec::new();
+        for row in rows {
+            for column in columns.clone() {
+                let Some(color) = color(self.tiles[row * self.columns + column]) else {
+                    continue;
+                };
+                let min = (
+                    origin.0 + column as f32 * self.size,
+                    origin.1 + row as f32 * self.size,
+                );
+                rects.push(Rectangulo {
+                    min,
+                    max: (min.0 + self.size, min.1 + self.size),
+                    color,
+                });
+            }
+        }
+        rects
+    }
+}
+
+impl Display for Mapa {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "mapa({}, {}, {})", self.columns, self.rows, self.size)
+    }
+}
@@ -0,0 +1,149 @@
+pub mod camara;
+pub mod formas;
+pub mod fuente;
+pub mod gif;
+pub mod grabadora;
+pub mod mapa;
+pub mod paleta;
+pub mod particulas;
+pub mod png;
+pub mod rasterizador;
+pub mod texto;
+pub mod tortuga;
+pub mod transformacion;
+
+// Color RGBA sin premultiplicar
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const fn new(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    // 0xRRGGBBAA
+    pub fn from_rgba_u32(n: u32) -> Self {
+        Self::new((n >> 24) as u8, (n >> 16) as u8, (n >> 8) as u8, n as u8)
+    }
+
+    // Mezcla cada componente, con `t` de 0 (`self`) a 1 (`other`)
+    pub fn mix(self, other: Self, t: f64) -> Self {
+        let mix = |a: u8, b: u8| {
+            (a as f64 + (b as f64 - a as f64) * t)
+                .round()
+                .clamp(0.0, 255.0) as u8
+        };
+        Self::new(
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+            mix(self.a, other.a),
+        )
+    }
+}
+
+// Rectangulo relleno de `Lienzo::rects_filled`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rectangulo {
+    pub min: (f32, f32),
+    pub max: (f32, f32),
+    pub color: Color,
+}
+
+// Superficie de dibujo. Las posiciones son relativas a la esquina superior
+// izquierda del lienzo, cada implementacion las traslada a su propio espacio.
+pub trait Lienzo {
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color);
+
+    fn rect_filled(&mut self, min: (f32, f32), max: (f32, f32), color: Color);
+
+    fn circle_filled(&mut self, center: (f32, f32), radius: f32, color: Color);
+
+    fn polygon_filled(&mut self, points: &[(f32, f32)], color: Color);
+
+    // Lineas unidas por los puntos, con `closed` se une el ultimo con el primero
+    fn polyline(&mut self, points: &[(f32, f32)], closed: bool, width: f32, color: Color);
+
+    fn text(&mut self, text: &str, pos: (f32, f32), font_size: f32, color: Color);
+
+    // Muchos rectangulos en una sola llamada, como los tiles de un mapa. Los
+    // lienzos que pueden los dibujan juntos en lugar de uno por uno.
+    fn rects_filled(&mut self, rects: &[Rectangulo]) {
+        for rect in rects {
+            self.rect_filled(rect.min, rect.max, rect.color);
+        }
+    }
+}
+
+impl<L: Lienzo + ?Sized> Lienzo for Box<L> {
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        (**self).line(from, to, width, color);
+    }
+
+    fn rect_filled(&mut self, min: (f32, f32), max: (f32, f32), color: Color) {
+        (**self).rect_filled(min, max, color);
+    }
+
+    fn circle_filled(&mut self, center: (f32, f32), radius: f32, color: Color) {
+        (**self).circle_filled(center, radius, color);
+    }
+
+    fn polygon_filled(&mut self, points: &[(f32, f32)], color: Color) {
+        (**self).polygon_filled(points, color);
+    }
+
+    fn polyline(&mut self, points: &[(f32, f32)], closed: bool, width: f32, color: Color) {
+        (**self).polyline(points, closed, width, color);
+    }
+
+    fn text(&mut self, text: &str, pos: (f32, f32), font_size: f32, color: Color) {
+        (**self).text(text, pos, font_size, color);
+    }
+
+    fn rects_filled(&mut self, rects: &[Rectangulo]) {
+        (**self).rects_filled(rects);
+    }
+}
+
+// Dibuja en dos lienzos a la vez, por ejemplo en pantalla y en una captura
+impl<A: Lienzo, B: Lienzo> Lienzo for (A, B) {
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        self.0.line(from, to, width, color);
+        self.1.line(from, to, width, color);
+    }
+
+    fn rect_filled(&mut self, min: (f32, f32), max: (f32, f32), color: Color) {
+        self.0.rect_filled(min, max, color);
+        self.1.rect_filled(min, max, color);
+    }
+
+    fn circle_filled(&mut self, center: (f32, f32), radius: f32, color: Color) {
+        self.0.circle_filled(center, radius, color);
+        self.1.circle_filled(center, radius, color);
+    }
+
+    fn polygon_filled(&mut self, points: &[(f32, f32)], color: Color) {
+        self.0.polygon_filled(points, color);
+        self.1.polygon_filled(points, color);
+    }
+
+    fn polyline(&mut self, points: &[(f32, f32)], closed: bool, width: f32, color: Color) {
+        self.0.polyline(points, closed, width, color);
+        self.1.polyline(points, closed, width, color);
+    }
+
+    fn text(&mut self, text: &str, pos: (f32, f32), font_size: f32, color: Color) {
+        self.0.text(text, pos, font_size, color);
+        self.1.text(text, pos, font_size, color);
+    }
+
+    fn rects_filled(&mut self, rects: &[Rectangulo]) {
+        self.0.rects_filled(rects);
+        self.1.rects_filled(rects);
+    }
+}
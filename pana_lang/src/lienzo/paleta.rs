@@ -0,0 +1,42 @@
+use super::Color;
+
+// Paleta de 16 colores de la Commodore 64 (valores de Pepto)
+pub const C64: [Color; 16] = [
+    Color::new(0x00, 0x00, 0x00, 0xFF), // 0 negro
+    Color::new(0xFF, 0xFF, 0xFF, 0xFF), // 1 blanco
+    Color::new(0x68, 0x37, 0x2B, 0xFF), // 2 rojo
+    Color::new(0x70, 0xA4, 0xB2, 0xFF), // 3 cian
+    Color::new(0x6F, 0x3D, 0x86, 0xFF), // 4 morado
+    Color::new(0x58, 0x8D, 0x43, 0xFF), // 5 verde
+    Color::new(0x35, 0x28, 0x79, 0xFF), // 6 azul
+    Color::new(0xB8, 0xC7, 0x6F, 0xFF), // 7 amarillo
+    Color::new(0x6F, 0x4F, 0x25, 0xFF), // 8 naranja
+    Color::new(0x43, 0x39, 0x00, 0xFF), // 9 cafe
+    Color::new(0x9A, 0x67, 0x59, 0xFF), // 10 rojo claro
+    Color::new(0x44, 0x44, 0x44, 0xFF), // 11 gris oscuro
+    Color::new(0x6C, 0x6C, 0x6C, 0xFF), // 12 gris
+    Color::new(0x9A, 0xD2, 0x84, 0xFF), // 13 verde claro
+    Color::new(0x6C, 0x5E, 0xB5, 0xFF), // 14 azul claro
+    Color::new(0x95, 0x95, 0x95, 0xFF), // 15 gris claro
+];
+
+// Indice del color de la paleta mas cercano. La transparencia se mezcla con negro.
+pub fn nearest(r: u8, g: u8, b: u8, a: u8) -> u8 {
+    let alpha = a as i32;
+    let (r, g, b) = (
+        r as i32 * alpha / 255,
+        g as i32 * alpha / 255,
+        b as i32 * alpha / 255,
+    );
+    let mut best = (0, i32::MAX);
+    for (i, color) in C64.iter().enumerate() {
+        let dr = color.r as i32 - r;
+        let dg = color.g as i32 - g;
+        let db = color.b as i32 - b;
+        let dist = dr * dr + dg * dg + db * db;
+        if dist < best.1 {
+            best = (i as u8, dist);
+        }
+    }
+    best.0
+}
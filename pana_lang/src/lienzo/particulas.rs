@@ -0,0 +1,170 @@
+use std::fmt::Display;
+
+use crate::buildins::rng::Rng;
+
+use super::{Color, Rectangulo};
+
+// Evita que un emisor con un ritmo enorme agote la memoria
+pub const MAX_PARTICLES: usize = 10_000;
+
+#[derive(Clone, Debug, PartialEq)]
+struct Particula {
+    pos: (f32, f32),
+    vel: (f32, f32),
+    // Segundos desde que nacio
+    age: f32,
+}
+
+// Opciones de `crear_emisor` y `cambiar_emisor`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Opciones {
+    // Particulas por segundo, 0 solo emite con `emitir`
+    pub rate: f32,
+    // Segundos que vive cada particula
+    pub lifetime: f32,
+    // Pixeles por segundo. Cada particula sale con entre la mitad y el total.
+    pub speed: f32,
+    // Radianes, en el sentido del reloj como `rotar`. Las particulas salen
+    // hasta `spread / 2` hacia cada lado de la direccion.
+    pub direction: f32,
+    pub spread: f32,
+    // Pixeles por segundo al cuadrado, hacia abajo
+    pub gravity: f32,
+    // Colores que recorre cada particula durante su vida
+    pub colors: Vec<Color>,
+    // Lado del cuadrado de cada particula
+    pub size: f32,
+}
+
+impl Default for Opciones {
+    fn default() -> Self {
+        Self {
+            rate: 30.0,
+            lifetime: 1.0,
+            speed: 60.0,
+            direction: -std::f32::consts::FRAC_PI_2,
+            spread: std::f32::consts::FRAC_PI_4,
+            gravity: 0.0,
+            colors: vec![Color::new(0xFF, 0xFF, 0xFF, 0xFF)],
+            size: 3.0,
+        }
+    }
+}
+
+// Emisor de particulas de `crear_emisor`. La simulacion corre en Rust, asi
+// miles de particulas no pasan por el evaluador.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Emisor {
+    pub pos: (f32, f32),
+    pub options: Opciones,
+    particles: Vec<Particula>,
+    // Parte de la siguiente particula que ya se acumulo con `rate`
+    pending: f32,
+    rng: Rng,
+}
+
+impl Emisor {
+    // Cada emisor usa otra semilla, asi dos emisores no salen iguales
+    pub fn new(pos: (f32, f32), options: Opciones, seed: u64) -> Self {
+        Self {
+            pos,
+            options,
+            particles: Vec::new(),
+            pending: 0.0,
+            rng: Rng::new(seed),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.particles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.particles.is_empty()
+    }
+
+    fn random(&mut self) -> f32 {
+        self.rng.rand_f64() as f32
+    }
+
+    // Lanza `count` particulas desde la posicion del emisor
+    pub fn emit(&mut self, count: usize) {
+        let count = count.min(MAX_PARTICLES - self.particles.len());
+        for _ in 0..count {
+            let angle = self.options.direction + (self.random() - 0.5) * self.options.spread;
+            let speed = self.options.speed * (0.5 + self.random() * 0.5);
+            self.particles.push(Particula {
+                pos: self.pos,
+                vel: (angle.cos() * speed, angle.sin() * speed),
+                age: 0.0,
+            });
+        }
+    }
+
+    // Avanza la simulacion `seconds` segundos
+    pub fn update(&mut self, seconds: f32) {
+        let seconds = seconds.max(0.0);
+        let Opciones {
+            lifetime, gravity, ..
+        } = self.options;
+        self.particles.retain_mut(|particle| {
+            particle.age += seconds;
+            particle.vel.1 += gravity * seconds;
+            particle.pos.0 += particle.vel.0 * seconds;
+            particle.pos.1 += particle.vel.1 * seconds;
+            particle.age < lifetime
+        });
+        self.pending += self.options.rate.max(0.0) * seconds;
+        let count = self.pending.floor();
+        self.pending -= count;
+        self.emit(count as usize);
+    }
+
+    // Color en la parte `t` de la vida, de 0 a 1
+    fn color(&self, t: f32) -> Option<Color> {
+        let colors = &self.options.colors;
+        let last = colors.len().checked_sub(1)?;
+        let pos = t.clamp(0.0, 1.0) * last as f32;
+        let i = (pos.floor() as usize).min(last);
+        let next = (i + 1).min(last);
+        Some(colors[i].mix(colors[next], (pos - i as f32) as f64))
+    }
+
+    pub fn rects(&self) -> Vec<Rectangulo> {
+        let half = self.options.size / 2.0;
+        self.particles
+            .iter()
+            .filter_map(|particle| {
+                let color = self.color(particle.age / self.options.lifetime)?;
+                Some(Rectangulo {
+                    min: (particle.pos.0 - half, particle.pos.1 - half),
+                    max: (particle.pos.0 + half, particle.pos.1 + half),
+                    color,
+                })
+            })
+            .collect()
+    }
+}
+
+impl Display for Emisor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "emisor({}, {})", self.pos.0, self.pos.1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emitted(seed: u64) -> Emisor {
+        let mut emisor = Emisor::new((10.0, 20.0), Opciones::default(), seed);
+        emisor.emit(20);
+        emisor
+    }
+
+    #[test]
+    fn same_seed_same_particles() {
+        assert_eq!(emitted(0), emitted(0));
+        assert_ne!(emitted(0), emitted(1));
+    }
+}
@@ -0,0 +1,158 @@
+// Codificador PNG minimo: RGBA de 8 bits, sin filtros y con bloques deflate
+// sin compresion. Las imagenes del lienzo son pequenas, asi que no vale la
+// pena depender de una libreria de compresion.
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+const MAX_STORED_BLOCK: usize = 0xFFFF;
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in bytes.chunks(5552) {
+        for byte in chunk {
+            a += *byte as u32;
+            b += a;
+        }
+        a %= 65521;
+        b %= 65521;
+    }
+    (b << 16) | a
+}
+
+fn write_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend_from_slice(kind);
+    out.extend_from_slice(data);
+    let crc = crc32(&out[start..]);
+    out.extend_from_slice(&crc.to_be_bytes());
+}
+
+// Envuelve los datos en un flujo zlib con bloques deflate sin compresion
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(if blocks.peek().is_none() { 1 } else { 0 });
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+// `rgba` debe tener `width * height * 4` bytes
+pub fn encode_png(width: usize, height: usize, rgba: &[u8]) -> Vec<u8> {
+    let mut raw = Vec::with_capacity((width * 4 + 1) * height);
+    for row in rgba.chunks(width * 4).take(height) {
+        // Filtro 0: ninguno
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut header = Vec::with_capacity(13);
+    header.extend_from_slice(&(width as u32).to_be_bytes());
+    header.extend_from_slice(&(height as u32).to_be_bytes());
+    // 8 bits por canal, tipo de color 6 (RGBA), compresion, filtro y entrelazado por defecto
+    header.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut out = SIGNATURE.to_vec();
+    write_chunk(&mut out, b"IHDR", &header);
+    write_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+    write_chunk(&mut out, b"IEND", &[]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn be_u32(bytes: &[u8]) -> u32 {
+        u32::from_be_bytes(bytes[..4].try_into().unwrap())
+    }
+
+    // Lee el PNG de vuelta: revisa los CRC y junta los bloques sin compresion
+    fn decode(png: &[u8]) -> (usize, usize, Vec<u8>) {
+        assert_eq!(png[..8], SIGNATURE);
+        let (mut pos, mut size, mut idat) = (8, (0, 0), Vec::new());
+        while pos < png.len() {
+            let len = be_u32(&png[pos..]) as usize;
+            let body = &png[pos + 4..pos + 8 + len];
+            assert_eq!(be_u32(&png[pos + 8 + len..]), crc32(body));
+            match &body[..4] {
+                b"IHDR" => size = (be_u32(&body[4..]), be_u32(&body[8..])),
+                b"IDAT" => idat.extend_from_slice(&body[4..]),
+                _ => {}
+            }
+            pos += len + 12;
+        }
+
+        assert_eq!(idat[..2], [0x78, 0x01]);
+        let (mut i, mut raw) = (2, Vec::new());
+        loop {
+            let last = idat[i] == 1;
+            let len = u16::from_le_bytes([idat[i + 1], idat[i + 2]]);
+            let nlen = u16::from_le_bytes([idat[i + 3], idat[i + 4]]);
+            assert_eq!(len, !nlen);
+            raw.extend_from_slice(&idat[i + 5..i + 5 + len as usize]);
+            i += 5 + len as usize;
+            if last {
+                break;
+            }
+        }
+        assert_eq!(be_u32(&idat[i..]), adler32(&raw));
+
+        let (width, height) = (size.0 as usize, size.1 as usize);
+        let mut rgba = Vec::new();
+        for row in raw.chunks(width * 4 + 1) {
+            assert_eq!(row[0], 0);
+            rgba.extend_from_slice(&row[1..]);
+        }
+        assert_eq!(rgba.len(), width * height * 4);
+        (width, height, rgba)
+    }
+
+    #[test]
+    fn crc32_known_answers() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(crc32(b"IEND"), 0xAE42_6082);
+    }
+
+    #[test]
+    fn adler32_known_answers() {
+        assert_eq!(adler32(b""), 1);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+        // Mas largo que un bloque de 5552 bytes
+        assert_eq!(adler32(&[0xFF; 100_000]), 0x149A_302C);
+    }
+
+    #[test]
+    fn small_image_round_trip() {
+        let rgba: Vec<u8> = (0..3 * 2 * 4).map(|i| i as u8 * 10).collect();
+        assert_eq!(decode(&encode_png(3, 2, &rgba)), (3, 2, rgba));
+    }
+
+    #[test]
+    fn image_with_several_blocks_round_trip() {
+        // 80100 bytes sin comprimir, necesita dos bloques
+        let rgba: Vec<u8> = (0..200 * 100 * 4).map(|i| (i % 251) as u8).collect();
+        assert_eq!(decode(&encode_png(200, 100, &rgba)), (200, 100, rgba));
+    }
+}
@@ -0,0 +1,323 @@
+use std::{cell::RefCell, rc::Rc};
+
+use super::{
+    fuente::{glyph, GLYPH_SIZE},
+    grabadora::Grabadora,
+    png::encode_png,
+    Color, Lienzo,
+};
+
+pub const BACKGROUND: Color = Color::new(0, 0, 0, 255);
+
+// Lienzo que dibuja en la CPU sobre un bufer RGBA. Los pixeles tienen su centro
+// en coordenadas enteras. Como la `Grabadora`, los clones comparten el bufer.
+#[derive(Clone, Debug)]
+pub struct Rasterizador {
+    width: usize,
+    height: usize,
+    pixels: Rc<RefCell<Vec<u8>>>,
+}
+
+impl Rasterizador {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_color(width, height, BACKGROUND)
+    }
+
+    pub fn with_color(width: usize, height: usize, color: Color) -> Self {
+        let rasterizador = Self {
+            width,
+            height,
+            pixels: Rc::new(RefCell::new(vec![0; width * height * 4])),
+        };
+        rasterizador.clear(color);
+        rasterizador
+    }
+
+    // Copia independiente, que no comparte el bufer
+    pub fn snapshot(&self) -> Self {
+        Self {
+            width: self.width,
+            height: self.height,
+            pixels: Rc::new(RefCell::new(self.pixels())),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn clear(&self, color: Color) {
+        for pixel in self.pixels.borrow_mut().chunks_mut(4) {
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    // Copia de los pixeles en formato RGBA, fila por fila
+    pub fn pixels(&self) -> Vec<u8> {
+        self.pixels.borrow().clone()
+    }
+
+    pub fn get_pixel(&self, x: usize, y: usize) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y * self.width + x) * 4;
+        let pixels = self.pixels.borrow();
+        Some(Color::new(
+            pixels[i],
+            pixels[i + 1],
+            pixels[i + 2],
+            pixels[i + 3],
+        ))
+    }
+
+    // Reemplaza el pixel sin mezclar. Los pixeles fuera del bufer se ignoran.
+    pub fn set_pixel(&self, x: i64, y: i64, color: Color) {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return;
+        }
+        let i = (y as usize * self.width + x as usize) * 4;
+        self.pixels.borrow_mut()[i..i + 4].copy_from_slice(&[color.r, color.g, color.b, color.a]);
+    }
+
+    // Mezcla otro bufer encima, alineado en la esquina superior izquierda
+    pub fn draw_over(&self, other: &Rasterizador) {
+        let src = other.pixels.borrow();
+        let mut pixels = self.pixels.borrow_mut();
+        for y in 0..self.height.min(other.height) {
+            for x in 0..self.width.min(other.width) {
+                let i = (y * other.width + x) * 4;
+                let color = Color::new(src[i], src[i + 1], src[i + 2], src[i + 3]);
+                if color.a > 0 {
+                    Self::blend(&mut pixels, self.width, x as i64, y as i64, color);
+                }
+            }
+        }
+    }
+
+    pub fn to_png(&self) -> Vec<u8> {
+        encode_png(self.width, self.height, &self.pixels.borrow())
+    }
+
+    // Mezcla el color sobre el pixel con el operador "sobre"
+    fn blend(pixels: &mut [u8], width: usize, x: i64, y: i64, color: Color) {
+        let i = (y as usize * width + x as usize) * 4;
+        let dst = &mut pixels[i..i + 4];
+        if color.a == 255 {
+            dst.copy_from_slice(&[color.r, color.g, color.b, 255]);
+            return;
+        }
+        let src_a = color.a as f32 / 255.0;
+        let dst_a = dst[3] as f32 / 255.0;
+        let out_a = src_a + dst_a * (1.0 - src_a);
+        if out_a <= 0.0 {
+            return;
+        }
+        for (channel, src) in dst.iter_mut().zip([color.r, color.g, color.b]) {
+            let mixed = (src as f32 * src_a + *channel as f32 * dst_a * (1.0 - src_a)) / out_a;
+            *channel = mixed.round() as u8;
+        }
+        dst[3] = (out_a * 255.0).round() as u8;
+    }
+
+    // Recorre los pixeles dentro del rectangulo que cumplen la condicion
+    fn fill_where(
+        &mut self,
+        min: (f32, f32),
+        max: (f32, f32),
+        color: Color,
+        inside: impl Fn(f32, f32) -> bool,
+    ) {
+        let x0 = (min.0.floor() as i64).max(0);
+        let y0 = (min.1.floor() as i64).max(0);
+        let x1 = (max.0.ceil() as i64).min(self.width as i64 - 1);
+        let y1 = (max.1.ceil() as i64).min(self.height as i64 - 1);
+        let mut pixels = self.pixels.borrow_mut();
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                if inside(x as f32, y as f32) {
+                    Self::blend(&mut pixels, self.width, x, y, color);
+                }
+            }
+        }
+    }
+}
+
+// Imagen de un cuadro en el modo ventana: los pixeles de `poner_pixel` quedan
+// debajo de las figuras que se grabaron durante el cuadro
+pub fn compose_frame(
+    width: usize,
+    height: usize,
+    pixels: Option<&Rasterizador>,
+    grabadora: &Grabadora,
+) -> Rasterizador {
+    let mut frame = Rasterizador::new(width, height);
+    if let Some(pixels) = pixels {
+        frame.draw_over(pixels);
+    }
+    grabadora.replay(&mut frame);
+    frame
+}
+
+impl Lienzo for Rasterizador {
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        let half = (width / 2.0).max(0.5);
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        let len_sq = dx * dx + dy * dy;
+        self.fill_where(
+            (from.0.min(to.0) - half, from.1.min(to.1) - half),
+            (from.0.max(to.0) + half, from.1.max(to.1) + half),
+            color,
+            |x, y| {
+                // Distancia del centro del pixel al segmento
+                let t = if len_sq == 0.0 {
+                    0.0
+                } else {
+                    (((x - from.0) * dx + (y - from.1) * dy) / len_sq).clamp(0.0, 1.0)
+                };
+                let (px, py) = (from.0 + dx * t - x, from.1 + dy * t - y);
+                px * px + py * py <= half * half
+            },
+        );
+    }
+
+    fn rect_filled(&mut self, min: (f32, f32), max: (f32, f32), color: Color) {
+        self.fill_where(min, max, color, |x, y| {
+            x >= min.0 && x < max.0 && y >= min.1 && y < max.1
+        });
+    }
+
+    fn circle_filled(&mut self, center: (f32, f32), radius: f32, color: Color) {
+        self.fill_where(
+            (center.0 - radius, center.1 - radius),
+            (center.0 + radius, center.1 + radius),
+            color,
+            |x, y| (x - center.0).powi(2) + (y - center.1).powi(2) <= radius * radius,
+        );
+    }
+
+    fn polygon_filled(&mut self, points: &[(f32, f32)], color: Color) {
+        if points.len() < 3 {
+            return;
+        }
+        let min = points.iter().fold((f32::MAX, f32::MAX), |acc, p| {
+            (acc.0.min(p.0), acc.1.min(p.1))
+        });
+        let max = points.iter().fold((f32::MIN, f32::MIN), |acc, p| {
+            (acc.0.max(p.0), acc.1.max(p.1))
+        });
+        // Regla par-impar: cuenta los lados que cruza un rayo hacia la derecha
+        self.fill_where(min, max, color, |x, y| {
+            let mut inside = false;
+            let mut prev = points[points.len() - 1];
+            for &point in points {
+                if (point.1 > y) != (prev.1 > y)
+                    && x < (prev.0 - point.0) * (y - point.1) / (prev.1 - point.1) + point.0
+                {
+                    inside = !inside;
+                }
+                prev = point;
+            }
+            inside
+        });
+    }
+
+    fn polyline(&mut self, points: &[(f32, f32)], closed: bool, width: f32, color: Color) {
+        for pair in points.windows(2) {
+            self.line(pair[0], pair[1], width, color);
+        }
+        if let (true, Some(first), Some(last)) =
+            (closed && points.len() > 2, points.first(), points.last())
+        {
+            self.line(*last, *first, width, color);
+        }
+    }
+
+    fn text(&mut self, text: &str, pos: (f32, f32), font_size: f32, color: Color) {
+        // Los glifos miden 8 pixeles, se escalan en multiplos enteros. Con una
+        // escala mayor que el lienzo un solo pixel del glifo ya lo cubre entero.
+        let max_scale = self.width.max(self.height) as f32 + 1.0;
+        let scale = (font_size / GLYPH_SIZE as f32)
+            .round()
+            .max(1.0)
+            .min(max_scale) as i64;
+        let cell = GLYPH_SIZE as i64 * scale;
+        let (origin_x, origin_y) = (pos.0.round() as i64, pos.1.round() as i64);
+        let (width, height) = (self.width as i64, self.height as i64);
+        let mut pixels = self.pixels.borrow_mut();
+        for (row, line) in text.lines().enumerate() {
+            let cell_y = origin_y.saturating_add((row as i64).saturating_mul(cell));
+            for (col, c) in line.chars().enumerate() {
+                let cell_x = origin_x.saturating_add((col as i64).saturating_mul(cell));
+                for (gy, bits) in glyph(c).iter().enumerate() {
+                    for gx in 0..GLYPH_SIZE {
+                        if bits & (0x80 >> gx) == 0 {
+                            continue;
+                        }
+                        // Recorta el pixel escalado al lienzo antes de recorrerlo
+                        let x = cell_x.saturating_add(gx as i64 * scale);
+                        let y = cell_y.saturating_add(gy as i64 * scale);
+                        for y in y.max(0)..y.saturating_add(scale).min(height) {
+                            for x in x.max(0)..x.saturating_add(scale).min(width) {
+                                Self::blend(&mut pixels, self.width, x, y, color);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: Color = Color::new(255, 255, 255, 255);
+
+    #[test]
+    fn text_draws_glyph() {
+        let mut lienzo = Rasterizador::new(16, 16);
+        lienzo.text("A", (0.0, 0.0), 8.0, WHITE);
+        let drawn = (0..8)
+            .flat_map(|y| (0..8).map(move |x| (x, y)))
+            .filter(|(x, y)| lienzo.get_pixel(*x, *y) == Some(WHITE))
+            .count();
+        assert!(drawn > 0);
+        assert_eq!(lienzo.get_pixel(12, 12), Some(BACKGROUND));
+    }
+
+    #[test]
+    fn text_with_huge_font_size() {
+        // Termina enseguida y dibuja lo mismo que la escala mas grande que cabe
+        let mut huge = Rasterizador::new(16, 16);
+        huge.text("AB\nCD", (-40.0, -60.0), 1e30, WHITE);
+        huge.text("A", (-1e30, 1e30), f32::INFINITY, WHITE);
+        let mut clamped = Rasterizador::new(16, 16);
+        clamped.text("AB\nCD", (-40.0, -60.0), 17.0 * 8.0, WHITE);
+        assert_eq!(huge.pixels(), clamped.pixels());
+        assert!(huge.pixels().chunks(4).any(|pixel| pixel == [255; 4]));
+    }
+
+    #[test]
+    fn polygon_needs_three_points() {
+        let mut lienzo = Rasterizador::new(16, 16);
+        let points = [(0.0, 0.0), (15.0, 0.0), (15.0, 15.0)];
+        for count in 0..3 {
+            lienzo.polygon_filled(&points[..count], WHITE);
+        }
+        let drawn = |lienzo: &Rasterizador| {
+            (0..16)
+                .flat_map(|y| (0..16).map(move |x| (x, y)))
+                .filter(|(x, y)| lienzo.get_pixel(*x, *y) == Some(WHITE))
+                .count()
+        };
+        assert_eq!(drawn(&lienzo), 0);
+        lienzo.polygon_filled(&points, WHITE);
+        assert!(drawn(&lienzo) > 0);
+    }
+}
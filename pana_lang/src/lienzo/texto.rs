@@ -0,0 +1,208 @@
+use super::{fuente::GLYPH_SIZE, paleta::C64, Color, Lienzo};
+
+const TRANSPARENT: Color = Color::new(0, 0, 0, 0);
+
+#[derive(Clone, Copy)]
+struct Celda {
+    c: char,
+    fg: Color,
+    bg: Color,
+}
+
+const EMPTY: Celda = Celda {
+    c: ' ',
+    fg: TRANSPARENT,
+    bg: TRANSPARENT,
+};
+
+// Cuadricula sin `modo_pantalla`, la misma de una pantalla de 320x200
+pub const COLS: usize = 40;
+pub const ROWS: usize = 25;
+
+// Capa de texto: una cuadricula de celdas que se dibuja encima de los pixeles.
+// En `modo_pantalla` las celdas miden 8x8 pixeles; en la ventana la cuadricula
+// es de `COLS`x`ROWS` y las celdas crecen con el lienzo.
+pub struct PantallaTexto {
+    cols: usize,
+    rows: usize,
+    cells: Vec<Celda>,
+    cursor: (usize, usize),
+    fg: Color,
+    bg: Color,
+    pub cursor_visible: bool,
+}
+
+impl PantallaTexto {
+    pub fn new(cols: usize, rows: usize) -> Self {
+        let (cols, rows) = (cols.max(1), rows.max(1));
+        Self {
+            cols,
+            rows,
+            cells: vec![EMPTY; cols * rows],
+            cursor: (0, 0),
+            fg: C64[1],
+            bg: TRANSPARENT,
+            cursor_visible: true,
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cursor(&self) -> (usize, usize) {
+        self.cursor
+    }
+
+    // Borra todas las celdas y regresa el cursor al inicio
+    pub fn clear(&mut self) {
+        self.cells.fill(EMPTY);
+        self.cursor = (0, 0);
+    }
+
+    pub fn locate(&mut self, col: usize, row: usize) -> bool {
+        if col >= self.cols || row >= self.rows {
+            return false;
+        }
+        self.cursor = (col, row);
+        true
+    }
+
+    pub fn set_colors(&mut self, fg: Color, bg: Option<Color>) {
+        self.fg = fg;
+        self.bg = bg.unwrap_or(TRANSPARENT);
+    }
+
+    pub fn char_at(&self, col: usize, row: usize) -> Option<char> {
+        if col >= self.cols || row >= self.rows {
+            return None;
+        }
+        Some(self.cells[row * self.cols + col].c)
+    }
+
+    // Escribe en la posicion del cursor. Las lineas largas continuan en la
+    // siguiente fila y al pasar la ultima fila la pantalla sube una linea.
+    pub fn write(&mut self, text: &str, newline: bool) {
+        for c in text.chars() {
+            match c {
+                '\n' => self.new_line(),
+                '\r' => self.cursor.0 = 0,
+                '\t' => {
+                    let spaces = 4 - self.cursor.0 % 4;
+                    for _ in 0..spaces {
+                        self.put(' ');
+                    }
+                }
+                _ => self.put(c),
+            }
+        }
+        if newline {
+            self.new_line();
+        }
+    }
+
+    fn put(&mut self, c: char) {
+        if self.cursor.0 >= self.cols {
+            self.new_line();
+        }
+        let (col, row) = self.cursor;
+        self.cells[row * self.cols + col] = Celda {
+            c,
+            fg: self.fg,
+            bg: self.bg,
+        };
+        self.cursor.0 += 1;
+    }
+
+    fn new_line(&mut self) {
+        self.cursor.0 = 0;
+        if self.cursor.1 + 1 < self.rows {
+            self.cursor.1 += 1;
+        } else {
+            self.scroll();
+        }
+    }
+
+    fn scroll(&mut self) {
+        self.cells.copy_within(self.cols.., 0);
+        let last = (self.rows - 1) * self.cols;
+        self.cells[last..].fill(EMPTY);
+    }
+
+    // Celdas de 8x8 pixeles para una pantalla de `width`x`height`
+    pub fn for_screen(width: usize, height: usize) -> Self {
+        Self::new(width / GLYPH_SIZE, height / GLYPH_SIZE)
+    }
+
+    // Dibuja las celdas de `cell` pixeles y, si `blink_on`, el cursor como un
+    // bloque. La letra usa el lado menor de la celda.
+    pub fn render(&self, lienzo: &mut dyn Lienzo, cell: (f32, f32), blink_on: bool) {
+        let font_size = cell.0.min(cell.1);
+        let corner = |col: usize, row: usize| (col as f32 * cell.0, row as f32 * cell.1);
+        for (i, celda) in self.cells.iter().enumerate() {
+            let pos = corner(i % self.cols, i / self.cols);
+            if celda.bg.a > 0 {
+                lienzo.rect_filled(pos, (pos.0 + cell.0, pos.1 + cell.1), celda.bg);
+            }
+            if celda.c != ' ' {
+                let mut buf = [0; 4];
+                lienzo.text(celda.c.encode_utf8(&mut buf), pos, font_size, celda.fg);
+            }
+        }
+        if self.cursor_visible && blink_on && self.cursor.0 < self.cols {
+            let pos = corner(self.cursor.0, self.cursor.1);
+            lienzo.rect_filled(pos, (pos.0 + cell.0, pos.1 + cell.1), self.fg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        eval::evaluator::Evaluator,
+        lienzo::grabadora::{Comando, Grabadora},
+    };
+
+    #[test]
+    fn window_grid_is_fixed() {
+        for (width, height) in [(320.0, 200.0), (1280.0, 720.0), (100.0, 50.0)] {
+            let mut eval = Evaluator::new(None, width, height);
+            let text = eval.ensure_text();
+            assert_eq!((text.cols(), text.rows()), (COLS, ROWS));
+        }
+    }
+
+    #[test]
+    fn screen_grid_uses_8x8_cells() {
+        let mut eval = Evaluator::new(None, 1280.0, 720.0);
+        eval.set_screen(160, 100);
+        let text = eval.ensure_text();
+        assert_eq!((text.cols(), text.rows()), (20, 12));
+    }
+
+    #[test]
+    fn cells_scale_to_the_canvas() {
+        let mut text = PantallaTexto::new(COLS, ROWS);
+        text.locate(1, 2);
+        text.write("A", false);
+        let grabadora = Grabadora::new();
+        // Un lienzo de 640x500 tiene celdas de 16x20
+        text.render(&mut grabadora.clone(), (16.0, 20.0), true);
+        let comandos = grabadora.comandos();
+        assert!(comandos.iter().any(|comando| matches!(
+            comando,
+            Comando::Texto { text, pos, font_size, .. }
+                if text == "A" && *pos == (16.0, 40.0) && *font_size == 16.0
+        )));
+        // El cursor queda despues de la letra y cubre su celda
+        assert!(comandos.iter().any(|comando| matches!(
+            comando,
+            Comando::Rectangulo { min, max, .. } if *min == (32.0, 40.0) && *max == (48.0, 60.0)
+        )));
+    }
+}
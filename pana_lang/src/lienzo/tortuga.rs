@@ -0,0 +1,218 @@
+use super::{rasterizador::Rasterizador, Color, Lienzo};
+
+// Tamano del triangulo que representa a la tortuga
+const HEAD_SIZE: f32 = 10.0;
+const HEAD_COLOR: Color = Color::new(0x70, 0xE0, 0x70, 0xFF);
+
+// Un movimiento o giro de la tortuga. Los giros y los movimientos con el lapiz
+// arriba no dibujan, pero cuentan como pasos de la animacion.
+#[derive(Clone, Copy)]
+struct Paso {
+    from: (f32, f32),
+    to: (f32, f32),
+    // Direccion al empezar el paso
+    heading: f32,
+    width: f32,
+    color: Color,
+    pen_down: bool,
+}
+
+impl Paso {
+    fn draw(&self, lienzo: &mut dyn Lienzo) {
+        if self.pen_down {
+            lienzo.line(self.from, self.to, self.width, self.color);
+        }
+    }
+}
+
+// Graficos de tortuga. El camino se muestra de a `speed` pasos por cuadro, o
+// completo si `speed` es 0. Los pasos ya mostrados se dibujan una sola vez en
+// una superficie que conserva los pixeles y salen del camino. El camino esta
+// en coordenadas de la ventana: no lo mueven la camara ni `trasladar`, `rotar`
+// y `escalar`, que cambian en cada cuadro.
+pub struct Tortuga {
+    pos: (f32, f32),
+    // Grados en sentido horario, 0 apunta hacia arriba
+    heading: f32,
+    pub pen_down: bool,
+    pub color: Color,
+    pub width: f32,
+    pub speed: usize,
+    pub visible: bool,
+    // Pasos que faltan mostrar
+    path: Vec<Paso>,
+    // Pasos ya mostrados, para dibujarlos otra vez si la capa cambia de tamano
+    shown: Vec<Paso>,
+    // Capa con el camino ya mostrado en el modo ventana. En `modo_pantalla`
+    // el camino va directo a la pantalla.
+    pub layer: Option<Rasterizador>,
+}
+
+impl Tortuga {
+    pub fn new(pos: (f32, f32)) -> Self {
+        Self {
+            pos,
+            heading: 0.0,
+            pen_down: true,
+            color: Color::new(0xFF, 0xFF, 0xFF, 0xFF),
+            width: 1.0,
+            speed: 0,
+            visible: true,
+            path: Vec::new(),
+            shown: Vec::new(),
+            layer: None,
+        }
+    }
+
+    pub fn forward(&mut self, distance: f32) {
+        let radians = self.heading.to_radians();
+        let to = (
+            self.pos.0 + distance * radians.sin(),
+            self.pos.1 - distance * radians.cos(),
+        );
+        self.push(to);
+    }
+
+    pub fn turn(&mut self, degrees: f32) {
+        self.push(self.pos);
+        self.heading = (self.heading + degrees).rem_euclid(360.0);
+    }
+
+    fn push(&mut self, to: (f32, f32)) {
+        self.path.push(Paso {
+            from: self.pos,
+            to,
+            heading: self.heading,
+            width: self.width,
+            color: self.color,
+            pen_down: self.pen_down && to != self.pos,
+        });
+        self.pos = to;
+    }
+
+    // Dibuja en `lienzo`, que debe conservar los pixeles, los pasos que
+    // tocan en este cuadro y los saca del camino
+    pub fn advance(&mut self, lienzo: &mut dyn Lienzo) {
+        let count = match self.speed {
+            0 => self.path.len(),
+            speed => speed.min(self.path.len()),
+        };
+        for paso in self.path.drain(..count) {
+            paso.draw(lienzo);
+            self.shown.push(paso);
+        }
+    }
+
+    // Avanza el camino en la capa del modo ventana. Si la ventana cambio de
+    // tamano, la capa nueva empieza con los pasos ya mostrados.
+    pub fn advance_layer(&mut self, width: usize, height: usize) {
+        let layer = match &self.layer {
+            Some(layer) if (layer.width(), layer.height()) == (width, height) => layer.clone(),
+            _ => {
+                let layer = Rasterizador::with_color(width, height, Color::new(0, 0, 0, 0));
+                for paso in &self.shown {
+                    paso.draw(&mut layer.clone());
+                }
+                self.layer.insert(layer).clone()
+            }
+        };
+        self.advance(&mut layer.clone());
+    }
+
+    // Dibuja la tortuga al inicio del primer paso que falta mostrar
+    pub fn render_head(&self, lienzo: &mut dyn Lienzo) {
+        if !self.visible {
+            return;
+        }
+        let (pos, heading) = match self.path.first() {
+            Some(paso) => (paso.from, paso.heading),
+            None => (self.pos, self.heading),
+        };
+        let point = |angle: f32, distance: f32| {
+            let radians = (heading + angle).to_radians();
+            (
+                pos.0 + distance * radians.sin(),
+                pos.1 - distance * radians.cos(),
+            )
+        };
+        let tip = point(0.0, HEAD_SIZE);
+        let left = point(-140.0, HEAD_SIZE * 0.7);
+        let right = point(140.0, HEAD_SIZE * 0.7);
+        lienzo.line(tip, left, 1.0, HEAD_COLOR);
+        lienzo.line(left, right, 1.0, HEAD_COLOR);
+        lienzo.line(right, tip, 1.0, HEAD_COLOR);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lienzo::grabadora::{Comando, Grabadora};
+
+    fn lines(grabadora: &Grabadora) -> usize {
+        grabadora
+            .comandos()
+            .iter()
+            .filter(|comando| matches!(comando, Comando::Linea { .. }))
+            .count()
+    }
+
+    #[test]
+    fn shown_steps_leave_the_path() {
+        let mut tortuga = Tortuga::new((50.0, 50.0));
+        for _ in 0..1000 {
+            tortuga.forward(1.0);
+        }
+        let grabadora = Grabadora::new();
+        tortuga.advance(&mut grabadora.clone());
+        assert_eq!(lines(&grabadora), 1000);
+        assert!(tortuga.path.is_empty());
+
+        // Los cuadros siguientes no vuelven a dibujar el camino
+        grabadora.limpiar();
+        tortuga.advance(&mut grabadora.clone());
+        assert_eq!(lines(&grabadora), 0);
+    }
+
+    #[test]
+    fn speed_shows_steps_per_frame() {
+        let mut tortuga = Tortuga::new((50.0, 50.0));
+        tortuga.speed = 2;
+        tortuga.forward(10.0);
+        tortuga.turn(90.0);
+        tortuga.forward(10.0);
+        let grabadora = Grabadora::new();
+        tortuga.advance(&mut grabadora.clone());
+        // El giro cuenta como paso pero no dibuja
+        assert_eq!(lines(&grabadora), 1);
+        assert_eq!(tortuga.path.len(), 1);
+        tortuga.advance(&mut grabadora.clone());
+        assert_eq!(lines(&grabadora), 2);
+        assert!(tortuga.path.is_empty());
+    }
+
+    #[test]
+    fn resize_keeps_shown_steps() {
+        let mut tortuga = Tortuga::new((10.0, 50.0));
+        tortuga.speed = 1;
+        tortuga.turn(90.0);
+        tortuga.forward(30.0);
+        tortuga.forward(30.0);
+        tortuga.advance_layer(100, 100);
+        tortuga.advance_layer(100, 100);
+        let drawn = |tortuga: &Tortuga, x: usize| {
+            let layer = tortuga.layer.as_ref().unwrap();
+            layer.get_pixel(x, 50).map_or(0, |color| color.a)
+        };
+        assert!(drawn(&tortuga, 25) > 0);
+        assert_eq!(drawn(&tortuga, 55), 0);
+
+        // La ventana cambia de tamano a mitad del camino
+        tortuga.advance_layer(200, 150);
+        let layer = tortuga.layer.as_ref().unwrap();
+        assert_eq!((layer.width(), layer.height()), (200, 150));
+        assert!(drawn(&tortuga, 25) > 0);
+        assert!(drawn(&tortuga, 55) > 0);
+        assert!(tortuga.path.is_empty());
+    }
+}
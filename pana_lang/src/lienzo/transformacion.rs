@@ -0,0 +1,211 @@
+use super::{formas::full_ellipse_points, Color, Lienzo, Rectangulo};
+
+// Matriz afin de `trasladar`, `rotar` y `escalar`:
+// x' = a * x + c * y + e
+// y' = b * x + d * y + f
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transformacion {
+    a: f32,
+    b: f32,
+    c: f32,
+    d: f32,
+    e: f32,
+    f: f32,
+}
+
+impl Default for Transformacion {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Transformacion {
+    pub const IDENTITY: Self = Self {
+        a: 1.0,
+        b: 0.0,
+        c: 0.0,
+        d: 1.0,
+        e: 0.0,
+        f: 0.0,
+    };
+
+    pub fn is_identity(&self) -> bool {
+        *self == Self::IDENTITY
+    }
+
+    // Aplica `other` antes que `self`, asi cada operacion nueva trabaja en
+    // las coordenadas que dejaron las anteriores.
+    pub fn then(&self, other: Self) -> Self {
+        Self {
+            a: self.a * other.a + self.c * other.b,
+            b: self.b * other.a + self.d * other.b,
+            c: self.a * other.c + self.c * other.d,
+            d: self.b * other.c + self.d * other.d,
+            e: self.a * other.e + self.c * other.f + self.e,
+            f: self.b * other.e + self.d * other.f + self.f,
+        }
+    }
+
+    pub fn translate(&self, x: f32, y: f32) -> Self {
+        self.then(Self {
+            e: x,
+            f: y,
+            ..Self::IDENTITY
+        })
+    }
+
+    // Radianes, en el sentido del reloj porque el eje y apunta hacia abajo
+    pub fn rotate(&self, angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        self.then(Self {
+            a: cos,
+            b: sin,
+            c: -sin,
+            d: cos,
+            ..Self::IDENTITY
+        })
+    }
+
+    pub fn scale(&self, x: f32, y: f32) -> Self {
+        self.then(Self {
+            a: x,
+            d: y,
+            ..Self::IDENTITY
+        })
+    }
+
+    pub fn apply(&self, (x, y): (f32, f32)) -> (f32, f32) {
+        (
+            self.a * x + self.c * y + self.e,
+            self.b * x + self.d * y + self.f,
+        )
+    }
+
+    // Transformacion que deshace esta, `None` si la escala es 0
+    pub fn invert(&self) -> Option<Self> {
+        let det = self.a * self.d - self.b * self.c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        Some(Self {
+            a: self.d / det,
+            b: -self.b / det,
+            c: -self.c / det,
+            d: self.a / det,
+            e: (self.c * self.f - self.d * self.e) / det,
+            f: (self.b * self.e - self.a * self.f) / det,
+        })
+    }
+
+    // Cuanto crecen las longitudes en promedio, para grosores y radios
+    pub fn scale_factor(&self) -> f32 {
+        (self.a * self.d - self.b * self.c).abs().sqrt()
+    }
+
+    // Sin rotacion ni deformacion los rectangulos siguen alineados
+    fn keeps_axes(&self) -> bool {
+        self.b == 0.0 && self.c == 0.0
+    }
+
+    // Los circulos siguen siendo circulos si la escala es igual en ambos ejes
+    fn keeps_circles(&self) -> bool {
+        self.a == self.d && self.b == -self.c
+    }
+}
+
+// Lienzo que transforma todo lo que se dibuja antes de pasarlo a otro
+pub struct Transformado<'a> {
+    pub lienzo: &'a mut dyn Lienzo,
+    pub transform: Transformacion,
+}
+
+impl Transformado<'_> {
+    fn points(&self, points: &[(f32, f32)]) -> Vec<(f32, f32)> {
+        points.iter().map(|&p| self.transform.apply(p)).collect()
+    }
+}
+
+impl Lienzo for Transformado<'_> {
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        self.lienzo.line(
+            self.transform.apply(from),
+            self.transform.apply(to),
+            width * self.transform.scale_factor(),
+            color,
+        );
+    }
+
+    fn rect_filled(&mut self, min: (f32, f32), max: (f32, f32), color: Color) {
+        if self.transform.keeps_axes() {
+            let (p1, p2) = (self.transform.apply(min), self.transform.apply(max));
+            return self.lienzo.rect_filled(
+                (p1.0.min(p2.0), p1.1.min(p2.1)),
+                (p1.0.max(p2.0), p1.1.max(p2.1)),
+                color,
+            );
+        }
+        let corners = self.points(&[min, (max.0, min.1), max, (min.0, max.1)]);
+        self.lienzo.polygon_filled(&corners, color);
+    }
+
+    fn circle_filled(&mut self, center: (f32, f32), radius: f32, color: Color) {
+        if self.transform.keeps_circles() {
+            return self.lienzo.circle_filled(
+                self.transform.apply(center),
+                radius * self.transform.scale_factor(),
+                color,
+            );
+        }
+        let points = self.points(&full_ellipse_points(center, (radius, radius)));
+        self.lienzo.polygon_filled(&points, color);
+    }
+
+    fn polygon_filled(&mut self, points: &[(f32, f32)], color: Color) {
+        let points = self.points(points);
+        self.lienzo.polygon_filled(&points, color);
+    }
+
+    fn polyline(&mut self, points: &[(f32, f32)], closed: bool, width: f32, color: Color) {
+        let points = self.points(points);
+        self.lienzo.polyline(
+            &points,
+            closed,
+            width * self.transform.scale_factor(),
+            color,
+        );
+    }
+
+    // El texto se mueve y escala, pero no rota
+    fn text(&mut self, text: &str, pos: (f32, f32), font_size: f32, color: Color) {
+        self.lienzo.text(
+            text,
+            self.transform.apply(pos),
+            font_size * self.transform.scale_factor(),
+            color,
+        );
+    }
+
+    fn rects_filled(&mut self, rects: &[Rectangulo]) {
+        if !self.transform.keeps_axes() {
+            for rect in rects {
+                self.rect_filled(rect.min, rect.max, rect.color);
+            }
+            return;
+        }
+        let rects: Vec<Rectangulo> = rects
+            .iter()
+            .map(|rect| {
+                let (p1, p2) = (
+                    self.transform.apply(rect.min),
+                    self.transform.apply(rect.max),
+                );
+                Rectangulo {
+                    min: (p1.0.min(p2.0), p1.1.min(p2.1)),
+                    max: (p1.0.max(p2.0), p1.1.max(p2.1)),
+                    color: rect.color,
+                }
+            })
+            .collect();
+        self.lienzo.rects_filled(&rects);
+    }
+}
@@ -10,11 +10,20 @@ pub struct Expression {
     pub r#type: ExprType,
     pub line: usize,
     pub col: usize,
+    // No contiene llamadas ni bloques, asi que no puede llegar a `esperar` o
+    // `preguntar` y se evalua sin tareas
+    pub sync: bool,
 }
 
 impl Expression {
     pub fn new(r#type: ExprType, line: usize, col: usize) -> Self {
-        Self { r#type, line, col }
+        let sync = r#type.is_sync();
+        Self {
+            r#type,
+            line,
+            col,
+            sync,
+        }
     }
 }
 
@@ -63,6 +72,12 @@ pub enum ExprType {
         left: Box<Expression>,
         index: Box<Expression>,
     },
+    Slice {
+        left: Box<Expression>,
+        start: Option<Box<Expression>>,
+        end: Option<Box<Expression>>,
+        step: Option<Box<Expression>>,
+    },
     Prefix {
         operator: TokenType,
         right: Box<Expression>,
@@ -126,7 +141,7 @@ impl PartialEq for ExprType {
                 Self::DictionaryLiteral { pairs: l_pairs },
                 Self::DictionaryLiteral { pairs: r_pairs },
             ) => l_pairs == r_pairs,
-            (Self::Index { .. }, Self::Index { .. }) => {
+            (Self::Index { .. }, Self::Index { .. }) | (Self::Slice { .. }, Self::Slice { .. }) => {
                 panic!("No se puede comparar expresion de indexacion")
             }
             (
@@ -193,6 +208,22 @@ impl Display for ExprType {
             ExprType::StringLiteral(string) => write!(f, "\"{}\"", string),
             ExprType::ListLiteral { elements } => write!(f, "[{}]", format_arguments(elements)),
             ExprType::Index { left, index } => write!(f, "{}[{}]", left.r#type, index.r#type),
+            ExprType::Slice {
+                left,
+                start,
+                end,
+                step,
+            } => {
+                let bound = |expr: &Option<Box<Expression>>| {
+                    expr.as_ref()
+                        .map_or(String::new(), |expr| expr.r#type.to_string())
+                };
+                write!(f, "{}[{}:{}", left.r#type, bound(start), bound(end))?;
+                if step.is_some() {
+                    write!(f, ":{}", bound(step))?;
+                }
+                write!(f, "]")
+            }
             ExprType::NullLiteral => write!(f, "nulo"),
             ExprType::DictionaryLiteral { pairs } => write!(
                 f,
@@ -217,6 +248,43 @@ impl Display for ExprType {
 }
 
 impl ExprType {
+    // Las hojas y los operadores son sincronos si todo lo que contienen lo es.
+    // Una funcion anonima no ejecuta su cuerpo al crearse.
+    fn is_sync(&self) -> bool {
+        let sync = |expr: &Option<Box<Expression>>| expr.as_ref().map_or(true, |expr| expr.sync);
+        match self {
+            ExprType::Identifier(_)
+            | ExprType::NumericLiteral(_)
+            | ExprType::BooleanLiteral(_)
+            | ExprType::StringLiteral(_)
+            | ExprType::NullLiteral
+            | ExprType::FnLiteral { .. } => true,
+            ExprType::ListLiteral { elements } => elements.iter().all(|expr| expr.sync),
+            ExprType::DictionaryLiteral { pairs } => {
+                pairs.iter().all(|(key, value)| key.sync && value.sync)
+            }
+            ExprType::Index { left, index } => left.sync && index.sync,
+            ExprType::Slice {
+                left,
+                start,
+                end,
+                step,
+            } => left.sync && sync(start) && sync(end) && sync(step),
+            ExprType::Prefix { right, .. } => right.sync,
+            // Las funciones miembro pueden recibir otra funcion y llamarla
+            ExprType::Infix {
+                operator: TokenType::Dot,
+                ..
+            } => false,
+            ExprType::Infix { left, right, .. } => left.sync && right.sync,
+            ExprType::Assignment { left, right } => left.sync && right.sync,
+            ExprType::If { .. }
+            | ExprType::While { .. }
+            | ExprType::Call { .. }
+            | ExprType::ForRange { .. } => false,
+        }
+    }
+
     pub fn get_type(&self) -> &str {
         match self {
             ExprType::Identifier(_) => "identificador",
@@ -228,6 +296,7 @@ impl ExprType {
             ExprType::ListLiteral { .. } => "funcion",
             ExprType::DictionaryLiteral { .. } => "lista",
             ExprType::Index { .. } => "diccionario",
+            ExprType::Slice { .. } => "rebanada",
             ExprType::Prefix { .. } => "infija",
             ExprType::Infix { .. } => "prefija",
             ExprType::If { .. } => "condicion",
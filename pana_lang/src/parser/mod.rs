@@ -63,8 +63,8 @@ fn create_funny_assigment(
                 cur_line,
                 cur_col,
             )),
-            right: Box::new(Expression {
-                r#type: ExprType::Infix {
+            right: Box::new(Expression::new(
+                ExprType::Infix {
                     left: Box::new(Expression::new(
                         ExprType::Identifier(ident.clone()),
                         cur_line,
@@ -75,7 +75,7 @@ fn create_funny_assigment(
                 },
                 line,
                 col,
-            }),
+            )),
         },
         cur_line,
         cur_col,
@@ -86,6 +86,7 @@ pub struct Parser {
     lexer: Lexer,
     current_token: Token,
     peek_token: Token,
+    doc: Option<String>,
     pub error: Option<ParserError>,
 }
 
@@ -103,6 +104,7 @@ impl Parser {
                 line: 0,
                 col: 0,
             },
+            doc: None,
             error: None,
         }
     }
@@ -171,7 +173,9 @@ impl Parser {
         self.next_token();
 
         while self.current_token.r#type != TokenType::Eof {
-            if !self.current_token_is(TokenType::CommentLine)
+            if let TokenType::CommentBlock(doc) = &self.current_token.r#type {
+                self.doc = Some(doc.clone());
+            } else if !self.current_token_is(TokenType::CommentLine)
                 && !self.current_token_is(TokenType::NewLine)
             {
                 match self.parse_statement() {
@@ -192,10 +196,12 @@ impl Parser {
             self.next_token();
             return self.parse_statement();
         }
+        // El comentario de bloque solo documenta a la declaracion que le sigue
+        let doc = self.doc.take();
         match self.current_token.r#type {
             TokenType::Var => self.parse_var_statement(),
             TokenType::Return => self.parse_return_statement(),
-            TokenType::Func => self.parse_fn_statement(),
+            TokenType::Func => self.parse_fn_statement(doc),
             TokenType::Break => {
                 if !self.expected_peek(TokenType::SemiColon) {
                     return Err(ParserError::MissingSemiColon(
@@ -236,7 +242,11 @@ impl Parser {
                     self.current_token.col,
                 ));
             }
-            if !self.current_token_is(TokenType::NewLine) {
+            if let TokenType::CommentBlock(doc) = &self.current_token.r#type {
+                self.doc = Some(doc.clone());
+            } else if !self.current_token_is(TokenType::NewLine)
+                && !self.current_token_is(TokenType::CommentLine)
+            {
                 match self.parse_statement() {
                     Ok(stmt) => {
                         statements.push(stmt);
@@ -309,7 +319,7 @@ impl Parser {
         Ok(Statement::Return(expr, line, col))
     }
 
-    fn parse_fn_statement(&mut self) -> Result<Statement, ParserError> {
+    fn parse_fn_statement(&mut self, doc: Option<String>) -> Result<Statement, ParserError> {
         let identifier: String;
 
         if let TokenType::Ident(ident) = self.peek_token.r#type.clone() {
@@ -347,6 +357,7 @@ impl Parser {
             name: identifier,
             params,
             body,
+            doc,
             line,
             col,
         })
@@ -876,7 +887,13 @@ impl Parser {
 
     fn parse_index_expression(&mut self, left: Expression) -> Result<Expression, ParserError> {
         self.next_token();
+        if self.current_token_is(TokenType::Colon) {
+            return self.parse_slice_expression(left, None);
+        }
         let index = self.parse_expression(Precedence::Lowest)?;
+        if self.expected_peek(TokenType::Colon) {
+            return self.parse_slice_expression(left, Some(Box::new(index)));
+        }
 
         if !self.expected_peek(TokenType::RBracket) {
             return Err(ParserError::MissingRightBracket(
@@ -923,6 +940,68 @@ impl Parser {
         ))
     }
 
+    // lista[inicio:fin:pasos], cualquiera de los tres limites es opcional
+    fn parse_slice_expression(
+        &mut self,
+        left: Expression,
+        start: Option<Box<Expression>>,
+    ) -> Result<Expression, ParserError> {
+        let end = self.parse_slice_bound()?;
+        let mut step = None;
+        if self.expected_peek(TokenType::Colon) {
+            step = self.parse_slice_bound()?;
+        }
+
+        if !self.expected_peek(TokenType::RBracket) {
+            return Err(ParserError::MissingRightBracket(
+                self.peek_token.line,
+                self.peek_token.col,
+            ));
+        }
+
+        let slice = Expression::new(
+            ExprType::Slice {
+                left: Box::new(left),
+                start,
+                end,
+                step,
+            },
+            self.current_token.line,
+            self.current_token.col,
+        );
+
+        if self.peek_token_is(TokenType::Assign) {
+            self.next_token();
+            self.next_token();
+
+            let right = self.parse_expression(Precedence::Lowest)?;
+            if !self.expected_peek(TokenType::SemiColon) {
+                return Err(ParserError::MissingSemiColon(
+                    self.peek_token.line,
+                    self.peek_token.col,
+                ));
+            }
+            return Ok(Expression::new(
+                ExprType::Assignment {
+                    left: Box::new(slice),
+                    right: Box::new(right),
+                },
+                self.current_token.line,
+                self.current_token.col,
+            ));
+        }
+
+        Ok(slice)
+    }
+
+    fn parse_slice_bound(&mut self) -> Result<Option<Box<Expression>>, ParserError> {
+        if self.peek_token_is(TokenType::Colon) || self.peek_token_is(TokenType::RBracket) {
+            return Ok(None);
+        }
+        self.next_token();
+        Ok(Some(Box::new(self.parse_expression(Precedence::Lowest)?)))
+    }
+
     fn parse_while_loop(&mut self) -> Result<Expression, ParserError> {
         self.next_token();
 
@@ -16,6 +16,7 @@ pub enum Statement {
         name: String,
         params: FnParams,
         body: BlockStatement,
+        doc: Option<String>,
         line: usize,
         col: usize,
     },
@@ -7,6 +7,7 @@ pub enum TokenType {
     IllegalMsg(String),
     Eof,
     CommentLine,
+    CommentBlock(String),
 
     // Identifiers, literals
     Ident(String),
@@ -101,6 +102,7 @@ impl Display for TokenType {
             TokenType::RBracket => write!(f, "]"),
             TokenType::Null => write!(f, "nulo"),
             TokenType::CommentLine => write!(f, "#"),
+            TokenType::CommentBlock(_) => write!(f, "###"),
             TokenType::Colon => write!(f, ":"),
             TokenType::IllegalMsg(msg) => write!(f, "{}", msg),
             TokenType::NewLine => write!(f, "\\n"),
@@ -1,4 +1,5 @@
 use std::{
+    cmp::Ordering,
     fmt::Display,
     ops::{Add, Div, Mul, Neg, Rem, Sub},
 };
@@ -16,6 +17,26 @@ impl Numeric {
             Numeric::Float(_) => "flotante",
         }
     }
+
+    // Compara por valor, promoviendo el entero a flotante: 1 == 1.0, 0.5 < 4
+    pub fn value_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Numeric::Int(a), Numeric::Int(b)) => Some(a.cmp(b)),
+            (Numeric::Int(a), Numeric::Float(b)) => (*a as f64).partial_cmp(b),
+            (Numeric::Float(a), Numeric::Int(b)) => a.partial_cmp(&(*b as f64)),
+            (Numeric::Float(a), Numeric::Float(b)) => a.partial_cmp(b),
+        }
+    }
+
+    // A diferencia de `partial_cmp` siempre retorna un orden, los NaN quedan al final
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Numeric::Int(a), Numeric::Int(b)) => a.cmp(b),
+            (Numeric::Int(a), Numeric::Float(b)) => (*a as f64).total_cmp(b),
+            (Numeric::Float(a), Numeric::Int(b)) => a.total_cmp(&(*b as f64)),
+            (Numeric::Float(a), Numeric::Float(b)) => a.total_cmp(b),
+        }
+    }
 }
 
 impl Neg for Numeric {
@@ -102,3 +123,77 @@ impl Display for Numeric {
         }
     }
 }
+
+// Vector de dos dimensiones, en coordenadas del lienzo: el eje y apunta hacia abajo
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Vector {
+    pub const fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    pub fn length(&self) -> f64 {
+        self.x.hypot(self.y)
+    }
+
+    // El vector nulo no tiene direccion y se queda igual
+    pub fn normalize(&self) -> Self {
+        let length = self.length();
+        if length == 0.0 {
+            return *self;
+        }
+        Self::new(self.x / length, self.y / length)
+    }
+
+    // Radianes, en el sentido del reloj como `rotar`
+    pub fn rotate(&self, angle: f64) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Self::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    pub fn total_cmp(&self, other: &Self) -> Ordering {
+        self.x.total_cmp(&other.x).then(self.y.total_cmp(&other.y))
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<f64> for Vector {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Display for Vector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "vector({}, {})", self.x, self.y)
+    }
+}
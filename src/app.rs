@@ -1,12 +1,18 @@
-use std::{cell::RefCell, rc::Rc, time::Duration};
+use std::{cell::RefCell, collections::HashMap, rc::Rc, time::Duration};
 
 use egui::{text_edit::CursorRange, Color32, FontId, Frame, Margin, RichText, Sense, Vec2, Vec2b};
 use egui_code_editor::{CodeEditor, ColorTheme};
 use egui_commonmark::{CommonMarkCache, CommonMarkViewer};
+use pana_lang::eval::tarea::Tarea;
 
+use crate::corrutina::{Corrutina, Estado};
 use crate::pana_syntax::pana_syntax;
+use crate::pausa::{prepare_frame, Anfitrion, Espera, Reanudar};
 
 const MANUAL_STR: &str = include_str!("manual.md");
+// Llave de eframe donde quedan los datos de `guardar_dato` de los programas
+// sin nombre. Los demas agregan el hash de su nombre.
+const DATOS_KEY: &str = "pana_datos";
 
 #[derive(serde::Deserialize, serde::Serialize)]
 enum Views {
@@ -14,6 +20,19 @@ enum Views {
     Canvas,
 }
 
+// Parte del programa que corre en la corrutina. Si falla el codigo de nivel
+// superior no queda programa que animar.
+enum Etapa {
+    Programa,
+    Cuadro,
+}
+
+type Resultado = (
+    pana_lang::eval::evaluator::Evaluator,
+    Etapa,
+    Result<(), String>,
+);
+
 #[derive(serde::Deserialize, serde::Serialize)]
 #[serde(default)]
 pub struct App {
@@ -21,15 +40,48 @@ pub struct App {
     view: Views,
     first_run: bool,
     show_manual: bool,
+    gif_frames: usize,
     #[serde(skip)]
-    loop_fn: pana_lang::parser::statement::BlockStatement,
+    loop_fn: Rc<pana_lang::parser::statement::BlockStatement>,
     #[serde(skip)]
     environment: pana_lang::eval::environment::RcEnvironment,
     #[serde(skip)]
     evaluator: Option<pana_lang::eval::evaluator::Evaluator>,
     #[serde(skip)]
+    corrutina: Option<Corrutina<Espera, Reanudar, Resultado>>,
+    // Por que esta detenido el programa, `None` si no lo esta
+    #[serde(skip)]
+    espera: Option<Espera>,
+    // Texto de la caja de `preguntar`
+    #[serde(skip)]
+    answer: String,
+    // Figuras del cuadro en el modo ventana, para las capturas y las pausas
+    #[serde(skip)]
+    grabadora: pana_lang::lienzo::grabadora::Grabadora,
+    // Datos de `guardar_dato` de cada programa ejecutado, por su llave de
+    // eframe. Se conservan al cerrar la aplicacion.
+    #[serde(skip)]
+    datos: HashMap<String, pana_lang::eval::almacen::Datos>,
+    // Llave de los datos del programa que corre
+    #[serde(skip)]
+    datos_key: String,
+    #[serde(skip)]
     err_msg: String,
     #[serde(skip)]
+    status_msg: String,
+    #[serde(skip)]
+    capture_requested: bool,
+    #[serde(skip)]
+    recording: Option<pana_lang::lienzo::gif::AnimacionGif>,
+    #[serde(skip)]
+    canvas_size: (usize, usize),
+    #[serde(skip)]
+    framebuffer_texture: Option<egui::TextureHandle>,
+    // Donde se mostro la textura en el ultimo cuadro, con el color del borde
+    // en `modo_pantalla`
+    #[serde(skip)]
+    shown_image: Option<(egui::Rect, Option<Color32>)>,
+    #[serde(skip)]
     manual_commonmark_cache: CommonMarkCache,
 }
 
@@ -40,12 +92,25 @@ impl Default for App {
             code: include_str!("./example.pana").to_string(),
             view: Views::Editor,
             first_run: false,
-            loop_fn: Vec::default(),
+            loop_fn: Rc::default(),
             environment: Rc::new(RefCell::new(
                 pana_lang::eval::environment::Environment::new(None),
             )),
             evaluator: None,
+            corrutina: None,
+            espera: None,
+            answer: String::new(),
+            grabadora: pana_lang::lienzo::grabadora::Grabadora::new(),
+            datos: HashMap::new(),
+            datos_key: DATOS_KEY.to_owned(),
             err_msg: String::new(),
+            status_msg: String::new(),
+            capture_requested: false,
+            gif_frames: 120,
+            recording: None,
+            canvas_size: (1, 1),
+            framebuffer_texture: None,
+            shown_image: None,
             manual_commonmark_cache: CommonMarkCache::default(),
         }
     }
@@ -60,6 +125,25 @@ impl App {
         Default::default()
     }
 
+    // Lee de `storage` los datos del programa del editor, si no se leyeron
+    // antes en esta sesion
+    fn load_datos(&mut self, storage: Option<&dyn eframe::Storage>) {
+        self.datos_key = datos_key(&self.code);
+        if self.datos.contains_key(&self.datos_key) {
+            return;
+        }
+        let text = storage.and_then(|storage| storage.get_string(&self.datos_key));
+        let datos = match text.map(|text| pana_lang::eval::almacen::Datos::from_json(&text)) {
+            Some(Ok(datos)) => datos,
+            Some(Err(err)) => {
+                log::error!("No se pudieron leer los datos guardados: {}", err);
+                pana_lang::eval::almacen::Datos::default()
+            }
+            None => pana_lang::eval::almacen::Datos::default(),
+        };
+        self.datos.insert(self.datos_key.clone(), datos);
+    }
+
     fn editor(&mut self, ui: &mut egui::Ui) {
         let output = CodeEditor::default()
             .id_source("code editor")
@@ -96,6 +180,396 @@ impl App {
         }
     }
 
+    // Ejecuta `job` con el evaluador en la corrutina. Retorna `false` si el
+    // programa quedo detenido en `preguntar`, `esperar` o `siguiente_cuadro`.
+    fn run(
+        &mut self,
+        etapa: Etapa,
+        job: impl for<'a> FnOnce(
+                &'a mut pana_lang::eval::evaluator::Evaluator,
+                &'a pana_lang::eval::environment::RcEnvironment,
+            ) -> Tarea<'a, Result<(), String>>
+            + 'static,
+    ) -> bool {
+        let Some(corrutina) = self.corrutina.as_mut() else {
+            return true;
+        };
+        let Some(mut evaluator) = self.evaluator.take() else {
+            return true;
+        };
+        let environment = self.environment.clone();
+        let estado = corrutina.start(Box::pin(async move {
+            let result = job(&mut evaluator, &environment).await;
+            (evaluator, etapa, result)
+        }));
+        self.finish(estado)
+    }
+
+    // Sigue con el programa detenido
+    fn resume(&mut self, reanudar: Reanudar) -> bool {
+        let Some(corrutina) = self.corrutina.as_mut() else {
+            return true;
+        };
+        let estado = corrutina.resume(reanudar);
+        self.finish(estado)
+    }
+
+    fn finish(&mut self, estado: Estado<Espera, Resultado>) -> bool {
+        match estado {
+            Estado::Pausa(espera) => {
+                self.espera = Some(espera);
+                false
+            }
+            Estado::Terminado((evaluator, etapa, result)) => {
+                self.espera = None;
+                match (result, etapa) {
+                    (Err(msg), Etapa::Programa) => self.err_msg = msg,
+                    (result, _) => {
+                        if let Err(msg) = result {
+                            self.err_msg = msg;
+                        }
+                        self.evaluator = Some(evaluator);
+                    }
+                }
+                true
+            }
+        }
+    }
+
+    // Llama `AlTerminar` y suelta la corrutina. Un programa que quedo
+    // detenido se suelta sin seguir.
+    fn stop_program(&mut self) {
+        self.run(Etapa::Cuadro, |evaluator, environment| {
+            Box::pin(async move {
+                match evaluator
+                    .call_hook("AlTerminar", Vec::new(), environment)
+                    .await
+                {
+                    Some(res) => check(res),
+                    None => Ok(()),
+                }
+            })
+        });
+        self.espera = None;
+        self.corrutina = None;
+    }
+
+    // Prepara el evaluador y la corrutina, y ejecuta el codigo de nivel
+    // superior y `Inicio`
+    fn start_program(&mut self, painter: &egui::Painter, canvas_rect: egui::Rect) {
+        let lexer = pana_lang::lexer::Lexer::new(self.code.chars().collect());
+        let mut parser = pana_lang::parser::Parser::new(lexer);
+        let mut program = parser.parse();
+
+        // Soltar la corrutina anterior termina el programa que quedo detenido
+        self.espera = None;
+        self.corrutina = None;
+        self.shown_image = None;
+        self.loop_fn = Rc::default();
+        self.environment = Rc::new(RefCell::new(
+            pana_lang::eval::environment::Environment::new(None),
+        ));
+        self.grabadora = pana_lang::lienzo::grabadora::Grabadora::new();
+
+        let mut evaluator = pana_lang::eval::evaluator::Evaluator::new(
+            None,
+            canvas_rect.width(),
+            canvas_rect.height(),
+        );
+        prepare_frame(
+            &mut evaluator,
+            frame_lienzo(painter.clone(), canvas_rect),
+            (canvas_rect.width(), canvas_rect.height()),
+            &self.grabadora,
+        );
+        evaluator.datos = self.datos.get(&self.datos_key).cloned();
+        #[cfg(target_arch = "wasm32")]
+        {
+            evaluator.reloj = Some(Box::new(crate::reloj::RelojNavegador));
+        }
+        let (corrutina, suspension) = Corrutina::new();
+        let anfitrion = Anfitrion::new(suspension, self.grabadora.clone());
+        evaluator.entrada = Some(Box::new(anfitrion.clone()));
+        evaluator.cuadros = Some(Box::new(anfitrion));
+        self.corrutina = Some(corrutina);
+
+        if let Some(error) = parser.error {
+            self.err_msg = error.to_string();
+            return;
+        }
+
+        if let Ok(loop_fn) = evaluator.extract_loop_fn(&mut program) {
+            self.loop_fn = Rc::new(loop_fn);
+        } else {
+            self.err_msg = "No se encontro la funcion `Bucle`".to_string();
+            return;
+        }
+
+        self.evaluator = Some(evaluator);
+        self.run(Etapa::Programa, move |evaluator, environment| {
+            Box::pin(async move {
+                check(evaluator.eval_program(&program, environment).await)?;
+                match evaluator.call_hook("Inicio", Vec::new(), environment).await {
+                    Some(res) => check(res),
+                    None => Ok(()),
+                }
+            })
+        });
+    }
+
+    // Un cuadro de `Bucle`, con los eventos de la ventana antes
+    fn run_frame(
+        &mut self,
+        ctx: &egui::Context,
+        response: &egui::Response,
+        painter: &egui::Painter,
+    ) {
+        let canvas_rect = response.rect;
+        let Some(evaluator) = self.evaluator.as_mut() else {
+            return;
+        };
+
+        let click = response
+            .clicked()
+            .then(|| response.interact_pointer_pos())
+            .flatten()
+            .map(|pos| match &evaluator.screen {
+                // En `modo_pantalla` el clic se traduce a pixeles de la pantalla
+                Some(screen) => {
+                    let (screen_rect, scale) =
+                        screen_layout(canvas_rect, screen.width, screen.height);
+                    (
+                        ((pos.x - screen_rect.left()) / scale).floor(),
+                        ((pos.y - screen_rect.top()) / scale).floor(),
+                    )
+                }
+                None => (pos.x - canvas_rect.left(), pos.y - canvas_rect.top()),
+            });
+
+        let keys: Vec<egui::Key> = ctx.input(|i| {
+            i.events
+                .iter()
+                .filter_map(|event| match event {
+                    egui::Event::Key {
+                        key,
+                        pressed: true,
+                        repeat: false,
+                        ..
+                    } => Some(*key),
+                    _ => None,
+                })
+                .collect()
+        });
+
+        evaluator.begin_frame(ctx.input(|i| i.unstable_dt) as f64);
+        prepare_frame(
+            evaluator,
+            frame_lienzo(painter.clone(), canvas_rect),
+            (canvas_rect.width(), canvas_rect.height()),
+            &self.grabadora,
+        );
+
+        // Los eventos y el cuadro corren juntos, asi una pausa en cualquiera
+        // de ellos detiene el resto hasta que el programa siga
+        let loop_fn = self.loop_fn.clone();
+        self.run(Etapa::Cuadro, move |evaluator, environment| {
+            Box::pin(async move {
+                let mut result = Ok(());
+                if let Some((x, y)) = click {
+                    let arguments = [x, y]
+                        .map(|n| {
+                            pana_lang::eval::objects::ResultObj::Copy(
+                                pana_lang::eval::objects::Object::Numeric(
+                                    pana_lang::types::Numeric::Float(n as f64),
+                                ),
+                            )
+                        })
+                        .to_vec();
+                    if let Some(res) = evaluator
+                        .call_hook("AlHacerClic", arguments, environment)
+                        .await
+                    {
+                        result = result.and(check(res));
+                    }
+                }
+                for key in keys {
+                    let arguments = vec![pana_lang::eval::objects::ResultObj::Copy(
+                        pana_lang::eval::objects::Object::String(key_name(key)),
+                    )];
+                    if let Some(res) = evaluator
+                        .call_hook("AlPresionarTecla", arguments, environment)
+                        .await
+                    {
+                        result = result.and(check(res));
+                    }
+                }
+
+                let env = Rc::new(RefCell::new(
+                    pana_lang::eval::environment::Environment::new(Some(environment.clone())),
+                ));
+                result = check(evaluator.eval_program(&loop_fn, &env).await).and(result);
+                evaluator.draw_turtle();
+                result
+            })
+        });
+    }
+
+    // Caja de texto de `preguntar`. Al aceptar, el programa sigue desde donde
+    // se detuvo.
+    fn question_box(&mut self, ctx: &egui::Context, question: &str) {
+        let mut accepted = false;
+        egui::Window::new("Pregunta")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                if !question.is_empty() {
+                    ui.label(question);
+                }
+                let response = ui.text_edit_singleline(&mut self.answer);
+                let enter = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                response.request_focus();
+                accepted = ui.button("Aceptar").clicked() || enter;
+            });
+        if accepted {
+            let answer = std::mem::take(&mut self.answer);
+            self.resume(Reanudar::Respuesta(answer));
+        }
+    }
+
+    // Mientras el programa espera una respuesta se sigue viendo el ultimo cuadro
+    fn paint_last_image(&self, painter: &egui::Painter, canvas_rect: egui::Rect) {
+        let (Some(texture), Some((rect, border))) = (&self.framebuffer_texture, self.shown_image)
+        else {
+            return;
+        };
+        if let Some(border) = border {
+            painter.rect_filled(canvas_rect, egui::Rounding::ZERO, border);
+        }
+        let uv = egui::Rect::from_min_max(egui::Pos2::ZERO, egui::Pos2::new(1.0, 1.0));
+        painter.image(texture.id(), rect, uv, Color32::WHITE);
+    }
+
+    // Sube los pixeles del cuadro a la textura
+    fn upload_framebuffer(
+        &mut self,
+        ctx: &egui::Context,
+        framebuffer: &pana_lang::lienzo::rasterizador::Rasterizador,
+    ) -> egui::TextureId {
+        let image = egui::ColorImage::from_rgba_unmultiplied(
+            [framebuffer.width(), framebuffer.height()],
+            &framebuffer.pixels(),
+        );
+        let texture = match &mut self.framebuffer_texture {
+            Some(texture) => {
+                texture.set(image, egui::TextureOptions::NEAREST);
+                texture
+            }
+            None => self.framebuffer_texture.insert(ctx.load_texture(
+                "framebuffer",
+                image,
+                egui::TextureOptions::NEAREST,
+            )),
+        };
+        texture.id()
+    }
+
+    // Muestra los pixeles del cuadro en `slot`, debajo de las figuras, y los
+    // guarda en la captura o la animacion. En `modo_pantalla` los pixeles
+    // incluyen la capa de texto y se muestran sobre el color del borde.
+    fn present(
+        &mut self,
+        ctx: &egui::Context,
+        canvas_rect: egui::Rect,
+        painter: &egui::Painter,
+        slot: egui::layers::ShapeIdx,
+    ) {
+        // El cursor de texto parpadea dos veces por segundo
+        let blink_on = (ctx.input(|i| i.time) * 2.0) as i64 % 2 == 0;
+        let (screen, pixels) = match (&self.espera, &self.evaluator) {
+            (Some(Espera::Cuadro(vista)), _) => (vista.screen, vista.pixels.clone()),
+            (None, Some(evaluator)) => (
+                evaluator
+                    .screen
+                    .as_ref()
+                    .map(|screen| (screen.width, screen.height, screen.border)),
+                evaluator
+                    .screen_frame(blink_on)
+                    .or_else(|| evaluator.window_frame(blink_on)),
+            ),
+            _ => return,
+        };
+        self.canvas_size = match screen {
+            Some((width, height, _)) => (width, height),
+            None => (
+                canvas_rect.width().max(1.0) as usize,
+                canvas_rect.height().max(1.0) as usize,
+            ),
+        };
+
+        // La captura y la grabacion necesitan la imagen del cuadro. En el modo
+        // ventana las figuras grabadas se componen sobre los pixeles.
+        let needs_frame = self.capture_requested || self.recording.is_some();
+        let frame = needs_frame.then(|| match (screen, &pixels) {
+            (Some(_), Some(pixels)) => pixels.clone(),
+            _ => pana_lang::lienzo::rasterizador::compose_frame(
+                self.canvas_size.0,
+                self.canvas_size.1,
+                pixels.as_ref(),
+                &self.grabadora,
+            ),
+        });
+
+        self.shown_image = None;
+        if let Some(pixels) = &pixels {
+            let texture = self.upload_framebuffer(ctx, pixels);
+            let uv = egui::Rect::from_min_max(egui::Pos2::ZERO, egui::Pos2::new(1.0, 1.0));
+            let shape = match screen {
+                // Pantalla fija escalada y centrada sobre el color del borde
+                Some((width, height, border)) => {
+                    let border =
+                        Color32::from_rgba_unmultiplied(border.r, border.g, border.b, border.a);
+                    let (screen_rect, _) = screen_layout(canvas_rect, width, height);
+                    self.shown_image = Some((screen_rect, Some(border)));
+                    egui::Shape::Vec(vec![
+                        egui::Shape::rect_filled(canvas_rect, egui::Rounding::ZERO, border),
+                        egui::Shape::image(texture, screen_rect, uv, Color32::WHITE),
+                    ])
+                }
+                None => {
+                    let rect = egui::Rect::from_min_size(
+                        canvas_rect.min,
+                        egui::Vec2::new(pixels.width() as f32, pixels.height() as f32),
+                    );
+                    self.shown_image = Some((rect, None));
+                    egui::Shape::image(texture, rect, uv, Color32::WHITE)
+                }
+            };
+            painter.set(slot, shape);
+        }
+
+        let capture = std::mem::take(&mut self.capture_requested);
+        if let (Some(frame), true) = (&frame, capture) {
+            self.status_msg = save_capture(frame);
+        }
+        if let (Some(frame), Some(animation)) = (&frame, self.recording.as_mut()) {
+            // Los cuadros de otro tamano no caben en la animacion
+            if (animation.width(), animation.height()) == (frame.width(), frame.height()) {
+                animation.push_frame(frame);
+            }
+            self.status_msg = format!(
+                "Grabando cuadro {} de {}",
+                animation.frames(),
+                self.gif_frames
+            );
+            if animation.frames() >= self.gif_frames {
+                if let Some(animation) = self.recording.take() {
+                    self.status_msg = save_gif(animation);
+                }
+            }
+        }
+    }
+
     fn canvas(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         if !self.err_msg.is_empty() {
             ui.label(
@@ -105,64 +579,136 @@ impl App {
             );
         }
         ctx.request_repaint_after(Duration::from_millis(16)); //60fps
-        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::drag());
+        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
         let canvas_rect = response.rect;
-        if self.first_run {
-            let lexer = pana_lang::lexer::Lexer::new(self.code.chars().collect());
-            let mut parser = pana_lang::parser::Parser::new(lexer);
-            let mut program = parser.parse();
+        // Lugar reservado debajo de las figuras para los pixeles
+        let pixels_slot = painter.add(egui::Shape::Noop);
 
-            self.loop_fn.clear();
-            self.environment = Rc::new(RefCell::new(
-                pana_lang::eval::environment::Environment::new(None),
-            ));
-
-            self.evaluator = Some(pana_lang::eval::evaluator::Evaluator::new(
-                Some(painter),
-                canvas_rect.width(),
-                canvas_rect.width(),
-                canvas_rect.top(),
-            ));
+        // Un programa detenido en `esperar` sigue en este cuadro. Si se acaba
+        // de ejecutar, primero muestra el cuadro con el que se detuvo.
+        let paused = matches!(self.espera, Some(Espera::Cuadro(_)));
+        if self.first_run {
+            self.first_run = false;
+            self.start_program(&painter, canvas_rect);
+        }
 
-            if let Some(error) = parser.error {
-                self.err_msg = error.to_string();
+        match &self.espera {
+            Some(Espera::Pregunta(question)) => {
+                let question = question.clone();
+                self.paint_last_image(&painter, canvas_rect);
+                self.question_box(ctx, &question);
                 return;
             }
-
-            let evaluator = self.evaluator.as_mut().unwrap();
-
-            if let Ok(loop_fn) = evaluator.extract_loop_fn(&mut program) {
-                self.loop_fn = loop_fn;
-            } else {
-                self.err_msg = "No se encontro la funcion `Bucle`".to_string();
-                return;
+            Some(Espera::Cuadro(_)) if paused => {
+                self.resume(Reanudar::Cuadro {
+                    lienzo: frame_lienzo(painter.clone(), canvas_rect),
+                    size: (canvas_rect.width(), canvas_rect.height()),
+                    seconds: ctx.input(|i| i.unstable_dt) as f64,
+                });
             }
+            Some(Espera::Cuadro(_)) => {}
+            None => self.run_frame(ctx, &response, &painter),
+        }
+        self.present(ctx, canvas_rect, &painter, pixels_slot);
+    }
+}
 
-            if let pana_lang::eval::objects::ResultObj::Copy(
-                pana_lang::eval::objects::Object::Error(msg),
-            ) = evaluator.eval_program(&program, &self.environment)
-            {
-                self.err_msg = msg;
-                return;
-            }
-            self.first_run = false;
+fn check(res: pana_lang::eval::objects::ResultObj) -> Result<(), String> {
+    match res {
+        pana_lang::eval::objects::ResultObj::Copy(pana_lang::eval::objects::Object::Error(msg)) => {
+            Err(msg)
         }
+        _ => Ok(()),
+    }
+}
 
-        let env = Rc::new(RefCell::new(
-            pana_lang::eval::environment::Environment::new(Some(self.environment.clone())),
-        ));
+// Area donde se muestra la pantalla fija: el mayor multiplo entero que cabe,
+// centrado en el lienzo. Retorna el area y la escala.
+fn screen_layout(rect: egui::Rect, width: usize, height: usize) -> (egui::Rect, f32) {
+    let scale = (rect.width() / width as f32)
+        .min(rect.height() / height as f32)
+        .floor()
+        .max(1.0);
+    let size = egui::Vec2::new(width as f32 * scale, height as f32 * scale);
+    (egui::Rect::from_center_size(rect.center(), size), scale)
+}
+
+// Guarda un archivo junto al ejecutable y retorna el mensaje para la barra superior
+#[cfg(not(target_arch = "wasm32"))]
+fn save_file(prefix: &str, extension: &str, bytes: Vec<u8>) -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let path = format!("{}_{}.{}", prefix, secs, extension);
+    match std::fs::write(&path, bytes) {
+        Ok(()) => format!("Guardado en {}", path),
+        Err(err) => format!("No se pudo guardar {}: {}", path, err),
+    }
+}
 
-        let evaluator = self.evaluator.as_mut().unwrap();
-        evaluator.canvas.width = canvas_rect.width();
-        evaluator.canvas.height = canvas_rect.height();
-        evaluator.canvas.top = canvas_rect.top();
+#[cfg(target_arch = "wasm32")]
+fn save_file(_prefix: &str, _extension: &str, _bytes: Vec<u8>) -> String {
+    "Guardar archivos no esta disponible en el navegador".to_owned()
+}
 
-        if let pana_lang::eval::objects::ResultObj::Copy(pana_lang::eval::objects::Object::Error(
-            msg,
-        )) = evaluator.eval_program(&self.loop_fn, &env)
-        {
-            self.err_msg = msg;
-        }
+fn save_capture(rasterizador: &pana_lang::lienzo::rasterizador::Rasterizador) -> String {
+    save_file("captura", "png", rasterizador.to_png())
+}
+
+fn save_gif(animation: pana_lang::lienzo::gif::AnimacionGif) -> String {
+    save_file("animacion", "gif", animation.finish())
+}
+
+// Lienzo donde dibuja el programa en este cuadro
+#[cfg(feature = "egui_lienzo")]
+fn frame_lienzo(
+    painter: egui::Painter,
+    rect: egui::Rect,
+) -> Option<Box<dyn pana_lang::lienzo::Lienzo>> {
+    Some(Box::new(crate::egui_lienzo::EguiLienzo::new(painter, rect)))
+}
+
+#[cfg(not(feature = "egui_lienzo"))]
+fn frame_lienzo(
+    _painter: egui::Painter,
+    _rect: egui::Rect,
+) -> Option<Box<dyn pana_lang::lienzo::Lienzo>> {
+    None
+}
+
+// Nombre de la tecla que recibe `AlPresionarTecla`
+// Llave de los datos de `guardar_dato` del programa. Su nombre es el comentario
+// de la primera linea, como `# Serpiente`, asi los datos siguen al programa
+// aunque se edite el resto del codigo.
+fn datos_key(code: &str) -> String {
+    let name = code
+        .lines()
+        .next()
+        .and_then(|line| line.trim().strip_prefix('#'))
+        .map_or("", |name| name.trim_start_matches('#').trim());
+    if name.is_empty() {
+        return DATOS_KEY.to_owned();
+    }
+    // FNV-1a, que a diferencia de `DefaultHasher` no cambia entre versiones
+    let hash = name.bytes().fold(0xcbf29ce484222325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{}_{:016x}", DATOS_KEY, hash)
+}
+
+fn key_name(key: egui::Key) -> String {
+    match key {
+        egui::Key::ArrowUp => "arriba".to_owned(),
+        egui::Key::ArrowDown => "abajo".to_owned(),
+        egui::Key::ArrowLeft => "izquierda".to_owned(),
+        egui::Key::ArrowRight => "derecha".to_owned(),
+        egui::Key::Space => "espacio".to_owned(),
+        egui::Key::Enter => "enter".to_owned(),
+        egui::Key::Escape => "escape".to_owned(),
+        egui::Key::Backspace => "borrar".to_owned(),
+        egui::Key::Tab => "tab".to_owned(),
+        _ => key.name().to_lowercase(),
     }
 }
 
@@ -171,9 +717,16 @@ impl eframe::App for App {
     // fn save(&mut self, storage: &mut dyn eframe::Storage) {
     //     eframe::set_value(storage, eframe::APP_KEY, self);
     // }
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        for (key, datos) in &self.datos {
+            if datos.changed() {
+                storage.set_string(key, datos.to_json());
+            }
+        }
+    }
 
     /// Called each time the UI needs repainting, which may be many times per second.
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         if self.show_manual {
             egui::Window::new("Manual")
                 .open(&mut self.show_manual)
@@ -195,14 +748,55 @@ impl eframe::App for App {
                 match self.view {
                     Views::Editor => {
                         if ui.button("Ejecutar").clicked() {
+                            self.load_datos(frame.storage());
                             self.view = Views::Canvas;
                             self.first_run = true;
+                            self.status_msg.clear();
                         }
                     }
                     Views::Canvas => {
+                        if ui.button("Capturar pantalla").clicked() {
+                            self.capture_requested = true;
+                        }
+                        match self.recording.take() {
+                            Some(animation) => {
+                                if ui.button("Detener GIF").clicked() {
+                                    self.status_msg = save_gif(animation);
+                                } else {
+                                    self.recording = Some(animation);
+                                }
+                            }
+                            None => {
+                                if ui.button("Grabar GIF").clicked() {
+                                    match pana_lang::lienzo::gif::AnimacionGif::new(
+                                        self.canvas_size.0,
+                                        self.canvas_size.1,
+                                        pana_lang::lienzo::gif::FRAME_DELAY_CS,
+                                    ) {
+                                        Ok(animation) => self.recording = Some(animation),
+                                        Err(msg) => self.status_msg = msg,
+                                    }
+                                }
+                                ui.add(
+                                    egui::DragValue::new(&mut self.gif_frames)
+                                        .clamp_range(1..=600)
+                                        .suffix(" cuadros"),
+                                );
+                            }
+                        }
                         if ui.button("Codigo").clicked() {
                             self.view = Views::Editor;
                             self.err_msg.clear();
+                            if let Some(animation) = self.recording.take() {
+                                self.status_msg = save_gif(animation);
+                            }
+                            if !self.first_run {
+                                self.stop_program();
+                                if !self.err_msg.is_empty() {
+                                    log::error!("{}", self.err_msg);
+                                    self.err_msg.clear();
+                                }
+                            }
                         }
                     }
                 }
@@ -210,6 +804,9 @@ impl eframe::App for App {
                     self.show_manual = true;
                 }
                 ui.add_space(16.0);
+                if !self.status_msg.is_empty() {
+                    ui.label(&self.status_msg);
+                }
             })
         });
 
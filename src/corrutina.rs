@@ -0,0 +1,87 @@
+use std::{cell::RefCell, future::poll_fn, rc::Rc, task::Poll};
+
+use pana_lang::eval::tarea::{self, Tarea};
+
+// Como quedo el programa al devolver el control a la aplicacion
+pub enum Estado<P, T> {
+    // Se detuvo con `P` y espera que la aplicacion lo reanude
+    Pausa(P),
+    Terminado(T),
+}
+
+// Lo que se pasan el programa y la aplicacion en cada pausa
+struct Turno<P, R> {
+    pausa: Option<P>,
+    respuesta: Option<R>,
+}
+
+// Ejecuta el programa como una tarea que se puede detener a mitad de una
+// funcion, por ejemplo en `preguntar` o `esperar`, y seguir en un cuadro
+// posterior. `P` es lo que entrega el programa al detenerse, `R` lo que
+// recibe al reanudarse y `T` lo que retorna al terminar.
+pub struct Corrutina<P, R, T> {
+    turno: Rc<RefCell<Turno<P, R>>>,
+    task: Option<Tarea<'static, T>>,
+}
+
+// Lado del programa: se usa desde dentro de la corrutina para detenerla
+pub struct Suspension<P, R> {
+    turno: Rc<RefCell<Turno<P, R>>>,
+}
+
+impl<P, R> Suspension<P, R> {
+    // Devuelve el control a la aplicacion hasta que esta responde. Si la
+    // aplicacion abandona el programa, la tarea se suelta sin seguir.
+    pub async fn suspend(&self, pausa: P) -> R {
+        self.turno.borrow_mut().pausa = Some(pausa);
+        poll_fn(|_| match self.turno.borrow_mut().respuesta.take() {
+            Some(respuesta) => Poll::Ready(respuesta),
+            None => Poll::Pending,
+        })
+        .await
+    }
+}
+
+impl<P, R, T> Corrutina<P, R, T> {
+    pub fn new() -> (Self, Suspension<P, R>) {
+        let turno = Rc::new(RefCell::new(Turno {
+            pausa: None,
+            respuesta: None,
+        }));
+        let suspension = Suspension {
+            turno: turno.clone(),
+        };
+        let corrutina = Self { turno, task: None };
+        (corrutina, suspension)
+    }
+
+    // Corre `task` hasta que termina o se detiene. Un programa que seguia
+    // detenido se suelta.
+    pub fn start(&mut self, task: Tarea<'static, T>) -> Estado<P, T> {
+        self.task = Some(task);
+        self.step()
+    }
+
+    // Sigue con el programa detenido
+    pub fn resume(&mut self, respuesta: R) -> Estado<P, T> {
+        self.turno.borrow_mut().respuesta = Some(respuesta);
+        self.step()
+    }
+
+    fn step(&mut self) -> Estado<P, T> {
+        let Some(task) = self.task.as_mut() else {
+            panic!("La corrutina no tiene programa que seguir");
+        };
+        match tarea::poll(task.as_mut()) {
+            Poll::Ready(result) => {
+                self.task = None;
+                Estado::Terminado(result)
+            }
+            // Las tareas del programa solo se detienen en `suspend`
+            Poll::Pending => match self.turno.borrow_mut().pausa.take() {
+                Some(pausa) => Estado::Pausa(pausa),
+                None => panic!("La corrutina se detuvo sin pausa"),
+            },
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use pana_lang::lienzo::{formas::triangulate, Color, Lienzo, Rectangulo};
+
+// Implementacion del lienzo de pana_lang sobre un `egui::Painter`
+pub struct EguiLienzo {
+    painter: egui::Painter,
+    rect: egui::Rect,
+}
+
+impl EguiLienzo {
+    pub fn new(painter: egui::Painter, rect: egui::Rect) -> Self {
+        Self { painter, rect }
+    }
+
+    fn pos(&self, (x, y): (f32, f32)) -> egui::Pos2 {
+        egui::Pos2::new(x + self.rect.left(), y + self.rect.top())
+    }
+}
+
+fn color32(color: Color) -> egui::Color32 {
+    egui::Color32::from_rgba_unmultiplied(color.r, color.g, color.b, color.a)
+}
+
+impl Lienzo for EguiLienzo {
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32, color: Color) {
+        self.painter.line_segment(
+            [self.pos(from), self.pos(to)],
+            egui::Stroke::new(width, color32(color)),
+        );
+    }
+
+    fn rect_filled(&mut self, min: (f32, f32), max: (f32, f32), color: Color) {
+        self.painter.rect_filled(
+            egui::Rect::from_two_pos(self.pos(min), self.pos(max)),
+            egui::Rounding::ZERO,
+            color32(color),
+        );
+    }
+
+    fn circle_filled(&mut self, center: (f32, f32), radius: f32, color: Color) {
+        self.painter
+            .circle_filled(self.pos(center), radius, color32(color));
+    }
+
+    fn polygon_filled(&mut self, points: &[(f32, f32)], color: Color) {
+        // egui solo rellena poligonos convexos, asi que se arma una malla
+        let mut mesh = egui::Mesh::default();
+        for &point in points {
+            mesh.colored_vertex(self.pos(point), color32(color));
+        }
+        for [a, b, c] in triangulate(points) {
+            mesh.add_triangle(a as u32, b as u32, c as u32);
+        }
+        self.painter.add(egui::Shape::mesh(mesh));
+    }
+
+    fn polyline(&mut self, points: &[(f32, f32)], closed: bool, width: f32, color: Color) {
+        let points = points.iter().map(|&point| self.pos(point)).collect();
+        let stroke = egui::Stroke::new(width, color32(color));
+        self.painter.add(match closed {
+            true => egui::Shape::closed_line(points, stroke),
+            false => egui::Shape::line(points, stroke),
+        });
+    }
+
+    fn text(&mut self, text: &str, pos: (f32, f32), font_size: f32, color: Color) {
+        let galley = self.painter.layout(
+            text.to_owned(),
+            egui::FontId::monospace(font_size),
+            color32(color),
+            self.rect.width(),
+        );
+        self.painter.galley(self.pos(pos), galley);
+    }
+
+    // Todos los rectangulos van en una sola malla, asi un mapa grande no
+    // agrega una figura por tile
+    fn rects_filled(&mut self, rects: &[Rectangulo]) {
+        let mut mesh = egui::Mesh::default();
+        mesh.reserve_triangles(rects.len() * 2);
+        mesh.reserve_vertices(rects.len() * 4);
+        for rect in rects {
+            mesh.add_colored_rect(
+                egui::Rect::from_two_pos(self.pos(rect.min), self.pos(rect.max)),
+                color32(rect.color),
+            );
+        }
+        self.painter.add(egui::Shape::mesh(mesh));
+    }
+}
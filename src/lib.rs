@@ -1,5 +1,11 @@
 #![warn(rust_2018_idioms)]
 
 mod app;
+mod corrutina;
+#[cfg(feature = "egui_lienzo")]
+mod egui_lienzo;
 mod pana_syntax;
+mod pausa;
+#[cfg(target_arch = "wasm32")]
+mod reloj;
 pub use app::App;
@@ -17,9 +17,115 @@ pub fn pana_syntax() -> Syntax {
             "sino",
             "nulo",
             "fn",
-            "var"
+            "var",
         ]),
         types: HashSet::from([]),
-        special: HashSet::from(["Bucle"]),
+        special: HashSet::from([
+            // Funciones que llama la aplicacion
+            "Bucle",
+            "Inicio",
+            "AlTerminar",
+            "AlHacerClic",
+            "AlPresionarTecla",
+            // Funciones internas
+            "longitud",
+            "tipo",
+            "documentacion",
+            "dibujar_texto",
+            "dibujar_linea",
+            "dibujar_rectangulo",
+            "dibujar_circulo",
+            "lienzo_ancho",
+            "lienzo_altura",
+            "aleatorio",
+            "cadena",
+            "seno",
+            "coseno",
+            "tangente",
+            "raiz",
+            "potencia",
+            "abs",
+            "piso",
+            "techo",
+            "redondear",
+            "min",
+            "max",
+            "limitar",
+            "interpolar",
+            "modo_pantalla",
+            "color",
+            "color_borde",
+            "poner_pixel",
+            "obtener_pixel",
+            "limpiar_pantalla",
+            "ubicar",
+            "escribir",
+            "color_texto",
+            "caracter_en",
+            "mostrar_cursor",
+            "avanzar",
+            "retroceder",
+            "girar",
+            "subir_lapiz",
+            "bajar_lapiz",
+            "color_lapiz",
+            "grosor",
+            "velocidad_tortuga",
+            "mostrar_tortuga",
+            "reiniciar_tortuga",
+            "dibujar_elipse",
+            "dibujar_arco",
+            "dibujar_triangulo",
+            "dibujar_poligono",
+            "relleno",
+            "sin_relleno",
+            "trazo",
+            "sin_trazo",
+            "redondeo",
+            "estilo_inicial",
+            "trasladar",
+            "rotar",
+            "escalar",
+            "guardar_estado",
+            "restaurar_estado",
+            "rgb",
+            "rgba",
+            "hsv",
+            "mezclar_color",
+            "preguntar",
+            "leer_linea",
+            "esperar",
+            "siguiente_cuadro",
+            "guardar_dato",
+            "cargar_dato",
+            "a_json",
+            "desde_json",
+            "ahora",
+            "marca_tiempo",
+            "formatear_fecha",
+            "vector",
+            "distancia",
+            "angulo_entre",
+            "punto_en_rectangulo",
+            "colisionan_rectangulos",
+            "colisionan_circulos",
+            "crear_mapa",
+            "poner_tile",
+            "obtener_tile",
+            "dibujar_mapa",
+            "tile_en",
+            "colisiona_mapa",
+            "camara_posicion",
+            "camara_zoom",
+            "camara_seguir",
+            "usar_camara",
+            "camara_a_mundo",
+            "crear_emisor",
+            "cambiar_emisor",
+            "mover_emisor",
+            "emitir",
+            "dibujar_emisor",
+            "cantidad_particulas",
+        ]),
     }
 }
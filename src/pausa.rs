@@ -0,0 +1,135 @@
+use std::rc::Rc;
+
+use pana_lang::{
+    eval::{cuadros::Cuadros, entrada::Entrada, evaluator::Evaluator, tarea::Tarea},
+    lienzo::{grabadora::Grabadora, rasterizador::Rasterizador, Color, Lienzo},
+};
+
+use crate::corrutina::Suspension;
+
+// Por que se detuvo el programa
+pub enum Espera {
+    // `preguntar` o `leer_linea`
+    Pregunta(String),
+    // `esperar` o `siguiente_cuadro`, con lo que se ve del cuadro terminado
+    Cuadro(Vista),
+}
+
+// Lo que recibe el programa al seguir
+pub enum Reanudar {
+    Respuesta(String),
+    Cuadro {
+        lienzo: Option<Box<dyn Lienzo>>,
+        size: (f32, f32),
+        // Segundos desde el cuadro anterior
+        seconds: f64,
+    },
+}
+
+// Pixeles del cuadro con el que se detuvo el programa. Las figuras del modo
+// ventana ya quedaron en el lienzo y en la grabadora de la aplicacion.
+pub struct Vista {
+    // Ancho, alto y borde en `modo_pantalla`
+    pub screen: Option<(usize, usize, Color)>,
+    pub pixels: Option<Rasterizador>,
+}
+
+impl Vista {
+    fn new(evaluator: &Evaluator) -> Self {
+        Self {
+            screen: evaluator
+                .screen
+                .as_ref()
+                .map(|screen| (screen.width, screen.height, screen.border)),
+            pixels: evaluator
+                .screen_frame(true)
+                .or_else(|| evaluator.window_frame(true).map(|frame| frame.snapshot())),
+        }
+    }
+}
+
+// Prepara el lienzo del cuadro. En `modo_pantalla` se dibuja en el bufer de la
+// pantalla, que conserva lo dibujado. En el modo ventana las figuras van al
+// lienzo de la ventana y a la grabadora, para las capturas y las pausas.
+pub fn prepare_frame(
+    evaluator: &mut Evaluator,
+    lienzo: Option<Box<dyn Lienzo>>,
+    size: (f32, f32),
+    grabadora: &Grabadora,
+) {
+    grabadora.limpiar();
+    if evaluator.screen.is_some() {
+        evaluator.lienzo = Some(Box::new(evaluator.ensure_framebuffer()));
+        return;
+    }
+    evaluator.canvas.width = size.0;
+    evaluator.canvas.height = size.1;
+    evaluator.lienzo = match lienzo {
+        Some(lienzo) => Some(Box::new((lienzo, grabadora.clone()))),
+        None => Some(Box::new(grabadora.clone())),
+    };
+}
+
+// `Entrada` y `Cuadros` del programa que corre en la corrutina: cada pausa
+// devuelve el control a la aplicacion hasta que esta lo reanuda.
+#[derive(Clone)]
+pub struct Anfitrion {
+    suspension: Rc<Suspension<Espera, Reanudar>>,
+    grabadora: Grabadora,
+}
+
+impl Anfitrion {
+    pub fn new(suspension: Suspension<Espera, Reanudar>, grabadora: Grabadora) -> Self {
+        Self {
+            suspension: Rc::new(suspension),
+            grabadora,
+        }
+    }
+}
+
+impl Entrada for Anfitrion {
+    fn leer_linea<'a>(&'a mut self, pregunta: &'a str) -> Tarea<'a, Option<String>> {
+        Box::pin(async move {
+            match self
+                .suspension
+                .suspend(Espera::Pregunta(pregunta.to_owned()))
+                .await
+            {
+                Reanudar::Respuesta(answer) => Some(answer),
+                Reanudar::Cuadro { .. } => None,
+            }
+        })
+    }
+}
+
+impl Cuadros for Anfitrion {
+    fn siguiente_cuadro<'a>(
+        &'a mut self,
+        evaluator: &'a mut Evaluator,
+        keep: bool,
+    ) -> Tarea<'a, Option<f64>> {
+        Box::pin(async move {
+            // Las figuras de este cuadro se repiten en el siguiente, sin la
+            // tortuga que se vuelve a dibujar al final de cada cuadro
+            let previous = Grabadora::new();
+            if keep {
+                self.grabadora.replay(&mut previous.clone());
+            }
+            evaluator.draw_turtle();
+            let vista = Vista::new(evaluator);
+            let Reanudar::Cuadro {
+                lienzo,
+                size,
+                seconds,
+            } = self.suspension.suspend(Espera::Cuadro(vista)).await
+            else {
+                return None;
+            };
+            prepare_frame(evaluator, lienzo, size, &self.grabadora);
+            if let Some(lienzo) = evaluator.lienzo.as_mut() {
+                previous.replay(lienzo);
+            }
+            Some(seconds)
+        })
+    }
+}
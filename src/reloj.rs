@@ -0,0 +1,15 @@
+use pana_lang::eval::reloj::Reloj;
+
+// En el navegador `SystemTime` no existe, la hora sale de `Date` de JavaScript.
+pub struct RelojNavegador;
+
+impl Reloj for RelojNavegador {
+    fn marca_tiempo(&self) -> f64 {
+        js_sys::Date::now() / 1000.0
+    }
+
+    // `getTimezoneOffset` da los minutos de la hora local a UTC, al reves del desfase
+    fn desfase(&self) -> i64 {
+        -(js_sys::Date::new_0().get_timezone_offset() * 60.0) as i64
+    }
+}